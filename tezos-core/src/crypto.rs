@@ -1,11 +1,57 @@
 //! Crypto module exposed the [CryptoProvider] trait and a default implementation.
 
 pub mod default;
+#[cfg(feature = "encryption")]
+mod encryption;
+mod hd;
+mod key_pair;
+#[cfg(feature = "mnemonic")]
+mod mnemonic;
+#[cfg(feature = "vanity")]
+mod vanity;
 
-use crate::Result;
+pub use self::hd::{DerivationPath, ExtendedSecretKey};
+pub use self::key_pair::KeyPair;
+#[cfg(feature = "mnemonic")]
+pub use self::mnemonic::Mnemonic;
+#[cfg(feature = "vanity")]
+pub use self::vanity::{VanityMatch, VanityPattern, VanitySearch, VanitySearchHandle};
+
+use alloc::vec::Vec;
+
+use crate::{Error, Result};
+
+/// Elliptic curves used by the Tezos implicit accounts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Curve {
+    /// The ed25519 curve used by the **tz1** accounts.
+    Ed25519,
+    /// The secp256k1 curve used by the **tz2** accounts.
+    Secp256K1,
+    /// The p256 (secp256r1) curve used by the **tz3** accounts.
+    P256,
+}
 
 /// Trait defining the interface of a crypto provider.
 pub trait CryptoProvider {
     fn sign(&self, message: &[u8], secret: &[u8]) -> Result<Vec<u8>>;
     fn verify(&self, message: &[u8], signature: &[u8], public_key: &[u8]) -> Result<bool>;
+    /// Derives the public key from the secret key. Providers which can't do it keep the default
+    /// implementation, which fails with [Error::PublicKeyDerivationNotSupported].
+    fn public_key(&self, _secret: &[u8]) -> Result<Vec<u8>> {
+        Err(Error::PublicKeyDerivationNotSupported)
+    }
+}
+
+/// Trait defining the interface of a BLS12-381 crypto provider, which additionally supports signature aggregation.
+pub trait BlsCryptoProvider: CryptoProvider {
+    /// Aggregates the given signatures into a single signature.
+    fn aggregate_signatures(&self, signatures: &[&[u8]]) -> Result<Vec<u8>>;
+    /// Verifies an aggregated signature, where the message at each index has been signed by the public key at the same index.
+    fn aggregate_verify(
+        &self,
+        messages: &[&[u8]],
+        signature: &[u8],
+        public_keys: &[&[u8]],
+    ) -> Result<bool>;
 }
@@ -1,4 +1,17 @@
-#[cfg(any(feature = "ed25519", feature = "secp256_k1", feature = "p256"))]
+#[cfg(any(
+    feature = "ed25519",
+    feature = "secp256_k1",
+    feature = "p256",
+    feature = "bls"
+))]
+use alloc::vec::Vec;
+
+#[cfg(any(
+    feature = "ed25519",
+    feature = "secp256_k1",
+    feature = "p256",
+    feature = "bls"
+))]
 use crate::{CryptoProvider, Error, Result};
 
 /// Default implementation for the ed25519 crypto provider. It is activated by enabling the `ed25519` feature.
@@ -34,6 +47,18 @@ impl CryptoProvider for DefaultEd25519CryptoProvider {
 
         Ok(public_key.verify(message, &signature).is_ok())
     }
+
+    fn public_key(&self, secret: &[u8]) -> Result<Vec<u8>> {
+        use ed25519_dalek::{PublicKey, SecretKey, SECRET_KEY_LENGTH};
+
+        let seed = secret
+            .get(..SECRET_KEY_LENGTH)
+            .ok_or(Error::InvalidSecretKeyBytes)?;
+        let secret_key = SecretKey::from_bytes(seed).map_err(|_| Error::InvalidSecretKeyBytes)?;
+        let public_key: PublicKey = (&secret_key).into();
+
+        Ok(public_key.to_bytes().to_vec())
+    }
 }
 
 /// Default implementation for the secp256_k1 crypto provider. It is activated by enabling the `secp256_k1` feature.
@@ -53,7 +78,7 @@ impl CryptoProvider for DefaultSecp256K1CryptoProvider {
 
         let signature: k256::ecdsa::Signature = sk.sign(message);
 
-        Ok(signature.to_vec())
+        Ok(signature.as_ref().to_vec())
     }
 
     fn verify(&self, message: &[u8], signature: &[u8], public_key: &[u8]) -> Result<bool> {
@@ -65,6 +90,13 @@ impl CryptoProvider for DefaultSecp256K1CryptoProvider {
             .map_err(|_error| Error::InvalidSignatureBytes)?;
         Ok(vk.verify(message, &signature).is_ok())
     }
+
+    fn public_key(&self, secret: &[u8]) -> Result<Vec<u8>> {
+        let sk = k256::ecdsa::SigningKey::from_bytes(secret)
+            .map_err(|_error| Error::InvalidSecretKeyBytes)?;
+
+        Ok(sk.verifying_key().to_bytes().to_vec())
+    }
 }
 
 /// Default implementation for the p256 crypto provider. It is activated by enabling the `p256` feature.
@@ -84,7 +116,7 @@ impl CryptoProvider for DefaultP256CryptoProvider {
 
         let signature: p256::ecdsa::Signature = sk.sign(message);
 
-        Ok(signature.to_vec())
+        Ok(signature.as_ref().to_vec())
     }
 
     fn verify(&self, message: &[u8], signature: &[u8], public_key: &[u8]) -> Result<bool> {
@@ -96,11 +128,128 @@ impl CryptoProvider for DefaultP256CryptoProvider {
             .map_err(|_error| Error::InvalidSignatureBytes)?;
         Ok(vk.verify(message, &signature).is_ok())
     }
+
+    fn public_key(&self, secret: &[u8]) -> Result<Vec<u8>> {
+        let sk = p256::ecdsa::SigningKey::from_bytes(secret)
+            .map_err(|_error| Error::InvalidSecretKeyBytes)?;
+
+        Ok(sk
+            .verifying_key()
+            .to_encoded_point(true)
+            .as_bytes()
+            .to_vec())
+    }
+}
+
+/// Default implementation for the bls12_381 crypto provider. It is activated by enabling the `bls` feature.
+///
+/// This implementation internally uses [blst](https://github.com/supranational/blst) with the
+/// minimal-pubkey-size variant and the message augmentation scheme, as the Tezos protocol does.
+/// Secret keys are expected in the little-endian encoding used by Tezos.
+#[cfg(feature = "bls")]
+#[derive(Debug)]
+pub struct DefaultBls12381CryptoProvider;
+
+#[cfg(feature = "bls")]
+impl DefaultBls12381CryptoProvider {
+    const DST: &'static [u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_AUG_";
+
+    fn secret_key(secret: &[u8]) -> Result<blst::min_pk::SecretKey> {
+        let mut secret = zeroize::Zeroizing::new(secret.to_vec());
+        secret.reverse();
+
+        blst::min_pk::SecretKey::from_bytes(&secret).map_err(|_error| Error::InvalidSecretKeyBytes)
+    }
+
+    fn public_key_point(public_key: &[u8]) -> Result<blst::min_pk::PublicKey> {
+        blst::min_pk::PublicKey::key_validate(public_key)
+            .map_err(|_error| Error::InvalidPublicKeyBytes)
+    }
+
+    fn signature_point(signature: &[u8]) -> Result<blst::min_pk::Signature> {
+        blst::min_pk::Signature::from_bytes(signature)
+            .map_err(|_error| Error::InvalidSignatureBytes)
+    }
+}
+
+#[cfg(feature = "bls")]
+impl CryptoProvider for DefaultBls12381CryptoProvider {
+    fn sign(&self, message: &[u8], secret: &[u8]) -> Result<Vec<u8>> {
+        let sk = Self::secret_key(secret)?;
+        let public_key = sk.sk_to_pk().compress();
+
+        Ok(sk.sign(message, Self::DST, &public_key).compress().to_vec())
+    }
+
+    fn verify(&self, message: &[u8], signature: &[u8], public_key: &[u8]) -> Result<bool> {
+        let pk = Self::public_key_point(public_key)?;
+        let signature = Self::signature_point(signature)?;
+
+        Ok(
+            signature.verify(true, message, Self::DST, public_key, &pk, false)
+                == blst::BLST_ERROR::BLST_SUCCESS,
+        )
+    }
+
+    fn public_key(&self, secret: &[u8]) -> Result<Vec<u8>> {
+        Ok(Self::secret_key(secret)?.sk_to_pk().compress().to_vec())
+    }
+}
+
+#[cfg(feature = "bls")]
+impl crate::crypto::BlsCryptoProvider for DefaultBls12381CryptoProvider {
+    fn aggregate_signatures(&self, signatures: &[&[u8]]) -> Result<Vec<u8>> {
+        let signatures = signatures
+            .iter()
+            .map(|signature| Self::signature_point(signature))
+            .collect::<Result<Vec<_>>>()?;
+        let signatures = signatures.iter().collect::<Vec<_>>();
+        let aggregated = blst::min_pk::AggregateSignature::aggregate(&signatures, true)
+            .map_err(|_error| Error::InvalidSignatureBytes)?;
+
+        Ok(aggregated.to_signature().compress().to_vec())
+    }
+
+    fn aggregate_verify(
+        &self,
+        messages: &[&[u8]],
+        signature: &[u8],
+        public_keys: &[&[u8]],
+    ) -> Result<bool> {
+        if messages.len() != public_keys.len() {
+            return Ok(false);
+        }
+        let pks = public_keys
+            .iter()
+            .map(|public_key| Self::public_key_point(public_key))
+            .collect::<Result<Vec<_>>>()?;
+        let pks = pks.iter().collect::<Vec<_>>();
+        let signature = Self::signature_point(signature)?;
+        let messages = messages
+            .iter()
+            .zip(public_keys)
+            .map(|(message, public_key)| [public_key, *message].concat())
+            .collect::<Vec<_>>();
+        let messages = messages
+            .iter()
+            .map(|message| message.as_slice())
+            .collect::<Vec<_>>();
+
+        Ok(
+            signature.aggregate_verify(true, &messages, Self::DST, &pks, false)
+                == blst::BLST_ERROR::BLST_SUCCESS,
+        )
+    }
 }
 
 #[cfg(test)]
 mod test {
-    #[cfg(any(feature = "ed25519", feature = "secp256_k1", feature = "p256"))]
+    #[cfg(any(
+        feature = "ed25519",
+        feature = "secp256_k1",
+        feature = "p256",
+        feature = "bls"
+    ))]
     use super::*;
 
     fn ed25519_pair() -> (&'static [u8], &'static [u8]) {
@@ -555,4 +704,77 @@ mod test {
 
         Ok(())
     }
+
+    #[cfg(feature = "bls")]
+    fn bls12_381_secret(index: u8) -> Vec<u8> {
+        (index * 32 + 1..=index * 32 + 32).collect()
+    }
+
+    #[cfg(feature = "bls")]
+    #[test]
+    fn test_bls12_381_sign() -> Result<()> {
+        let cp = DefaultBls12381CryptoProvider;
+        let signature = cp.sign(b"tezos", &bls12_381_secret(0))?;
+        assert_eq!(
+            hex::encode(&signature),
+            "8b730e2e3e99ac2d37f4c3bbab05ba65bf850a6f0ed3d9c8573998bdcd3e9a2704a5c6db6bb39e9c652a389d382e76e114b5ab61475d64526e34247b92787d171dd4a14c699a106215a39f466b98bead27eee6f6647d251004aa01ba943a9555"
+        );
+        assert_eq!(
+            hex::encode(cp.public_key(&bls12_381_secret(0))?),
+            "b8f3a6c1e371d3bdba1a04e72f3fab9ee04f9e572399cf736323dd6023459625eb44feb02ddd8aa89bf4a59bdf9859df"
+        );
+
+        Ok(())
+    }
+
+    #[cfg(feature = "bls")]
+    #[test]
+    fn test_bls12_381_verify() -> Result<()> {
+        let cp = DefaultBls12381CryptoProvider;
+        let public_key = cp.public_key(&bls12_381_secret(0))?;
+        let other_public_key = cp.public_key(&bls12_381_secret(1))?;
+        let signature = cp.sign(b"tezos", &bls12_381_secret(0))?;
+
+        assert!(cp.verify(b"tezos", &signature, &public_key)?);
+        assert!(!cp.verify(b"tezos!", &signature, &public_key)?);
+        assert!(!cp.verify(b"tezos", &signature, &other_public_key)?);
+        assert!(cp.verify(b"tezos", &signature[1..], &public_key).is_err());
+
+        Ok(())
+    }
+
+    #[cfg(feature = "bls")]
+    #[test]
+    fn test_bls12_381_aggregate_verify() -> Result<()> {
+        use crate::crypto::BlsCryptoProvider;
+
+        let cp = DefaultBls12381CryptoProvider;
+        let secrets = [bls12_381_secret(0), bls12_381_secret(1)];
+        let public_keys = secrets
+            .iter()
+            .map(|secret| cp.public_key(secret))
+            .collect::<Result<Vec<_>>>()?;
+        let public_keys = public_keys
+            .iter()
+            .map(|key| key.as_slice())
+            .collect::<Vec<_>>();
+        let messages: [&[u8]; 2] = [b"tezos", b"tezos"];
+        let signatures = secrets
+            .iter()
+            .zip(messages)
+            .map(|(secret, message)| cp.sign(message, secret))
+            .collect::<Result<Vec<_>>>()?;
+        let signatures = signatures
+            .iter()
+            .map(|signature| signature.as_slice())
+            .collect::<Vec<_>>();
+
+        let signature = cp.aggregate_signatures(&signatures)?;
+        assert!(cp.aggregate_verify(&messages, &signature, &public_keys)?);
+        assert!(!cp.aggregate_verify(&[b"tezos", b"tezos!"], &signature, &public_keys)?);
+        assert!(!cp.aggregate_verify(&messages, &signature, &public_keys[..1])?);
+        assert!(!cp.aggregate_verify(&messages, signatures[0], &public_keys)?);
+
+        Ok(())
+    }
 }
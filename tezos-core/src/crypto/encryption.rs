@@ -0,0 +1,294 @@
+use alloc::vec::Vec;
+
+use crypto_secretbox::{
+    aead::{generic_array::GenericArray, Aead, KeyInit},
+    XSalsa20Poly1305,
+};
+use rand_core::{CryptoRng, RngCore};
+use sha2::Sha512;
+use zeroize::Zeroizing;
+
+use crate::{
+    types::encoded::{
+        Bls12381EncryptedSecretKey, Bls12381SecretKey, Ed25519EncryptedSeed, Ed25519SecretKey,
+        Ed25519Seed, Encoded, EncryptedSecretKey, P256EncryptedSecretKey, P256SecretKey,
+        Secp256K1EncryptedSecretKey, Secp256K1SecretKey, SecretKey,
+    },
+    Error, Result, Tezos,
+};
+
+const SALT_LENGTH: usize = 8;
+const KEY_LENGTH: usize = 32;
+const NONCE_LENGTH: usize = 24;
+const PBKDF2_ROUNDS: u32 = 32768;
+
+/// Encrypts the secret bytes the same way octez-client does: the password is stretched with
+/// PBKDF2-HMAC-SHA512 and the salt is followed by the NaCl secretbox of the secret (with a zero nonce).
+fn encrypt<R: RngCore + CryptoRng>(secret: &[u8], password: &str, rng: &mut R) -> Result<Vec<u8>> {
+    let mut salt = [0u8; SALT_LENGTH];
+    rng.fill_bytes(&mut salt);
+
+    let encrypted = cipher(password, &salt)
+        .encrypt(&GenericArray::from([0u8; NONCE_LENGTH]), secret)
+        .map_err(|_| Error::Internal {
+            description: "secret key encryption failed".into(),
+        })?;
+
+    Ok([&salt[..], &encrypted].concat())
+}
+
+/// Reverts [encrypt], returns [Error::InvalidPassword] if the password does not match.
+fn decrypt(encrypted: &[u8], password: &str) -> Result<Zeroizing<Vec<u8>>> {
+    let (salt, encrypted) = encrypted.split_at(SALT_LENGTH);
+
+    cipher(password, salt)
+        .decrypt(&GenericArray::from([0u8; NONCE_LENGTH]), encrypted)
+        .map(Zeroizing::new)
+        .map_err(|_| Error::InvalidPassword)
+}
+
+fn cipher(password: &str, salt: &[u8]) -> XSalsa20Poly1305 {
+    let mut key = Zeroizing::new([0u8; KEY_LENGTH]);
+    pbkdf2::pbkdf2_hmac::<Sha512>(password.as_bytes(), salt, PBKDF2_ROUNDS, key.as_mut());
+
+    XSalsa20Poly1305::new(key.as_ref().into())
+}
+
+impl Ed25519Seed {
+    /// Encrypts the seed with the given password.
+    pub fn encrypt<R: RngCore + CryptoRng>(
+        &self,
+        password: &str,
+        rng: &mut R,
+    ) -> Result<Ed25519EncryptedSeed> {
+        Ed25519EncryptedSeed::from_bytes(&encrypt(
+            &Zeroizing::new(self.to_bytes()?),
+            password,
+            rng,
+        )?)
+    }
+}
+
+impl Ed25519EncryptedSeed {
+    /// Decrypts the seed with the given password.
+    pub fn decrypt(&self, password: &str) -> Result<Ed25519Seed> {
+        Ed25519Seed::from_bytes(&decrypt(&self.to_bytes()?, password)?)
+    }
+}
+
+impl Ed25519SecretKey {
+    /// Encrypts the secret key with the given password. Only the seed is encrypted, as octez-client does.
+    pub fn encrypt<R: RngCore + CryptoRng>(
+        &self,
+        password: &str,
+        rng: &mut R,
+    ) -> Result<Ed25519EncryptedSeed> {
+        self.seed()?.encrypt(password, rng)
+    }
+}
+
+impl Secp256K1SecretKey {
+    /// Encrypts the secret key with the given password.
+    pub fn encrypt<R: RngCore + CryptoRng>(
+        &self,
+        password: &str,
+        rng: &mut R,
+    ) -> Result<Secp256K1EncryptedSecretKey> {
+        Secp256K1EncryptedSecretKey::from_bytes(&encrypt(
+            &Zeroizing::new(self.to_bytes()?),
+            password,
+            rng,
+        )?)
+    }
+}
+
+impl Secp256K1EncryptedSecretKey {
+    /// Decrypts the secret key with the given password.
+    pub fn decrypt(&self, password: &str) -> Result<Secp256K1SecretKey> {
+        Secp256K1SecretKey::from_bytes(&decrypt(&self.to_bytes()?, password)?)
+    }
+}
+
+impl P256SecretKey {
+    /// Encrypts the secret key with the given password.
+    pub fn encrypt<R: RngCore + CryptoRng>(
+        &self,
+        password: &str,
+        rng: &mut R,
+    ) -> Result<P256EncryptedSecretKey> {
+        P256EncryptedSecretKey::from_bytes(&encrypt(
+            &Zeroizing::new(self.to_bytes()?),
+            password,
+            rng,
+        )?)
+    }
+}
+
+impl P256EncryptedSecretKey {
+    /// Decrypts the secret key with the given password.
+    pub fn decrypt(&self, password: &str) -> Result<P256SecretKey> {
+        P256SecretKey::from_bytes(&decrypt(&self.to_bytes()?, password)?)
+    }
+}
+
+impl Bls12381SecretKey {
+    /// Encrypts the secret key with the given password.
+    pub fn encrypt<R: RngCore + CryptoRng>(
+        &self,
+        password: &str,
+        rng: &mut R,
+    ) -> Result<Bls12381EncryptedSecretKey> {
+        Bls12381EncryptedSecretKey::from_bytes(&encrypt(
+            &Zeroizing::new(self.to_bytes()?),
+            password,
+            rng,
+        )?)
+    }
+}
+
+impl Bls12381EncryptedSecretKey {
+    /// Decrypts the secret key with the given password.
+    pub fn decrypt(&self, password: &str) -> Result<Bls12381SecretKey> {
+        Bls12381SecretKey::from_bytes(&decrypt(&self.to_bytes()?, password)?)
+    }
+}
+
+impl SecretKey {
+    /// Encrypts the secret key with the given password.
+    pub fn encrypt<R: RngCore + CryptoRng>(
+        &self,
+        password: &str,
+        rng: &mut R,
+    ) -> Result<EncryptedSecretKey> {
+        Ok(match self {
+            Self::Ed25519(key) => key.encrypt(password, rng)?.into(),
+            Self::Secp256K1(key) => key.encrypt(password, rng)?.into(),
+            Self::P256(key) => key.encrypt(password, rng)?.into(),
+            Self::Bls12381(key) => key.encrypt(password, rng)?.into(),
+        })
+    }
+}
+
+impl EncryptedSecretKey {
+    /// Decrypts the secret key with the given password using the crypto providers
+    /// configured in the given [Tezos] instance.
+    ///
+    /// The crypto provider is only needed to expand an ed25519 seed to a full secret key.
+    pub fn decrypt_with(&self, password: &str, tezos: &Tezos) -> Result<SecretKey> {
+        Ok(match self {
+            Self::Ed25519(key) => key.decrypt(password)?.to_secret_key_with(tezos)?.into(),
+            Self::Secp256K1(key) => key.decrypt(password)?.into(),
+            Self::P256(key) => key.decrypt(password)?.into(),
+            Self::Bls12381(key) => key.decrypt(password)?.into(),
+        })
+    }
+
+    /// Decrypts the secret key with the given password.
+    pub fn decrypt(&self, password: &str) -> Result<SecretKey> {
+        let tezos: Tezos = Default::default();
+
+        self.decrypt_with(password, &tezos)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+
+    use super::*;
+
+    #[test]
+    fn test_decrypt_ed25519_seed() -> Result<()> {
+        let key: Ed25519EncryptedSeed =
+            "edesk1GBQ31Tu4Cffk2AayCNiUBLRePCxB5fhPfPanj1oqCUy7uvpxUYsoz5UvfYBkRqaQfRWkbEZFWZJDa4WJ2P"
+                .try_into()?;
+        assert_eq!(
+            key.decrypt("password")?.value(),
+            "edsk2ym28S1pkkCgG5KqWTWCNu5VVUMm6yKgRn7drdixVeppRQvqvb"
+        );
+        assert!(matches!(
+            key.decrypt("wrong password"),
+            Err(Error::InvalidPassword)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decrypt_secp256_k1_secret_key() -> Result<()> {
+        let key: Secp256K1EncryptedSecretKey =
+            "spesk1RzvxguVHK8kCEeiVL2KZ81iPecQUeABgEQDgZ8STMeL2wur1X78MuFjZbqh2iHkABpkFkqLbibayVWCQNa"
+                .try_into()?;
+        assert_eq!(
+            key.decrypt("password")?.value(),
+            "spsk2WUw2TFXQq2CsrNhB7EfFzdhMyNvGoYgD4uGQ6e17MgoRDv1co"
+        );
+        assert!(matches!(
+            key.decrypt("wrong password"),
+            Err(Error::InvalidPassword)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decrypt_p256_secret_key() -> Result<()> {
+        let key: P256EncryptedSecretKey =
+            "p2esk2TFqgNcoT4u99ut5doGTUFNwo9x4nNvkpM6YMLqXrt4SbFdQnqLM3hoAXLMB2uZYazj6LZGvcoYzk16H6Et"
+                .try_into()?;
+        assert_eq!(
+            key.decrypt("test1234")?.value(),
+            "p2sk2mJNRYqs3UXJzzF44Ym6jk38RVDPVSuLCfNd5ShE5zyVdu8Au9"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_encrypt() -> Result<()> {
+        let mut rng = ChaCha20Rng::from_seed([0; 32]);
+        let secret_keys = vec![
+            "edsk2ym28S1pkkCgG5KqWTWCNu5VVUMm6yKgRn7drdixVeppRQvqvb",
+            "spsk2WUw2TFXQq2CsrNhB7EfFzdhMyNvGoYgD4uGQ6e17MgoRDv1co",
+            "p2sk2Xoduh8dx6B3smV81NMV25cYpZJj7yYWMRARedzyJae8SB9auw",
+        ];
+        for secret_key in secret_keys {
+            let encrypted = if secret_key.starts_with("edsk") {
+                EncryptedSecretKey::Ed25519(
+                    Ed25519Seed::new(secret_key.into())?.encrypt("password", &mut rng)?,
+                )
+            } else {
+                SecretKey::new(secret_key.into())?.encrypt("password", &mut rng)?
+            };
+            let encrypted = EncryptedSecretKey::from_uri(&encrypted.to_uri())?;
+            let decrypted = match encrypted {
+                EncryptedSecretKey::Ed25519(key) => key.decrypt("password")?.value().to_owned(),
+                EncryptedSecretKey::Secp256K1(key) => key.decrypt("password")?.value().to_owned(),
+                EncryptedSecretKey::P256(key) => key.decrypt("password")?.value().to_owned(),
+                EncryptedSecretKey::Bls12381(key) => key.decrypt("password")?.value().to_owned(),
+            };
+            assert_eq!(decrypted, secret_key);
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "ed25519")]
+    #[test]
+    fn test_decrypt_secret_key() -> Result<()> {
+        let key = EncryptedSecretKey::from_uri(
+            "encrypted:edesk1GBQ31Tu4Cffk2AayCNiUBLRePCxB5fhPfPanj1oqCUy7uvpxUYsoz5UvfYBkRqaQfRWkbEZFWZJDa4WJ2P",
+        )?;
+        let secret_key = key.decrypt("password")?;
+        assert_eq!(
+            secret_key.value(),
+            "edskRhKTQkgxb7CNTr31rzy3xdkyKaYX9hySAnZYJTPmUzPB9EqTNRTUN2BgxEZgiX973veEXT1nmtJxMyBXE9UTU58tE5Tzui"
+        );
+        assert_eq!(
+            secret_key.public_key()?.public_key_hash()?.value(),
+            "tz1MtxLSjud9RYinHEKvnB63ZQB4cDYX7iCB"
+        );
+
+        Ok(())
+    }
+}
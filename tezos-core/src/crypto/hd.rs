@@ -0,0 +1,520 @@
+use alloc::{format, string::String, vec, vec::Vec};
+use core::{fmt, str::FromStr};
+
+use hmac::{Hmac, Mac};
+use ibig::UBig;
+use sha2::Sha512;
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::{
+    crypto::Curve,
+    types::encoded::{Ed25519Seed, Encoded, P256SecretKey, Secp256K1SecretKey, SecretKey},
+    Error, Result, Tezos,
+};
+
+type HmacSha512 = Hmac<Sha512>;
+
+/// A BIP32 derivation path, e.g. `m/44'/1729'/0'/0'`.
+///
+/// Hardened indexes can be marked either with `'` or `h`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct DerivationPath(Vec<u32>);
+
+impl DerivationPath {
+    /// The offset added to an index to make it hardened.
+    pub const HARDENED_OFFSET: u32 = 0x8000_0000;
+
+    const TEZOS_PURPOSE: u32 = 44;
+    const TEZOS_COIN_TYPE: u32 = 1729;
+
+    /// Creates a new [DerivationPath] from the raw indexes.
+    pub fn new(indexes: Vec<u32>) -> Self {
+        Self(indexes)
+    }
+
+    /// Creates the standard Tezos derivation path `m/44'/1729'/{account}'/0'`.
+    pub fn tezos(account: u32) -> Self {
+        Self(vec![
+            Self::TEZOS_PURPOSE | Self::HARDENED_OFFSET,
+            Self::TEZOS_COIN_TYPE | Self::HARDENED_OFFSET,
+            account | Self::HARDENED_OFFSET,
+            Self::HARDENED_OFFSET,
+        ])
+    }
+
+    /// Returns the raw indexes of the path.
+    pub fn indexes(&self) -> &[u32] {
+        &self.0
+    }
+
+    /// Checks if the given raw index is hardened.
+    pub fn is_hardened(index: u32) -> bool {
+        index >= Self::HARDENED_OFFSET
+    }
+
+    fn parse_index(value: &str) -> Result<u32> {
+        let (value, hardened) = match value.strip_suffix(|c| c == '\'' || c == 'h') {
+            Some(value) => (value, true),
+            None => (value, false),
+        };
+        let index = value
+            .parse::<u32>()
+            .ok()
+            .filter(|index| !Self::is_hardened(*index))
+            .ok_or_else(|| Error::InvalidDerivationPath {
+                description: format!("invalid index `{}`", value),
+            })?;
+
+        Ok(if hardened {
+            index | Self::HARDENED_OFFSET
+        } else {
+            index
+        })
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        let mut components = value.split('/');
+        if components.next() != Some("m") {
+            return Err(Error::InvalidDerivationPath {
+                description: format!("`{}` must start with `m`", value),
+            });
+        }
+
+        Ok(Self(
+            components
+                .map(Self::parse_index)
+                .collect::<Result<Vec<_>>>()?,
+        ))
+    }
+}
+
+impl TryFrom<&str> for DerivationPath {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self> {
+        value.parse()
+    }
+}
+
+impl TryFrom<String> for DerivationPath {
+    type Error = Error;
+
+    fn try_from(value: String) -> Result<Self> {
+        value.parse()
+    }
+}
+
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "m")?;
+        for index in self.0.iter() {
+            if Self::is_hardened(*index) {
+                write!(f, "/{}'", index - Self::HARDENED_OFFSET)?;
+            } else {
+                write!(f, "/{}", index)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A secret key extended with a chain code that can be used to derive child keys.
+///
+/// The derivation follows [SLIP-10](https://github.com/satoshilabs/slips/blob/master/slip-0010.md),
+/// which is BIP32 for secp256k1 and p256 and only allows hardened derivation for ed25519.
+///
+/// # Example
+///
+/// ```rust
+/// use tezos_core::crypto::{Curve, DerivationPath, ExtendedSecretKey};
+///
+/// let seed = [0u8; 64];
+/// let path: DerivationPath = "m/44'/1729'/0'/0'".try_into().expect("valid derivation path");
+/// let extended_key = ExtendedSecretKey::from_seed(Curve::Ed25519, &seed)
+///     .and_then(|key| key.derive_path(&path))
+///     .expect("valid derivation");
+/// # #[cfg(feature = "ed25519")]
+/// let secret_key = extended_key.to_secret_key().expect("valid secret key");
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct ExtendedSecretKey {
+    curve: Curve,
+    secret: [u8; 32],
+    chain_code: [u8; 32],
+}
+
+impl ExtendedSecretKey {
+    /// Creates the master key for the given curve from the seed (e.g. a BIP39 seed).
+    pub fn from_seed(curve: Curve, seed: &[u8]) -> Result<Self> {
+        let mut output = hmac_sha512(curve.hmac_key(), &[seed]);
+        while !curve.is_valid_secret(&output[..32]) {
+            output = hmac_sha512(curve.hmac_key(), &[output.as_slice()]);
+        }
+
+        Ok(Self::from_hmac_output(curve, &output))
+    }
+
+    /// Returns the curve of the key.
+    pub fn curve(&self) -> Curve {
+        self.curve
+    }
+
+    /// Returns the raw 32 bytes of the secret key.
+    pub fn secret(&self) -> &[u8; 32] {
+        &self.secret
+    }
+
+    /// Returns the chain code.
+    pub fn chain_code(&self) -> &[u8; 32] {
+        &self.chain_code
+    }
+
+    /// Derives the child key at the given raw index using the crypto providers configured in the given [Tezos] instance.
+    ///
+    /// Non-hardened derivation requires the public key of the current key and is not available for ed25519.
+    pub fn derive_child_with(&self, index: u32, tezos: &Tezos) -> Result<Self> {
+        let index_bytes = index.to_be_bytes();
+        let data = if DerivationPath::is_hardened(index) {
+            Zeroizing::new([&[0u8][..], &self.secret].concat())
+        } else {
+            match self.curve {
+                Curve::Ed25519 => {
+                    return Err(Error::InvalidDerivationPath {
+                        description: format!(
+                            "non-hardened index {} is not supported for ed25519",
+                            index
+                        ),
+                    })
+                }
+                Curve::Secp256K1 => {
+                    Zeroizing::new(tezos.get_crypto().public_key_secp256_k1(&self.secret)?)
+                }
+                Curve::P256 => Zeroizing::new(tezos.get_crypto().public_key_p256(&self.secret)?),
+            }
+        };
+
+        let mut output = hmac_sha512(&self.chain_code, &[&data, &index_bytes]);
+        loop {
+            match self.curve.child_secret(&output[..32], &self.secret) {
+                Some(secret) => {
+                    output[..32].copy_from_slice(secret.as_ref());
+                    return Ok(Self::from_hmac_output(self.curve, &output));
+                }
+                None => {
+                    output = hmac_sha512(&self.chain_code, &[&[1u8], &output[32..], &index_bytes]);
+                }
+            }
+        }
+    }
+
+    /// Derives the child key at the given raw index.
+    pub fn derive_child(&self, index: u32) -> Result<Self> {
+        let tezos: Tezos = Default::default();
+
+        self.derive_child_with(index, &tezos)
+    }
+
+    /// Derives the key at the given path, relative to this key, using the crypto providers configured in the given [Tezos] instance.
+    pub fn derive_path_with(&self, path: &DerivationPath, tezos: &Tezos) -> Result<Self> {
+        path.indexes().iter().try_fold(self.clone(), |key, index| {
+            key.derive_child_with(*index, tezos)
+        })
+    }
+
+    /// Derives the key at the given path, relative to this key.
+    pub fn derive_path(&self, path: &DerivationPath) -> Result<Self> {
+        let tezos: Tezos = Default::default();
+
+        self.derive_path_with(path, &tezos)
+    }
+
+    /// Converts the key to a [SecretKey] using the crypto providers configured in the given [Tezos] instance.
+    pub fn to_secret_key_with(&self, tezos: &Tezos) -> Result<SecretKey> {
+        Ok(match self.curve {
+            Curve::Ed25519 => Ed25519Seed::from_bytes(&self.secret)?
+                .to_secret_key_with(tezos)?
+                .into(),
+            Curve::Secp256K1 => Secp256K1SecretKey::from_bytes(&self.secret)?.into(),
+            Curve::P256 => P256SecretKey::from_bytes(&self.secret)?.into(),
+        })
+    }
+
+    /// Converts the key to a [SecretKey].
+    pub fn to_secret_key(&self) -> Result<SecretKey> {
+        let tezos: Tezos = Default::default();
+
+        self.to_secret_key_with(&tezos)
+    }
+
+    fn from_hmac_output(curve: Curve, output: &[u8; 64]) -> Self {
+        let mut secret = [0u8; 32];
+        let mut chain_code = [0u8; 32];
+        secret.copy_from_slice(&output[..32]);
+        chain_code.copy_from_slice(&output[32..]);
+
+        Self {
+            curve,
+            secret,
+            chain_code,
+        }
+    }
+}
+
+impl fmt::Debug for ExtendedSecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExtendedSecretKey")
+            .field("curve", &self.curve)
+            .field("secret", &"<redacted>")
+            .field("chain_code", &hex::encode(self.chain_code))
+            .finish()
+    }
+}
+
+impl Drop for ExtendedSecretKey {
+    fn drop(&mut self) {
+        self.secret.zeroize();
+        self.chain_code.zeroize();
+    }
+}
+
+impl Curve {
+    fn hmac_key(&self) -> &'static [u8] {
+        match self {
+            Curve::Ed25519 => b"ed25519 seed",
+            Curve::Secp256K1 => b"Bitcoin seed",
+            Curve::P256 => b"Nist256p1 seed",
+        }
+    }
+
+    fn order(&self) -> Option<UBig> {
+        let order: &[u8] = match self {
+            Curve::Ed25519 => return None,
+            Curve::Secp256K1 => &[
+                0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+                0xff, 0xfe, 0xba, 0xae, 0xdc, 0xe6, 0xaf, 0x48, 0xa0, 0x3b, 0xbf, 0xd2, 0x5e, 0x8c,
+                0xd0, 0x36, 0x41, 0x41,
+            ],
+            Curve::P256 => &[
+                0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+                0xff, 0xff, 0xbc, 0xe6, 0xfa, 0xad, 0xa7, 0x17, 0x9e, 0x84, 0xf3, 0xb9, 0xca, 0xc2,
+                0xfc, 0x63, 0x25, 0x51,
+            ],
+        };
+
+        Some(UBig::from_be_bytes(order))
+    }
+
+    fn is_valid_secret(&self, secret: &[u8]) -> bool {
+        match self.order() {
+            Some(order) => {
+                let secret = UBig::from_be_bytes(secret);
+                secret != UBig::from(0u8) && secret < order
+            }
+            None => true,
+        }
+    }
+
+    fn child_secret(&self, tweak: &[u8], parent: &[u8]) -> Option<Zeroizing<[u8; 32]>> {
+        let mut child = Zeroizing::new([0u8; 32]);
+        match self.order() {
+            Some(order) => {
+                let tweak = UBig::from_be_bytes(tweak);
+                if tweak >= order {
+                    return None;
+                }
+                let secret = (tweak + UBig::from_be_bytes(parent)) % order;
+                if secret == UBig::from(0u8) {
+                    return None;
+                }
+                let bytes = secret.to_be_bytes();
+                child[32 - bytes.len()..].copy_from_slice(&bytes);
+            }
+            None => child.copy_from_slice(tweak),
+        }
+
+        Some(child)
+    }
+}
+
+fn hmac_sha512(key: &[u8], data: &[&[u8]]) -> Zeroizing<[u8; 64]> {
+    let mut mac = HmacSha512::new_from_slice(key).expect("HMAC can take a key of any size");
+    for chunk in data {
+        mac.update(chunk);
+    }
+
+    Zeroizing::new(mac.finalize().into_bytes().into())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SEED: [u8; 16] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
+
+    fn assert_derivation(curve: Curve, path: &str, chain_code: &str, secret: &str) -> Result<()> {
+        let key = ExtendedSecretKey::from_seed(curve, &SEED)?.derive_path(&path.parse()?)?;
+        assert_eq!(hex::encode(key.chain_code()), chain_code);
+        assert_eq!(hex::encode(key.secret()), secret);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_derivation_path() -> Result<()> {
+        let path: DerivationPath = "m/44'/1729'/0'/0'".try_into()?;
+        assert_eq!(path, DerivationPath::tezos(0));
+        assert_eq!(path.to_string(), "m/44'/1729'/0'/0'");
+
+        let path: DerivationPath = "m/0h/1".try_into()?;
+        assert_eq!(path.indexes(), &[0x8000_0000, 1]);
+        let path: DerivationPath = "m".try_into()?;
+        assert!(path.indexes().is_empty());
+
+        assert!(DerivationPath::from_str("44'/1729'").is_err());
+        assert!(DerivationPath::from_str("m/2147483648").is_err());
+        assert!(DerivationPath::from_str("m/a'").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_slip10_ed25519() -> Result<()> {
+        let values = vec![
+            (
+                "m",
+                "90046a93de5380a72b5e45010748567d5ea02bbf6522f979e05c0d8d8ca9fffb",
+                "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7",
+            ),
+            (
+                "m/0'",
+                "8b59aa11380b624e81507a27fedda59fea6d0b779a778918a2fd3590e16e9c69",
+                "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3",
+            ),
+            (
+                "m/0'/1'",
+                "a320425f77d1b5c2505a6b1b27382b37368ee640e3557c315416801243552f14",
+                "b1d0bad404bf35da785a64ca1ac54b2617211d2777696fbffaf208f746ae84f2",
+            ),
+            (
+                "m/0'/1'/2'",
+                "2e69929e00b5ab250f49c3fb1c12f252de4fed2c1db88387094a0f8c4c9ccd6c",
+                "92a5b23c0b8a99e37d07df3fb9966917f5d06e02ddbd909c7e184371463e9fc9",
+            ),
+            (
+                "m/0'/1'/2'/2'",
+                "8f6d87f93d750e0efccda017d662a1b31a266e4a6f5993b15f5c1f07f74dd5cc",
+                "30d1dc7e5fc04c31219ab25a27ae00b50f6fd66622f6e9c913253d6511d1e662",
+            ),
+            (
+                "m/0'/1'/2'/2'/1000000000'",
+                "68789923a0cac2cd5a29172a475fe9e0fb14cd6adb5ad98a3fa70333e7afa230",
+                "8f94d394a8e8fd6b1bc2f3f49f5c47e385281d5c17e65324b0f62483e37e8793",
+            ),
+        ];
+        for (path, chain_code, secret) in values {
+            assert_derivation(Curve::Ed25519, path, chain_code, secret)?;
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_slip10_ed25519_non_hardened() -> Result<()> {
+        let key = ExtendedSecretKey::from_seed(Curve::Ed25519, &SEED)?;
+        assert!(matches!(
+            key.derive_child(1),
+            Err(Error::InvalidDerivationPath { .. })
+        ));
+
+        Ok(())
+    }
+
+    #[cfg(feature = "secp256_k1")]
+    #[test]
+    fn test_slip10_secp256_k1() -> Result<()> {
+        let values = vec![
+            (
+                "m",
+                "873dff81c02f525623fd1fe5167eac3a55a049de3d314bb42ee227ffed37d508",
+                "e8f32e723decf4051aefac8e2c93c9c5b214313817cdb01a1494b917c8436b35",
+            ),
+            (
+                "m/0'",
+                "47fdacbd0f1097043b78c63c20c34ef4ed9a111d980047ad16282c7ae6236141",
+                "edb2e14f9ee77d26dd93b4ecede8d16ed408ce149b6cd80b0715a2d911a0afea",
+            ),
+            (
+                "m/0'/1",
+                "2a7857631386ba23dacac34180dd1983734e444fdbf774041578e9b6adb37c19",
+                "3c6cb8d0f6a264c91ea8b5030fadaa8e538b020f0a387421a12de9319dc93368",
+            ),
+            (
+                "m/0'/1/2'",
+                "04466b9cc8e161e966409ca52986c584f07e9dc81f735db683c3ff6ec7b1503f",
+                "cbce0d719ecf7431d88e6a89fa1483e02e35092af60c042b1df2ff59fa424dca",
+            ),
+            (
+                "m/0'/1/2'/2",
+                "cfb71883f01676f587d023cc53a35bc7f88f724b1f8c2892ac1275ac822a3edd",
+                "0f479245fb19a38a1954c5c7c0ebab2f9bdfd96a17563ef28a6a4b1a2a764ef4",
+            ),
+            (
+                "m/0'/1/2'/2/1000000000",
+                "c783e67b921d2beb8f6b389cc646d7263b4145701dadd2161548a8b078e65e9e",
+                "471b76e389e528d6de6d816857e012c5455051cad6660850e58372a6c3e6e7c8",
+            ),
+        ];
+        for (path, chain_code, secret) in values {
+            assert_derivation(Curve::Secp256K1, path, chain_code, secret)?;
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "p256")]
+    #[test]
+    fn test_slip10_p256() -> Result<()> {
+        let values = vec![
+            (
+                "m",
+                "beeb672fe4621673f722f38529c07392fecaa61015c80c34f29ce8b41b3cb6ea",
+                "612091aaa12e22dd2abef664f8a01a82cae99ad7441b7ef8110424915c268bc2",
+            ),
+            (
+                "m/0'",
+                "3460cea53e6a6bb5fb391eeef3237ffd8724bf0a40e94943c98b83825342ee11",
+                "6939694369114c67917a182c59ddb8cafc3004e63ca5d3b84403ba8613debc0c",
+            ),
+            (
+                "m/0'/1",
+                "4187afff1aafa8445010097fb99d23aee9f599450c7bd140b6826ac22ba21d0c",
+                "284e9d38d07d21e4e281b645089a94f4cf5a5a81369acf151a1c3a57f18b2129",
+            ),
+            (
+                "m/0'/1/2'",
+                "98c7514f562e64e74170cc3cf304ee1ce54d6b6da4f880f313e8204c2a185318",
+                "694596e8a54f252c960eb771a3c41e7e32496d03b954aeb90f61635b8e092aa7",
+            ),
+            (
+                "m/0'/1/2'/2",
+                "ba96f776a5c3907d7fd48bde5620ee374d4acfd540378476019eab70790c63a0",
+                "5996c37fd3dd2679039b23ed6f70b506c6b56b3cb5e424681fb0fa64caf82aaa",
+            ),
+            (
+                "m/0'/1/2'/2/1000000000",
+                "b9b7b82d326bb9cb5b5b121066feea4eb93d5241103c9e7a18aad40f1dde8059",
+                "21c4f269ef0a5fd1badf47eeacebeeaa3de22eb8e5b0adcd0f27dd99d34d0119",
+            ),
+        ];
+        for (path, chain_code, secret) in values {
+            assert_derivation(Curve::P256, path, chain_code, secret)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,246 @@
+#[cfg(any(feature = "ed25519", feature = "secp256_k1", feature = "p256"))]
+use rand_core::{CryptoRng, RngCore};
+
+#[cfg(any(feature = "ed25519", feature = "secp256_k1", feature = "p256"))]
+use crate::{crypto::Curve, Error};
+use crate::{
+    types::encoded::{ImplicitAddress, PublicKey, SecretKey},
+    Result, Tezos,
+};
+
+/// A secret key together with its matching public key and implicit address.
+///
+/// A new key pair can be generated for each supported curve, provided the corresponding feature
+/// is enabled (`ed25519`, `secp256_k1`, `p256`). The randomness source is supplied by the caller.
+///
+/// # Example
+///
+/// ```rust
+/// use tezos_core::crypto::KeyPair;
+/// use tezos_core::types::encoded::SecretKey;
+///
+/// let secret_key: SecretKey = "edskRv7VyXGVZb8EsrR7D9XKUbbAQNQGtALP6QeB16ZCD7SmmJpzyeneJVg3Mq56YLbxRA1kSdAXiswwPiaVfR3NHGMCXCziuZ".try_into().expect("valid conversion to SecretKey");
+/// # #[cfg(feature = "ed25519")]
+/// let key_pair = KeyPair::from_secret_key(secret_key).expect("valid key pair");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyPair {
+    secret_key: SecretKey,
+    public_key: PublicKey,
+    address: ImplicitAddress,
+}
+
+impl KeyPair {
+    const SECRET_SIZE: usize = 32;
+
+    /// Creates a new [KeyPair] from the given secret key using the crypto providers
+    /// configured in the given [Tezos] instance.
+    pub fn from_secret_key_with(secret_key: SecretKey, tezos: &Tezos) -> Result<Self> {
+        let public_key = secret_key.public_key_with(tezos)?;
+        let address = public_key.public_key_hash()?;
+
+        Ok(Self {
+            secret_key,
+            public_key,
+            address,
+        })
+    }
+
+    /// Creates a new [KeyPair] from the given secret key.
+    pub fn from_secret_key(secret_key: SecretKey) -> Result<Self> {
+        let tezos: Tezos = Default::default();
+
+        Self::from_secret_key_with(secret_key, &tezos)
+    }
+
+    /// Generates a new key pair on the given curve using the given random number generator.
+    ///
+    /// Returns [Error::CryptoProviderNotSet] if the feature of the curve is not enabled.
+    #[cfg(any(feature = "ed25519", feature = "secp256_k1", feature = "p256"))]
+    pub fn generate<R: RngCore + CryptoRng>(curve: Curve, rng: &mut R) -> Result<Self> {
+        match curve {
+            #[cfg(feature = "ed25519")]
+            Curve::Ed25519 => Self::generate_ed25519(rng),
+            #[cfg(feature = "secp256_k1")]
+            Curve::Secp256K1 => Self::generate_secp256_k1(rng),
+            #[cfg(feature = "p256")]
+            Curve::P256 => Self::generate_p256(rng),
+            #[allow(unreachable_patterns)]
+            _ => Err(Error::CryptoProviderNotSet),
+        }
+    }
+
+    /// Generates a new ed25519 (**tz1**) key pair using the given random number generator.
+    #[cfg(feature = "ed25519")]
+    pub fn generate_ed25519<R: RngCore + CryptoRng>(rng: &mut R) -> Result<Self> {
+        use crate::types::encoded::{Ed25519Seed, Encoded};
+        use zeroize::Zeroizing;
+
+        let mut seed = Zeroizing::new([0u8; Self::SECRET_SIZE]);
+        rng.fill_bytes(seed.as_mut());
+        let secret_key = Ed25519Seed::from_bytes(seed.as_slice())?.to_secret_key()?;
+
+        Self::from_secret_key(secret_key.into())
+    }
+
+    /// Generates a new secp256k1 (**tz2**) key pair using the given random number generator.
+    #[cfg(feature = "secp256_k1")]
+    pub fn generate_secp256_k1<R: RngCore + CryptoRng>(rng: &mut R) -> Result<Self> {
+        use crate::types::encoded::{Encoded, Secp256K1SecretKey};
+
+        let secret = Self::generate_secret(rng, |bytes| {
+            k256::ecdsa::SigningKey::from_bytes(bytes).is_ok()
+        });
+        let secret_key = Secp256K1SecretKey::from_bytes(secret.as_slice())?;
+
+        Self::from_secret_key(secret_key.into())
+    }
+
+    /// Generates a new p256 (**tz3**) key pair using the given random number generator.
+    #[cfg(feature = "p256")]
+    pub fn generate_p256<R: RngCore + CryptoRng>(rng: &mut R) -> Result<Self> {
+        use crate::types::encoded::{Encoded, P256SecretKey};
+
+        let secret = Self::generate_secret(rng, |bytes| {
+            p256::ecdsa::SigningKey::from_bytes(bytes).is_ok()
+        });
+        let secret_key = P256SecretKey::from_bytes(secret.as_slice())?;
+
+        Self::from_secret_key(secret_key.into())
+    }
+
+    #[cfg(any(feature = "secp256_k1", feature = "p256"))]
+    fn generate_secret<R, F>(
+        rng: &mut R,
+        is_valid: F,
+    ) -> zeroize::Zeroizing<[u8; Self::SECRET_SIZE]>
+    where
+        R: RngCore + CryptoRng,
+        F: Fn(&[u8]) -> bool,
+    {
+        let mut secret = zeroize::Zeroizing::new([0u8; Self::SECRET_SIZE]);
+        loop {
+            rng.fill_bytes(secret.as_mut());
+            if is_valid(secret.as_ref()) {
+                return secret;
+            }
+        }
+    }
+
+    /// Returns the secret key.
+    pub fn secret_key(&self) -> &SecretKey {
+        &self.secret_key
+    }
+
+    /// Returns the public key.
+    pub fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+
+    /// Returns the implicit address derived from the public key.
+    pub fn address(&self) -> &ImplicitAddress {
+        &self.address
+    }
+
+    /// Consumes the [KeyPair] returning the secret key, the public key and the address.
+    pub fn into_parts(self) -> (SecretKey, PublicKey, ImplicitAddress) {
+        (self.secret_key, self.public_key, self.address)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[cfg(any(feature = "ed25519", feature = "secp256_k1", feature = "p256"))]
+    use {
+        super::*,
+        crate::types::encoded::Encoded,
+        rand_chacha::{rand_core::SeedableRng, ChaCha20Rng},
+    };
+
+    #[cfg(feature = "ed25519")]
+    #[test]
+    fn test_from_secret_key() -> Result<()> {
+        let key_pair = KeyPair::from_secret_key("edskRv7VyXGVZb8EsrR7D9XKUbbAQNQGtALP6QeB16ZCD7SmmJpzyeneJVg3Mq56YLbxRA1kSdAXiswwPiaVfR3NHGMCXCziuZ".try_into()?)?;
+        assert_eq!(
+            key_pair.public_key().value(),
+            "edpkttZKC51wemRqL2QxwpMnEKxWnbd35pq47Y6xsCHp5M1f7LN8NP"
+        );
+        assert_eq!(
+            key_pair.address().value(),
+            "tz1RFxSHjxxyaaXyAvJG3uRQeJUCCm4jk2LX"
+        );
+
+        Ok(())
+    }
+
+    #[cfg(feature = "ed25519")]
+    #[test]
+    fn test_generate_ed25519() -> Result<()> {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        let key_pair = KeyPair::generate_ed25519(&mut rng)?;
+        assert_eq!(key_pair.secret_key().value(), "edskRsYeULKtWpe4qzxq8iwBwwYbD9Y21d27KgUfNWDU7F2w2zJKcjpae1T2G2SugqEEo7bizxhF9k5Rdo5WbDX1WNWMECD6wZ");
+        assert_eq!(
+            key_pair.public_key().value(),
+            "edpkttkeiRXFDaGMh23dHdTxmsMP7y7nCJJmR4BkLJHMZic8LBe2fk"
+        );
+        assert_eq!(
+            key_pair.address().value(),
+            "tz1Zs5PWhyt6zrCi6PHVHPdLgDbQmqNfqpKD"
+        );
+
+        Ok(())
+    }
+
+    #[cfg(feature = "secp256_k1")]
+    #[test]
+    fn test_generate_secp256_k1() -> Result<()> {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        let key_pair = KeyPair::generate_secp256_k1(&mut rng)?;
+        assert_eq!(
+            key_pair.secret_key().value(),
+            "spsk2KrHCYFWvgatWx7a2KD2isYZ48bQ1JqB4cSrJRv53CPV33bRoL"
+        );
+        assert_eq!(
+            key_pair.public_key().value(),
+            "sppk7cRsPKF2GyuXgHJpyzoY38MQ2S9hpbpY8LjeQxPexoVz5TPHieT"
+        );
+        assert_eq!(
+            key_pair.address().value(),
+            "tz2QCMBwgqiS9onnbmXnfoeVdTWdo74nVXsy"
+        );
+
+        Ok(())
+    }
+
+    #[cfg(feature = "p256")]
+    #[test]
+    fn test_generate_p256() -> Result<()> {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        let key_pair = KeyPair::generate_p256(&mut rng)?;
+        assert_eq!(
+            key_pair.secret_key().value(),
+            "p2sk3F5QS3B7NvhNGQLrPLrpVmAcGZhNLQT878yHZuiikt8oCeudDT"
+        );
+        assert_eq!(
+            key_pair.public_key().value(),
+            "p2pk68PANwfCefardKexDtR9KLp18RH7gmocEzekFwyD7jXtQ6HdtG7"
+        );
+        assert_eq!(
+            key_pair.address().value(),
+            "tz3bbiWqeA8j7iJrRh9dRZgPK9ZLAUKKFeBG"
+        );
+
+        Ok(())
+    }
+
+    #[cfg(feature = "ed25519")]
+    #[test]
+    fn test_generate_distinct_key_pairs() -> Result<()> {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        let first = KeyPair::generate_ed25519(&mut rng)?;
+        let second = KeyPair::generate_ed25519(&mut rng)?;
+        assert_ne!(first, second);
+
+        Ok(())
+    }
+}
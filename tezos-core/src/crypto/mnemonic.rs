@@ -0,0 +1,289 @@
+use alloc::{format, string::String, vec::Vec};
+use core::fmt;
+
+use zeroize::Zeroizing;
+
+use crate::{
+    crypto::{Curve, DerivationPath, ExtendedSecretKey},
+    types::encoded::{Ed25519SecretKey, Ed25519Seed, Encoded, SecretKey, TraitMetaEncoded},
+    Error, Result, Tezos,
+};
+
+/// A [BIP39](https://github.com/bitcoin/bips/blob/master/bip-0039.mediawiki) mnemonic using the English wordlist.
+///
+/// The mnemonic is validated against the wordlist and its checksum when created. It can be turned into
+/// a seed and further into an [Ed25519SecretKey] the same way Tezos wallets do it: the first 32 bytes of
+/// the BIP39 seed become the ed25519 seed.
+///
+/// # Example
+///
+/// ```rust
+/// use tezos_core::crypto::Mnemonic;
+///
+/// let mnemonic: Mnemonic = "raw peace visual boil prefer rebel anchor right elegant side gossip enroll force salmon between"
+///     .try_into()
+///     .expect("valid mnemonic");
+/// # #[cfg(feature = "ed25519")]
+/// let secret_key = mnemonic
+///     .to_fundraiser_secret_key("vksbjweo.qsrgfvbw@tezos.example.org", "ECCQ6kCjHf")
+///     .expect("valid secret key");
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct Mnemonic(bip39::Mnemonic);
+
+impl Mnemonic {
+    /// Creates a new [Mnemonic] from the given phrase.
+    ///
+    /// Returns an error if any word is not in the wordlist, the word count is not supported or the checksum does not match.
+    pub fn new(phrase: &str) -> Result<Self> {
+        Ok(Self(bip39::Mnemonic::parse(phrase)?))
+    }
+
+    /// Creates a new [Mnemonic] encoding the given entropy (16, 20, 24, 28 or 32 bytes).
+    pub fn from_entropy(entropy: &[u8]) -> Result<Self> {
+        Ok(Self(bip39::Mnemonic::from_entropy(entropy)?))
+    }
+
+    /// Checks if the given phrase is a valid mnemonic.
+    pub fn is_valid(phrase: &str) -> bool {
+        bip39::Mnemonic::parse(phrase).is_ok()
+    }
+
+    /// Returns the words of the mnemonic.
+    pub fn words(&self) -> Vec<&'static str> {
+        self.0.words().collect()
+    }
+
+    /// Returns the 64 bytes long BIP39 seed derived with the given passphrase. The seed is zeroized on drop.
+    pub fn to_seed(&self, passphrase: &str) -> Zeroizing<[u8; 64]> {
+        Zeroizing::new(self.0.to_seed(passphrase))
+    }
+
+    /// Derives the [Ed25519Seed] from the mnemonic and the given passphrase.
+    pub fn to_ed25519_seed(&self, passphrase: &str) -> Result<Ed25519Seed> {
+        let seed = self.to_seed(passphrase);
+
+        Ed25519Seed::from_bytes(&seed[..Ed25519Seed::meta_value().bytes_length])
+    }
+
+    /// Derives the [Ed25519Seed] from a fundraiser mnemonic. The email and the password are
+    /// concatenated to form the passphrase.
+    pub fn to_fundraiser_ed25519_seed(&self, email: &str, password: &str) -> Result<Ed25519Seed> {
+        self.to_ed25519_seed(&format!("{}{}", email, password))
+    }
+
+    /// Derives the **tz1** [Ed25519SecretKey] from the mnemonic and the given passphrase using the crypto providers
+    /// configured in the given [Tezos] instance.
+    pub fn to_secret_key_with(&self, passphrase: &str, tezos: &Tezos) -> Result<Ed25519SecretKey> {
+        self.to_ed25519_seed(passphrase)?.to_secret_key_with(tezos)
+    }
+
+    /// Derives the **tz1** [Ed25519SecretKey] from the mnemonic and the given passphrase.
+    pub fn to_secret_key(&self, passphrase: &str) -> Result<Ed25519SecretKey> {
+        self.to_ed25519_seed(passphrase)?.to_secret_key()
+    }
+
+    /// Derives the **tz1** [Ed25519SecretKey] from a fundraiser mnemonic, email and password.
+    pub fn to_fundraiser_secret_key(
+        &self,
+        email: &str,
+        password: &str,
+    ) -> Result<Ed25519SecretKey> {
+        self.to_fundraiser_ed25519_seed(email, password)?
+            .to_secret_key()
+    }
+
+    /// Derives the [SecretKey] of the given curve at the given [DerivationPath] from the mnemonic and the given passphrase
+    /// using the crypto providers configured in the given [Tezos] instance.
+    pub fn derive_secret_key_with(
+        &self,
+        passphrase: &str,
+        curve: Curve,
+        path: &DerivationPath,
+        tezos: &Tezos,
+    ) -> Result<SecretKey> {
+        ExtendedSecretKey::from_seed(curve, self.to_seed(passphrase).as_slice())?
+            .derive_path_with(path, tezos)?
+            .to_secret_key_with(tezos)
+    }
+
+    /// Derives the [SecretKey] of the given curve at the given [DerivationPath] from the mnemonic and the given passphrase.
+    pub fn derive_secret_key(
+        &self,
+        passphrase: &str,
+        curve: Curve,
+        path: &DerivationPath,
+    ) -> Result<SecretKey> {
+        let tezos: Tezos = Default::default();
+
+        self.derive_secret_key_with(passphrase, curve, path, &tezos)
+    }
+}
+
+impl fmt::Display for Mnemonic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl fmt::Debug for Mnemonic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Mnemonic").field(&"<redacted>").finish()
+    }
+}
+
+impl TryFrom<&str> for Mnemonic {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self> {
+        Self::new(value)
+    }
+}
+
+impl TryFrom<String> for Mnemonic {
+    type Error = Error;
+
+    fn try_from(value: String) -> Result<Self> {
+        Self::new(&value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        assert!(Mnemonic::is_valid(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"
+        ));
+        assert!(Mnemonic::is_valid(
+            "raw peace visual boil prefer rebel anchor right elegant side gossip enroll force salmon between"
+        ));
+        // invalid checksum
+        assert!(!Mnemonic::is_valid(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon"
+        ));
+        // unknown word
+        assert!(!Mnemonic::is_valid(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon tezos"
+        ));
+        // invalid word count
+        assert!(!Mnemonic::is_valid("abandon abandon about"));
+    }
+
+    #[test]
+    fn test_seed() -> Result<()> {
+        let mnemonic = Mnemonic::new(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+        )?;
+        assert_eq!(
+            hex::encode(mnemonic.to_seed("TREZOR")),
+            "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_entropy() -> Result<()> {
+        let mnemonic = Mnemonic::from_entropy(&[0x7f; 16])?;
+        assert_eq!(
+            mnemonic.to_string(),
+            "legal winner thank year wave sausage worth useful legal winner thank yellow"
+        );
+        assert_eq!(mnemonic.words().len(), 12);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ed25519_seed() -> Result<()> {
+        let mnemonic: Mnemonic =
+            "legal winner thank year wave sausage worth useful legal winner thank yellow"
+                .try_into()?;
+        assert_eq!(
+            mnemonic.to_ed25519_seed("")?.value(),
+            "edsk3haoBhUkaNmZXRrUq9WeHqWuvnmatbmBcJcLtdjxCVZD3nqoQM"
+        );
+
+        Ok(())
+    }
+
+    #[cfg(feature = "ed25519")]
+    #[test]
+    fn test_secret_key() -> Result<()> {
+        let mnemonic: Mnemonic =
+            "legal winner thank year wave sausage worth useful legal winner thank yellow"
+                .try_into()?;
+        let secret_key = mnemonic.to_secret_key("")?;
+        assert_eq!(
+            secret_key.value(),
+            "edskRuk4a8zhe8fAvwi6s9xtr3zfi65teYr4fQMX7MY1GWwvwEpBJbvDd7zMNTirQNkMDWtgNoC8djaqineVxsVsfT8GrFk8R8"
+        );
+        assert_eq!(
+            secret_key.public_key()?.public_key_hash()?.value(),
+            "tz1QpnHSMsRe3H5FoTEMGxe6P2yZYrqcbsBn"
+        );
+
+        Ok(())
+    }
+
+    #[cfg(feature = "ed25519")]
+    #[test]
+    fn test_fundraiser_secret_key() -> Result<()> {
+        let mnemonic: Mnemonic = "raw peace visual boil prefer rebel anchor right elegant side gossip enroll force salmon between".try_into()?;
+        let secret_key = mnemonic
+            .to_fundraiser_secret_key("vksbjweo.qsrgfvbw@tezos.example.org", "ECCQ6kCjHf")?;
+        assert_eq!(
+            secret_key.value(),
+            "edskRwDFKu1mQxmNRbzEhnCYjqh8Brm6USM6Nsy6jU8Hmd9WGJjdcwTdKqQknzHQokhzjRJMb8s1JKkkwk91Zj9qkYZtv1Y2BJ"
+        );
+        assert_eq!(
+            secret_key.seed()?.value(),
+            "edsk3nXZFJU7zVGZ56W8E8u9iWGxdy6E5XgUxKN9NgRi2TGJHKtt7F"
+        );
+        assert_eq!(
+            secret_key.public_key()?.public_key_hash()?.value(),
+            "tz1eFg193SVikwqosgxiGwDLiF9H9fqZCzkp"
+        );
+
+        Ok(())
+    }
+
+    #[cfg(feature = "ed25519")]
+    #[test]
+    fn test_derive_ed25519_secret_key() -> Result<()> {
+        let mnemonic: Mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about".try_into()?;
+        let secret_key =
+            mnemonic.derive_secret_key("", Curve::Ed25519, &DerivationPath::tezos(0))?;
+        assert_eq!(
+            secret_key.value(),
+            "edskS3wZrW6P38vvgnrHqjuLLz76vy5dv9QduxAQ2yG7WL5WjEubiJQUcwS49tFfwym1MBioLATPzQFckaYgGhWBUS7nmikuME"
+        );
+        assert_eq!(
+            secret_key.public_key()?.public_key_hash()?.value(),
+            "tz1VQA4RP4fLjEEMW2FR4pE9kAg5abb5h5GL"
+        );
+
+        Ok(())
+    }
+
+    #[cfg(feature = "secp256_k1")]
+    #[test]
+    fn test_derive_secp256_k1_secret_key() -> Result<()> {
+        let mnemonic: Mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about".try_into()?;
+        let secret_key =
+            mnemonic.derive_secret_key("", Curve::Secp256K1, &"m/44'/1729'/0'/0/0".try_into()?)?;
+        assert_eq!(
+            secret_key.value(),
+            "spsk28iyPTjJr6EgzdfPbn77nmeL3FyN3GosALdtpdr3Vi7437PYLa"
+        );
+        assert_eq!(
+            secret_key.public_key()?.public_key_hash()?.value(),
+            "tz29obcqZxy5mrB2Bm7G4YQNr1VKng5oSKVT"
+        );
+
+        Ok(())
+    }
+}
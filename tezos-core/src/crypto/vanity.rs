@@ -0,0 +1,345 @@
+use alloc::{format, string::String};
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use rand_core::{CryptoRng, RngCore};
+use regex::Regex;
+
+use crate::{
+    crypto::{Curve, KeyPair},
+    types::encoded::{
+        Ed25519PublicKeyHash, Encoded, P256PublicKeyHash, Secp256K1PublicKeyHash, TraitMetaEncoded,
+    },
+    Error, Result,
+};
+
+const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// The pattern the address of a [VanitySearch] has to match.
+#[derive(Debug, Clone)]
+pub enum VanityPattern {
+    /// Matches the addresses starting with the prefix, including the **tz** prefix, e.g. `tz1Bake`.
+    Prefix(String),
+    /// Matches the addresses the regex matches. The regex is not anchored, use `^` to match from the start.
+    Regex(Regex),
+}
+
+impl VanityPattern {
+    /// Creates a [VanityPattern::Prefix] pattern.
+    pub fn prefix(prefix: &str) -> Self {
+        Self::Prefix(prefix.into())
+    }
+
+    /// Creates a [VanityPattern::Regex] pattern, returns an error if the regex is not valid.
+    pub fn regex(regex: &str) -> Result<Self> {
+        Regex::new(regex)
+            .map(Self::Regex)
+            .map_err(|error| Error::InvalidVanityPattern {
+                description: format!("{}", error),
+            })
+    }
+
+    /// Checks if the address matches the pattern.
+    pub fn is_match(&self, address: &str) -> bool {
+        match self {
+            Self::Prefix(prefix) => address.starts_with(prefix.as_str()),
+            Self::Regex(regex) => regex.is_match(address),
+        }
+    }
+}
+
+/// A multi-threaded search for a key pair whose address matches a [VanityPattern].
+///
+/// Each thread generates random key pairs with its own random number generator, created by the
+/// factory given to [VanitySearch::run], until one of the addresses matches. Each additional
+/// character of a prefix makes the search about 58 times longer.
+///
+/// # Example
+///
+/// ```rust
+/// use std::sync::atomic::{AtomicU64, Ordering};
+/// use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+/// use tezos_core::crypto::{Curve, VanityPattern, VanitySearch};
+/// use tezos_core::types::encoded::Encoded;
+///
+/// let search = VanitySearch::new(Curve::Ed25519, VanityPattern::prefix("tz1a"))
+///     .expect("valid pattern")
+///     .threads(2);
+///
+/// // In practice, use generators seeded from a secure source of entropy, e.g. `rand_core::OsRng`.
+/// let seeds = AtomicU64::new(0);
+/// let result = search
+///     .run(|| ChaCha20Rng::seed_from_u64(seeds.fetch_add(1, Ordering::Relaxed)))
+///     .expect("valid search")
+///     .expect("search not cancelled");
+/// assert!(result.key_pair.address().value().starts_with("tz1a"));
+/// println!("{} keys/s", result.throughput());
+/// ```
+pub struct VanitySearch {
+    curve: Curve,
+    pattern: VanityPattern,
+    threads: usize,
+    handle: VanitySearchHandle,
+}
+
+/// A handle to follow the progress of a [VanitySearch] and cancel it from another thread.
+#[derive(Debug, Clone)]
+pub struct VanitySearchHandle {
+    cancelled: Arc<AtomicBool>,
+    attempts: Arc<AtomicU64>,
+    started: Arc<Mutex<Option<Instant>>>,
+}
+
+/// The result of a successful [VanitySearch].
+#[derive(Debug, Clone)]
+pub struct VanityMatch {
+    /// The key pair whose address matches the pattern.
+    pub key_pair: KeyPair,
+    /// The number of key pairs generated by all the threads.
+    pub attempts: u64,
+    /// The duration of the search.
+    pub elapsed: Duration,
+}
+
+impl VanitySearch {
+    /// Creates a single-threaded search for the given curve.
+    ///
+    /// Returns an error if the pattern is a prefix that no address of the curve can start with.
+    pub fn new(curve: Curve, pattern: VanityPattern) -> Result<Self> {
+        if let VanityPattern::Prefix(prefix) = &pattern {
+            let address_prefix = Self::address_prefix(curve);
+            if !prefix.starts_with(address_prefix) && !address_prefix.starts_with(prefix.as_str()) {
+                return Err(Error::InvalidVanityPattern {
+                    description: format!("the addresses start with {}", address_prefix),
+                });
+            }
+            if let Some(character) = prefix.chars().find(|c| !BASE58_ALPHABET.contains(*c)) {
+                return Err(Error::InvalidVanityPattern {
+                    description: format!("{} is not a base58 character", character),
+                });
+            }
+        }
+
+        Ok(Self {
+            curve,
+            pattern,
+            threads: 1,
+            handle: VanitySearchHandle {
+                cancelled: Arc::new(AtomicBool::new(false)),
+                attempts: Arc::new(AtomicU64::new(0)),
+                started: Arc::new(Mutex::new(None)),
+            },
+        })
+    }
+
+    /// Sets the number of threads generating key pairs, at least one.
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Returns a handle to follow the progress of the search and cancel it.
+    pub fn handle(&self) -> VanitySearchHandle {
+        self.handle.clone()
+    }
+
+    /// Runs the search until a matching key pair is found, or returns `None` if it has been
+    /// cancelled with [VanitySearchHandle::cancel].
+    ///
+    /// Each run starts afresh: the cancellation and the progress of a previous run are reset, so a
+    /// cancelled search can be run again.
+    ///
+    /// The `rng` factory is called once per thread to create the random number generator of the thread.
+    pub fn run<R, F>(&self, rng: F) -> Result<Option<VanityMatch>>
+    where
+        R: RngCore + CryptoRng,
+        F: Fn() -> R + Sync,
+    {
+        self.handle.cancelled.store(false, Ordering::Relaxed);
+        self.handle.attempts.store(0, Ordering::Relaxed);
+        let started = Instant::now();
+        *self.handle.started.lock().unwrap() = Some(started);
+
+        let found = AtomicBool::new(false);
+        let key_pair: Mutex<Option<KeyPair>> = Mutex::new(None);
+        let results: Vec<Result<()>> = thread::scope(|scope| {
+            let threads: Vec<_> = (0..self.threads)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut rng = rng();
+                        while !found.load(Ordering::Relaxed) && !self.handle.is_cancelled() {
+                            let candidate = KeyPair::generate(self.curve, &mut rng)?;
+                            self.handle.attempts.fetch_add(1, Ordering::Relaxed);
+                            if self.pattern.is_match(candidate.address().value()) {
+                                key_pair.lock().unwrap().get_or_insert(candidate);
+                                found.store(true, Ordering::Relaxed);
+                            }
+                        }
+                        Ok(())
+                    })
+                })
+                .collect();
+            threads
+                .into_iter()
+                .map(|thread| thread.join().unwrap())
+                .collect()
+        });
+
+        let key_pair = key_pair.into_inner().unwrap();
+        if key_pair.is_none() {
+            results.into_iter().collect::<Result<()>>()?;
+        }
+
+        Ok(key_pair.map(|key_pair| VanityMatch {
+            key_pair,
+            attempts: self.handle.attempts(),
+            elapsed: started.elapsed(),
+        }))
+    }
+
+    fn address_prefix(curve: Curve) -> &'static str {
+        match curve {
+            Curve::Ed25519 => Ed25519PublicKeyHash::meta_value().base58_prefix,
+            Curve::Secp256K1 => Secp256K1PublicKeyHash::meta_value().base58_prefix,
+            Curve::P256 => P256PublicKeyHash::meta_value().base58_prefix,
+        }
+    }
+}
+
+impl VanitySearchHandle {
+    /// Stops the running search, [VanitySearch::run] returns `None` once the threads have stopped.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Checks if the search has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of key pairs generated so far.
+    pub fn attempts(&self) -> u64 {
+        self.attempts.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of key pairs generated per second so far, zero if the search has not started.
+    pub fn throughput(&self) -> f64 {
+        match *self.started.lock().unwrap() {
+            Some(started) => throughput(self.attempts(), started.elapsed()),
+            None => 0.0,
+        }
+    }
+}
+
+impl VanityMatch {
+    /// Returns the number of key pairs generated per second during the search.
+    pub fn throughput(&self) -> f64 {
+        throughput(self.attempts, self.elapsed)
+    }
+}
+
+fn throughput(attempts: u64, elapsed: Duration) -> f64 {
+    let seconds = elapsed.as_secs_f64();
+    if seconds > 0.0 {
+        attempts as f64 / seconds
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+
+    fn rng_factory() -> impl Fn() -> ChaCha20Rng + Sync {
+        let seeds = AtomicU64::new(0);
+        move || ChaCha20Rng::seed_from_u64(seeds.fetch_add(1, Ordering::Relaxed))
+    }
+
+    #[test]
+    fn test_prefix_search() -> Result<()> {
+        let search = VanitySearch::new(Curve::Ed25519, VanityPattern::prefix("tz1Z"))?.threads(4);
+        let result = search.run(rng_factory())?.unwrap();
+
+        let address = result.key_pair.address().value();
+        assert!(address.starts_with("tz1Z"));
+        let key_pair = KeyPair::from_secret_key(result.key_pair.secret_key().clone())?;
+        assert_eq!(key_pair.address().value(), address);
+        assert!(result.attempts >= 1);
+        assert_eq!(result.attempts, search.handle().attempts());
+        Ok(())
+    }
+
+    #[test]
+    fn test_regex_search() -> Result<()> {
+        let search = VanitySearch::new(Curve::Ed25519, VanityPattern::regex("[Xx]$")?)?.threads(2);
+        let result = search.run(rng_factory())?.unwrap();
+        assert!(result
+            .key_pair
+            .address()
+            .value()
+            .to_lowercase()
+            .ends_with('x'));
+        Ok(())
+    }
+
+    #[test]
+    fn test_cancel() -> Result<()> {
+        let search = VanitySearch::new(Curve::Ed25519, VanityPattern::regex("^KT1")?)?.threads(2);
+        let handle = search.handle();
+        let canceller = thread::spawn(move || {
+            while handle.attempts() < 10 {
+                thread::sleep(Duration::from_millis(1));
+            }
+            handle.cancel();
+        });
+
+        assert!(search.run(rng_factory())?.is_none());
+        canceller.join().unwrap();
+        assert!(search.handle().is_cancelled());
+        assert!(search.handle().attempts() >= 10);
+        assert!(search.handle().throughput() > 0.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_run_after_cancel() -> Result<()> {
+        let search = VanitySearch::new(Curve::Ed25519, VanityPattern::prefix("tz1Z"))?;
+        let handle = search.handle();
+        handle.cancel();
+        assert!(handle.is_cancelled());
+
+        let result = search.run(rng_factory())?.unwrap();
+        assert!(result.key_pair.address().value().starts_with("tz1Z"));
+        assert!(!handle.is_cancelled());
+        assert_eq!(result.attempts, handle.attempts());
+
+        let result = search.run(rng_factory())?.unwrap();
+        assert_eq!(result.attempts, handle.attempts());
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        assert!(matches!(
+            VanitySearch::new(Curve::Ed25519, VanityPattern::prefix("tz2abc")),
+            Err(Error::InvalidVanityPattern { .. })
+        ));
+        assert!(matches!(
+            VanitySearch::new(Curve::Ed25519, VanityPattern::prefix("tz1l")),
+            Err(Error::InvalidVanityPattern { .. })
+        ));
+        assert!(matches!(
+            VanityPattern::regex("tz1("),
+            Err(Error::InvalidVanityPattern { .. })
+        ));
+        assert!(VanitySearch::new(Curve::Ed25519, VanityPattern::prefix("tz")).is_ok());
+    }
+}
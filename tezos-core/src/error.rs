@@ -1,8 +1,12 @@
-use derive_more::{Display, Error as DError, From};
-use std::{result, string::FromUtf8Error};
+use alloc::string::{FromUtf8Error, String};
+use core::result;
+#[cfg(feature = "std")]
+use derive_more::Error as DError;
+use derive_more::{Display, From};
 
 /// Errors returned by this crate.
-#[derive(DError, Display, Debug, From)]
+#[derive(Display, Debug, From)]
+#[cfg_attr(feature = "std", derive(DError))]
 pub enum Error {
     Internal {
         description: String,
@@ -18,11 +22,14 @@ pub enum Error {
     InvalidIntegerString,
     InvalidUnsignedIntegerString,
     InvalidTezString,
+    MutezOverflow,
+    MutezUnderflow,
+    DivisionByZero,
     BigIntParse {
         source: ibig::error::ParseError,
     },
     IntParse {
-        source: std::num::ParseIntError,
+        source: core::num::ParseIntError,
     },
     InvalidStringConversion {
         source: FromUtf8Error,
@@ -32,7 +39,7 @@ pub enum Error {
     InvalidNaturalBytes,
     InvalidIntegerBytes,
     TryFromInt {
-        source: std::num::TryFromIntError,
+        source: core::num::TryFromIntError,
     },
     TryFromBigInt {
         source: ibig::error::OutOfBoundsError,
@@ -44,8 +51,35 @@ pub enum Error {
     InvalidNaturalConversion,
     InvalidAddress,
     InvalidContractAddress,
+    InvalidActivationSecret,
+    MerkleLeafIndexOutOfBounds,
     InvalidHexString,
     CryptoProviderNotSet,
+    PublicKeyDerivationNotSupported,
+    #[from(ignore)]
+    InvalidDerivationPath {
+        description: String,
+    },
+    InvalidPassword,
+    #[from(ignore)]
+    InvalidVanityPattern {
+        description: String,
+    },
+    InvalidTimelockTime,
+    InvalidTimelockValue,
+    InvalidSaplingSeed,
+    InvalidDiversifierIndex,
+    #[from(ignore)]
+    InvalidSaplingParameters {
+        description: String,
+    },
+    InvalidSaplingNote,
+    InvalidSaplingMemo,
+    InsufficientSaplingBalance,
+    #[cfg(feature = "mnemonic")]
+    Mnemonic {
+        source: bip39::Error,
+    },
 }
 
 pub type Result<T> = result::Result<T, Error>;
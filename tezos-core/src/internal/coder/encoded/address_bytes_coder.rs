@@ -1,4 +1,5 @@
-use std::ops::Add;
+use alloc::vec::Vec;
+use core::ops::Add;
 
 use crate::{
     internal::{
@@ -6,6 +6,7 @@ use crate::{
     types::encoded::{ContractAddress, ContractHash, Encoded, TraitMetaEncoded},
     Error, Result,
 };
+use alloc::{string::String, vec::Vec};
 
 pub struct ContractAddressBytesCoder;
 
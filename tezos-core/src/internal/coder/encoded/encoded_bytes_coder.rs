@@ -4,15 +4,19 @@ use crate::internal::{
 };
 use crate::types::encoded::{Encoded, MetaEncoded};
 use crate::{Error, Result};
+use alloc::vec::Vec;
+use zeroize::Zeroizing;
 
+/// Coder for the raw bytes of the encoded values. As the values may be secrets, the intermediate buffers
+/// are zeroized when dropped.
 pub struct EncodedBytesCoder;
 
 impl EncodedBytesCoder {
     pub fn decode_with_meta<E: Encoded>(value: &[u8], meta: &MetaEncoded) -> Result<E> {
         if value.len() == meta.bytes_length {
-            let bytes = [meta.bytes_prefix(), value].concat();
+            let bytes = Zeroizing::new([meta.bytes_prefix(), value].concat());
             return E::new(
-                bs58::encode(bytes)
+                bs58::encode(bytes.as_slice())
                     .with_check_version(meta.version())
                     .into_string(),
             );
@@ -45,9 +49,11 @@ impl<E: Encoded> ConfigurableEncoder<E, Vec<u8>, EncodedBytesCoderConfiguration,
         value: &E,
         configuration: EncodedBytesCoderConfiguration,
     ) -> Result<Vec<u8>> {
-        let bytes = bs58::decode(value.value())
-            .with_check(Some(value.meta().version()))
-            .into_vec()?;
+        let bytes = Zeroizing::new(
+            bs58::decode(value.value())
+                .with_check(Some(value.meta().version()))
+                .into_vec()?,
+        );
         if bytes.len() <= value.meta().versioned_bytes_prefix().len()
             || !bytes.starts_with(value.meta().versioned_bytes_prefix())
         {
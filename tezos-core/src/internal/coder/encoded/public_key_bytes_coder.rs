@@ -1,4 +1,5 @@
-use std::ops::Add;
+use alloc::vec::Vec;
+use core::ops::Add;
 
 use crate::{
     internal::{
@@ -10,8 +11,8 @@ use crate::{
         types::{BytesTag, EncodedTag},
     },
     types::encoded::{
-        Ed25519PublicKey, MetaEncoded, P256PublicKey, PublicKey, Secp256K1PublicKey,
-        TraitMetaEncoded,
+        Bls12381PublicKey, Ed25519PublicKey, MetaEncoded, P256PublicKey, PublicKey,
+        Secp256K1PublicKey, TraitMetaEncoded,
     },
     Error, Result,
 };
@@ -41,6 +42,7 @@ pub enum PublicKeyTag {
     EdPK,
     SpPK,
     P2PK,
+    BlPK,
 }
 
 impl PublicKeyTag {
@@ -58,13 +60,14 @@ impl BytesTag for PublicKeyTag {
             Self::EdPK => &[0],
             Self::SpPK => &[1],
             Self::P2PK => &[2],
+            Self::BlPK => &[3],
         }
     }
 }
 
 impl EncodedTag for PublicKeyTag {
     fn values() -> &'static [Self] {
-        &[Self::EdPK, Self::SpPK, Self::P2PK]
+        &[Self::EdPK, Self::SpPK, Self::P2PK, Self::BlPK]
     }
 
     fn meta(&self) -> &MetaEncoded {
@@ -72,6 +75,7 @@ impl EncodedTag for PublicKeyTag {
             Self::EdPK => Ed25519PublicKey::meta_value(),
             Self::SpPK => Secp256K1PublicKey::meta_value(),
             Self::P2PK => P256PublicKey::meta_value(),
+            Self::BlPK => Bls12381PublicKey::meta_value(),
         }
     }
 }
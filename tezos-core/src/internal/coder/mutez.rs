@@ -3,6 +3,7 @@ use crate::{
     types::{mutez::Mutez, number::Nat},
     Error, Result,
 };
+use alloc::vec::Vec;
 
 use super::{number::natural::NaturalBytesCoder, ConsumingDecoder, Decoder, Encoder};
 
@@ -1,3 +1,4 @@
+use alloc::{vec, vec::Vec};
 use ibig::{IBig, UBig};
 use num_traits::{Signed, Zero};
 
@@ -1,3 +1,4 @@
+use alloc::{vec, vec::Vec};
 use ibig::UBig;
 use num_traits::Zero;
 
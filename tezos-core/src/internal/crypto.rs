@@ -1,10 +1,12 @@
-use crate::crypto::CryptoProvider;
+use crate::crypto::{BlsCryptoProvider, CryptoProvider};
 use crate::{Error, Result};
+use alloc::{boxed::Box, vec::Vec};
 
 pub struct Crypto {
     ed25519_provider: Option<Box<dyn CryptoProvider>>,
     secp256_k1_provider: Option<Box<dyn CryptoProvider>>,
     p256_provider: Option<Box<dyn CryptoProvider>>,
+    bls12_381_provider: Option<Box<dyn BlsCryptoProvider>>,
 }
 
 impl Crypto {
@@ -12,11 +14,13 @@ impl Crypto {
         ed25519_provider: Option<Box<dyn CryptoProvider>>,
         secp256_k1_provider: Option<Box<dyn CryptoProvider>>,
         p256_provider: Option<Box<dyn CryptoProvider>>,
+        bls12_381_provider: Option<Box<dyn BlsCryptoProvider>>,
     ) -> Self {
         Self {
             ed25519_provider,
             secp256_k1_provider,
             p256_provider,
+            bls12_381_provider,
         }
     }
 
@@ -43,6 +47,13 @@ impl Crypto {
             .verify(message, signature, public_key)
     }
 
+    pub fn public_key_ed25519(&self, secret: &[u8]) -> Result<Vec<u8>> {
+        self.ed25519_provider
+            .as_ref()
+            .ok_or(Error::CryptoProviderNotSet)?
+            .public_key(secret)
+    }
+
     pub fn sign_secp256_k1(&self, message: &[u8], secret: &[u8]) -> Result<Vec<u8>> {
         self.secp256_k1_provider
             .as_ref()
@@ -62,6 +73,13 @@ impl Crypto {
             .verify(message, signature, public_key)
     }
 
+    pub fn public_key_secp256_k1(&self, secret: &[u8]) -> Result<Vec<u8>> {
+        self.secp256_k1_provider
+            .as_ref()
+            .ok_or(Error::CryptoProviderNotSet)?
+            .public_key(secret)
+    }
+
     pub fn sign_p256(&self, message: &[u8], secret: &[u8]) -> Result<Vec<u8>> {
         self.p256_provider
             .as_ref()
@@ -75,6 +93,58 @@ impl Crypto {
             .ok_or(Error::CryptoProviderNotSet)?
             .verify(message, signature, public_key)
     }
+
+    pub fn public_key_p256(&self, secret: &[u8]) -> Result<Vec<u8>> {
+        self.p256_provider
+            .as_ref()
+            .ok_or(Error::CryptoProviderNotSet)?
+            .public_key(secret)
+    }
+
+    pub fn sign_bls12_381(&self, message: &[u8], secret: &[u8]) -> Result<Vec<u8>> {
+        self.bls12_381_provider
+            .as_ref()
+            .ok_or(Error::CryptoProviderNotSet)?
+            .sign(message, secret)
+    }
+
+    pub fn verify_bls12_381(
+        &self,
+        message: &[u8],
+        signature: &[u8],
+        public_key: &[u8],
+    ) -> Result<bool> {
+        self.bls12_381_provider
+            .as_ref()
+            .ok_or(Error::CryptoProviderNotSet)?
+            .verify(message, signature, public_key)
+    }
+
+    pub fn public_key_bls12_381(&self, secret: &[u8]) -> Result<Vec<u8>> {
+        self.bls12_381_provider
+            .as_ref()
+            .ok_or(Error::CryptoProviderNotSet)?
+            .public_key(secret)
+    }
+
+    pub fn aggregate_signatures_bls12_381(&self, signatures: &[&[u8]]) -> Result<Vec<u8>> {
+        self.bls12_381_provider
+            .as_ref()
+            .ok_or(Error::CryptoProviderNotSet)?
+            .aggregate_signatures(signatures)
+    }
+
+    pub fn aggregate_verify_bls12_381(
+        &self,
+        messages: &[&[u8]],
+        signature: &[u8],
+        public_keys: &[&[u8]],
+    ) -> Result<bool> {
+        self.bls12_381_provider
+            .as_ref()
+            .ok_or(Error::CryptoProviderNotSet)?
+            .aggregate_verify(messages, signature, public_keys)
+    }
 }
 
 pub fn blake2b(message: &[u8], size: usize) -> Result<Vec<u8>> {
@@ -89,3 +159,57 @@ pub fn blake2b(message: &[u8], size: usize) -> Result<Vec<u8>> {
     hasher.finalize_variable(&mut buf).unwrap();
     Ok(buf)
 }
+
+/// Keyed blake2b hash with a 20 bytes (160 bits) digest.
+pub fn blake2b_160_keyed(message: &[u8], key: &[u8]) -> Result<Vec<u8>> {
+    blake2b_keyed::<blake2::Blake2bMac<blake2::digest::consts::U20>>(message, key)
+}
+
+/// Keyed blake2b hash with a 32 bytes (256 bits) digest.
+pub fn blake2b_256_keyed(message: &[u8], key: &[u8]) -> Result<Vec<u8>> {
+    blake2b_keyed::<blake2::Blake2bMac<blake2::digest::consts::U32>>(message, key)
+}
+
+fn blake2b_keyed<M: blake2::digest::Mac + blake2::digest::KeyInit>(
+    message: &[u8],
+    key: &[u8],
+) -> Result<Vec<u8>> {
+    let mut hasher =
+        <M as blake2::digest::KeyInit>::new_from_slice(key).map_err(|_| Error::InvalidBytes)?;
+    blake2::digest::Mac::update(&mut hasher, message);
+    Ok(hasher.finalize().into_bytes().to_vec())
+}
+
+/// Blake2b hash with a 64 bytes (512 bits) digest and a personalization of up to 16 bytes.
+pub fn blake2b_512_personal(message: &[u8], personal: &[u8]) -> Result<Vec<u8>> {
+    use blake2::{
+        digest::core_api::{Buffer, UpdateCore, VariableOutputCore},
+        Blake2bVarCore,
+    };
+    if personal.len() > 16 {
+        return Err(Error::InvalidBytes);
+    }
+    let mut core = Blake2bVarCore::new_with_params(&[], personal, 0, 64);
+    let mut buffer = Buffer::<Blake2bVarCore>::default();
+    buffer.digest_blocks(message, |blocks| core.update_blocks(blocks));
+    let mut output = Default::default();
+    core.finalize_variable_core(&mut buffer, &mut output);
+    Ok(output.to_vec())
+}
+
+/// Blake2s hash with a 32 bytes (256 bits) digest and a personalization of up to 8 bytes.
+pub fn blake2s_256_personal(message: &[u8], personal: &[u8]) -> Result<Vec<u8>> {
+    use blake2::{
+        digest::core_api::{Buffer, UpdateCore, VariableOutputCore},
+        Blake2sVarCore,
+    };
+    if personal.len() > 8 {
+        return Err(Error::InvalidBytes);
+    }
+    let mut core = Blake2sVarCore::new_with_params(&[], personal, 0, 32);
+    let mut buffer = Buffer::<Blake2sVarCore>::default();
+    buffer.digest_blocks(message, |blocks| core.update_blocks(blocks));
+    let mut output = Default::default();
+    core.finalize_variable_core(&mut buffer, &mut output);
+    Ok(output.to_vec())
+}
@@ -1,10 +1,27 @@
 use crate::{internal::consumable_list::ConsumableList, types::encoded::Encoded};
+use alloc::{borrow::ToOwned, string::String, vec::Vec};
 use num_traits::ToPrimitive;
 
 use crate::{Error, Result};
 
 use super::{coder::ConsumingDecoder, consumable_list::ConsumableBytes};
 
+/// Checks if the value is a non-empty string of decimal digits, e.g. `"42"`.
+pub fn is_natural_string(value: &str) -> bool {
+    !value.is_empty() && value.bytes().all(|byte| byte.is_ascii_digit())
+}
+
+/// Checks if the value is a non-empty string of decimal digits, optionally preceded by `-`, e.g. `"-42"`.
+pub fn is_integer_string(value: &str) -> bool {
+    is_natural_string(value.strip_prefix('-').unwrap_or(value))
+}
+
+/// Checks if the value is a string of hexadecimal digit pairs, optionally prefixed with `0x`, e.g. `"0x0a1B"`.
+pub fn is_hex_string(value: &str) -> bool {
+    let value = value.strip_prefix("0x").unwrap_or(value);
+    value.len().is_multiple_of(2) && value.bytes().all(|byte| byte.is_ascii_hexdigit())
+}
+
 pub fn encode_string(value: &str) -> Vec<u8> {
     let bytes = value.as_bytes();
     encode_bytes(bytes)
@@ -1,4 +1,5 @@
 #![allow(dead_code)]
+#![cfg_attr(not(feature = "std"), no_std)]
 
 //! The `tezos-core` crate defines many common types and basic crypto primitives used by
 //! the `tezos-michelson`, `tezos-operation`, `tezos-rpc` and `tezos-contract` crates.
@@ -19,6 +20,8 @@
 //!         - [Secp256K1PublicKeyHash](crate::types::encoded::Secp256K1PublicKeyHash)
 //!       - **tz3**
 //!         - [P256PublicKeyHash](crate::types::encoded::P256PublicKeyHash)
+//!       - **tz4**
+//!         - [Bls12381PublicKeyHash](crate::types::encoded::Bls12381PublicKeyHash)
 //!     - **KT1**
 //!         - [ContractAddress](crate::types::encoded::ContractAddress)
 //!
@@ -57,7 +60,7 @@
 //! ### `ImplicitAddress` (type)
 //!
 //! [ImplicitAddress](crate::types::encoded::ImplicitAddress) is an address type that covers all the **tz** addresses.
-//! It is an enum with associated values: `Address::TZ1(Ed25519PublicKeyHash)`, `Address::TZ2(Secp256K1PublicKeyHash)` and `Address::TZ3(P256PublicKeyHash)`.
+//! It is an enum with associated values: `Address::TZ1(Ed25519PublicKeyHash)`, `Address::TZ2(Secp256K1PublicKeyHash)`, `Address::TZ3(P256PublicKeyHash)` and `Address::TZ4(Bls12381PublicKeyHash)`.
 //!
 //! Create an [ImplicitAddress](crate::types::encoded::ImplicitAddress) instance from a string value:
 //!
@@ -123,14 +126,23 @@
 //! let ed25519_public_key = Ed25519PublicKey::new("edpktmJqEE79FtfdWse1gqnUey1vNBkB3zNV99Pi95SRAs8NMatczG".into());
 //! ```
 
+extern crate alloc;
+
 pub mod crypto;
 mod error;
 pub mod internal;
 pub mod types;
 pub mod merkle;
+#[cfg(feature = "sapling")]
+pub mod sapling;
+#[cfg(feature = "timelock")]
+pub mod timelock;
 
+use alloc::boxed::Box;
 use cfg_if::cfg_if;
 
+#[cfg(feature = "bls")]
+use crate::crypto::default::DefaultBls12381CryptoProvider;
 #[cfg(feature = "ed25519")]
 use crate::crypto::default::DefaultEd25519CryptoProvider;
 #[cfg(feature = "p256")]
@@ -139,7 +151,7 @@ use crate::crypto::default::DefaultP256CryptoProvider;
 use crate::crypto::default::DefaultSecp256K1CryptoProvider;
 use crate::internal::crypto::Crypto;
 pub use crate::{
-    crypto::CryptoProvider,
+    crypto::{BlsCryptoProvider, CryptoProvider},
     error::{Error, Result},
 };
 
@@ -190,6 +202,7 @@ impl Tezos {
             self.crypto_config.get_ed25519_crypto_provider(),
             self.crypto_config.get_secp256_k1_crypto_provider(),
             self.crypto_config.get_p256_crypto_provider(),
+            self.crypto_config.get_bls12_381_crypto_provider(),
         )
     }
 }
@@ -205,12 +218,17 @@ pub trait CryptoConfig {
     /// Should provide an instance of a structure implementing the [CryptoProvider] trait that implements the trait for p256 curve.
     /// If `None` is returned, then the functionality is considered not available.
     fn get_p256_crypto_provider(&self) -> Option<Box<dyn CryptoProvider>>;
+    /// Should provide an instance of a structure implementing the [BlsCryptoProvider] trait that implements the trait for bls12_381 curve.
+    /// If `None` is returned, then the functionality is considered not available.
+    fn get_bls12_381_crypto_provider(&self) -> Option<Box<dyn BlsCryptoProvider>> {
+        None
+    }
 }
 
 /// A structure providing the default implementation of [CryptoConfig].
 ///
 /// This structure will provide default implementations for the
-/// various crypto provider if the correspoding feature is enabled (`ed25519`, `secp256_k1`, `p256` or `full_crypto` for all of them).
+/// various crypto provider if the correspoding feature is enabled (`ed25519`, `secp256_k1`, `p256` or `full_crypto` for all of them, and `bls`).
 pub struct DefaultCryptoConfig;
 
 impl CryptoConfig for DefaultCryptoConfig {
@@ -249,6 +267,18 @@ impl CryptoConfig for DefaultCryptoConfig {
             }
         }
     }
+
+    fn get_bls12_381_crypto_provider(&self) -> Option<Box<dyn BlsCryptoProvider>> {
+        {
+            cfg_if! {
+                if #[cfg(feature = "bls")] {
+                    Some(Box::new(DefaultBls12381CryptoProvider))
+                } else {
+                    None
+                }
+            }
+        }
+    }
 }
 
 impl Default for Tezos {
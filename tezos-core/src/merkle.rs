@@ -1,3 +1,4 @@
+use alloc::vec::Vec;
 use crate::types::encoded::{
     OperationHash,
     OperationListHash,
@@ -51,6 +52,66 @@ pub fn get_root_hash(inputs: Vec<Vec<u8>>) -> [u8; DIGEST_SIZE] {
     }
 }
 
+/// Inclusion proof of a single input in the Merkle tree whose root is computed by [get_root_hash].
+///
+/// The `path` holds the sibling digests from the leaf level up to the level right below the root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleProof {
+    pub index: usize,
+    pub path: Vec<[u8; DIGEST_SIZE]>,
+}
+
+impl MerkleProof {
+    /// Computes the root hash of the tree the proof was generated from, assuming `input` is its leaf at `index`.
+    pub fn compute_root(&self, input: &[u8]) -> [u8; DIGEST_SIZE] {
+        let (root, _) = self.path.iter().fold((hash_digest(input), self.index), |(node, position), sibling| {
+            let parent = if position % 2 == 0 {
+                hash_digest([node, *sibling].concat().as_slice())
+            } else {
+                hash_digest([*sibling, node].concat().as_slice())
+            };
+            (parent, position / 2)
+        });
+        root
+    }
+
+    /// Checks that `input` is included at `index` in the tree with the given root hash.
+    pub fn verify(&self, input: &[u8], root_hash: &[u8]) -> bool {
+        self.compute_root(input) == root_hash
+    }
+}
+
+/// Generates the inclusion proof of `inputs[index]` in the tree whose root is `get_root_hash(inputs)`.
+///
+/// The missing right nodes of each level are filled with a padding node, which starts as the last leaf
+/// and is hashed with itself at every level, exactly as [get_root_hash] does.
+pub fn get_proof(inputs: Vec<Vec<u8>>, index: usize) -> Result<MerkleProof> {
+    if index >= inputs.len() {
+        return Err(Error::MerkleLeafIndexOutOfBounds);
+    }
+    let mut level: Vec<[u8; DIGEST_SIZE]> = inputs
+        .into_iter()
+        .map(|x| hash_digest(x.as_slice()))
+        .collect();
+    let mut padding = *level.last().expect("Expected at least 1 leaf");
+    let mut position = index;
+    let mut path = Vec::new();
+    while level.len() > 1 {
+        if level.len() % 2 == 1 {
+            level.push(padding);
+        }
+        path.push(level[position ^ 1]);
+        level = level
+            .chunks(2)
+            .map(|pair| hash_digest([pair[0], pair[1]].concat().as_slice()))
+            .collect();
+        padding = hash_digest([padding, padding].concat().as_slice());
+        position /= 2;
+    }
+
+    Ok(MerkleProof { index, path })
+}
+
 fn calc_list_hash<F, T>(hashes: Vec<F>) -> Result<T> where T: Encoded, F: Encoded {
     let inputs = hashes
         .iter()
@@ -60,6 +121,14 @@ fn calc_list_hash<F, T>(hashes: Vec<F>) -> Result<T> where T: Encoded, F: Encode
     T::from_bytes(&root_hash)
 }
 
+fn calc_list_proof<F>(hashes: &[F], index: usize) -> Result<MerkleProof> where F: Encoded {
+    let inputs = hashes
+        .iter()
+        .map(|x| x.to_bytes())
+        .collect::<Result<Vec<_>>>()?;
+    get_proof(inputs, index)
+}
+
 impl TryFrom<Vec<OperationHash>> for OperationListHash {
     type Error = Error;
 
@@ -89,6 +158,55 @@ impl TryFrom<Vec<Vec<OperationHash>>> for OperationListListHash {
     }
 }
 
+/// Inclusion proof of an [OperationHash] in an [OperationListListHash]: the proof of the operation
+/// in its list (validation pass) followed by the proof of that list in the list of lists.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OperationListListProof {
+    pub operation: MerkleProof,
+    pub list: MerkleProof,
+}
+
+impl OperationListHash {
+    /// Generates the proof that `hashes[index]` is included in the [OperationListHash] computed from `hashes`.
+    pub fn inclusion_proof(hashes: &[OperationHash], index: usize) -> Result<MerkleProof> {
+        calc_list_proof(hashes, index)
+    }
+
+    /// Checks that the [OperationHash] is included in this list hash at the position recorded in the proof.
+    pub fn verify_inclusion(&self, hash: &OperationHash, proof: &MerkleProof) -> Result<bool> {
+        Ok(proof.verify(&hash.to_bytes()?, &self.to_bytes()?))
+    }
+}
+
+impl OperationListListHash {
+    /// Generates the proof that `hashes[list_index][index]` is included in the [OperationListListHash]
+    /// computed from `hashes`.
+    pub fn inclusion_proof(
+        hashes: &[Vec<OperationHash>],
+        list_index: usize,
+        index: usize,
+    ) -> Result<OperationListListProof> {
+        let operations = hashes
+            .get(list_index)
+            .ok_or(Error::MerkleLeafIndexOutOfBounds)?;
+        let list_hashes = hashes
+            .iter()
+            .map(|x| OperationListHash::try_from(x.clone()))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(OperationListListProof {
+            operation: OperationListHash::inclusion_proof(operations, index)?,
+            list: calc_list_proof(&list_hashes, list_index)?,
+        })
+    }
+
+    /// Checks that the [OperationHash] is included in this list of lists hash at the positions recorded in the proof.
+    pub fn verify_inclusion(&self, hash: &OperationHash, proof: &OperationListListProof) -> Result<bool> {
+        let list_hash = proof.operation.compute_root(&hash.to_bytes()?);
+        Ok(proof.list.verify(&list_hash, &self.to_bytes()?))
+    }
+}
+
 impl BlockPayloadHash {
     /// Calculates block payload hash given:
     ///     - predecessor [BlockHash]
@@ -115,7 +233,7 @@ impl BlockPayloadHash {
 
 #[cfg(test)]
 mod test {
-    use std::vec;
+    use alloc::vec;
 
     use crate::Result;
 
@@ -235,4 +353,68 @@ mod test {
         assert_eq!(expected, actual);
         Ok(())
     }
+
+    fn operation_hashes(range: core::ops::Range<usize>) -> Vec<OperationHash> {
+        range
+            .map(|i| OperationHash::from_bytes(&hash_digest(&i.to_be_bytes())).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_proof() -> Result<()> {
+        for count in 1..=17 {
+            let inputs: Vec<Vec<u8>> = (0..count).map(|i: usize| i.to_be_bytes().to_vec()).collect();
+            let root_hash = get_root_hash(inputs.clone());
+            for index in 0..count {
+                let proof = get_proof(inputs.clone(), index)?;
+                assert!(proof.verify(&inputs[index], &root_hash), "{}/{}", index, count);
+                let other = (index + 1) % count;
+                assert_eq!(count == 1, proof.verify(&inputs[other], &root_hash));
+            }
+        }
+        assert!(matches!(get_proof(vec![], 0), Err(Error::MerkleLeafIndexOutOfBounds)));
+        assert!(matches!(get_proof(vec![vec![1]], 1), Err(Error::MerkleLeafIndexOutOfBounds)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_operation_list_hash_inclusion() -> Result<()> {
+        let hashes = operation_hashes(0..11);
+        let list_hash = OperationListHash::try_from(hashes.clone())?;
+
+        let proof = OperationListHash::inclusion_proof(&hashes, 6)?;
+        assert!(list_hash.verify_inclusion(&hashes[6], &proof)?);
+        assert!(!list_hash.verify_inclusion(&hashes[5], &proof)?);
+
+        let mut tampered = proof.clone();
+        tampered.index = 7;
+        assert!(!list_hash.verify_inclusion(&hashes[6], &tampered)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_operation_list_list_hash_inclusion() -> Result<()> {
+        let hashes = vec![operation_hashes(0..5), vec![], operation_hashes(5..6), operation_hashes(6..15)];
+        let list_list_hash = OperationListListHash::try_from(hashes.clone())?;
+
+        for (list_index, operations) in hashes.iter().enumerate() {
+            for (index, hash) in operations.iter().enumerate() {
+                let proof = OperationListListHash::inclusion_proof(&hashes, list_index, index)?;
+                assert_eq!(list_index, proof.list.index);
+                assert!(list_list_hash.verify_inclusion(hash, &proof)?);
+            }
+        }
+
+        let proof = OperationListListHash::inclusion_proof(&hashes, 3, 2)?;
+        assert!(!list_list_hash.verify_inclusion(&hashes[0][2], &proof)?);
+        assert!(matches!(
+            OperationListListHash::inclusion_proof(&hashes, 1, 0),
+            Err(Error::MerkleLeafIndexOutOfBounds)
+        ));
+        assert!(matches!(
+            OperationListListHash::inclusion_proof(&hashes, 4, 0),
+            Err(Error::MerkleLeafIndexOutOfBounds)
+        ));
+        Ok(())
+    }
 }
\ No newline at end of file
@@ -0,0 +1,616 @@
+//! Sapling key derivation, from the **sask** spending keys to the **zet1** payment addresses.
+//!
+//! The keys follow [ZIP-32](https://zips.z.cash/zip-0032) as used by the Tezos Sapling integration:
+//! a [SaplingSpendingKey] is an extended spending key, from which a [SaplingViewingKey] (the extended
+//! full viewing key) is derived. The viewing key generates a [SaplingAddress] for each valid
+//! diversifier index, so a single key can hand out unlinkable receiving addresses.
+//!
+//! The viewing key also decrypts the [SaplingCiphertext]s of a `sapling_state` diff to find the
+//! [SaplingNote]s sent to it.
+//!
+//! With the `sapling_builder` feature, a [SaplingTransactionBuilder] builds the `sapling_transaction`
+//! of a shield, shielded transfer or unshield, proving its spends and outputs with the Groth16
+//! parameters of the Sapling circuits (`sapling-spend.params` and `sapling-output.params`) through
+//! the `sapling-crypto` crate.
+//!
+//! # Example
+//!
+//! ```rust
+//! use tezos_core::types::encoded::{Encoded, SaplingSpendingKey};
+//!
+//! let spending_key = SaplingSpendingKey::from_seed(&[0u8; 32]).unwrap();
+//! let viewing_key = spending_key.viewing_key().unwrap();
+//!
+//! // Not every diversifier index is valid, the address of the next valid one is returned.
+//! let (address, index) = viewing_key.address(0).unwrap();
+//! let (next_address, next_index) = viewing_key.address(index + 1).unwrap();
+//! assert!(next_index > index);
+//! assert_ne!(address.value(), next_address.value());
+//! ```
+
+#[cfg(feature = "sapling_builder")]
+mod builder;
+mod group_hash;
+
+use alloc::vec::Vec;
+
+use crypto_secretbox::{
+    aead::{generic_array::GenericArray, Aead, KeyInit},
+    XSalsa20Poly1305,
+};
+use fpe::ff1::{BinaryNumeralString, FF1};
+use group::{cofactor::CofactorGroup, GroupEncoding};
+use jubjub::{ExtendedPoint, Fr, SubgroupPoint};
+use zeroize::Zeroizing;
+
+#[cfg(feature = "sapling_builder")]
+pub use self::builder::{
+    SaplingInput, SaplingOutput, SaplingOutputDescription, SaplingProver, SaplingSpendDescription,
+    SaplingState, SaplingTransaction, SaplingTransactionBuilder,
+};
+use self::group_hash::{find_group_hash, group_hash};
+use crate::{
+    internal::crypto::{blake2b_256_keyed, blake2b_512_personal, blake2s_256_personal},
+    types::encoded::{Encoded, SaplingAddress, SaplingSpendingKey},
+    Error, Result,
+};
+
+const MASTER_KEY_PERSONALIZATION: &[u8] = b"ZcashIP32Sapling";
+const EXPAND_SEED_PERSONALIZATION: &[u8] = b"Zcash_ExpandSeed";
+const IVK_PERSONALIZATION: &[u8] = b"Zcashivk";
+const DIVERSIFIER_PERSONALIZATION: &[u8] = b"Zcash_gd";
+const SPENDING_KEY_GENERATOR_PERSONALIZATION: &[u8] = b"Zcash_G_";
+const NULLIFIER_KEY_GENERATOR_PERSONALIZATION: &[u8] = b"Zcash_H_";
+const KDF_KEY: &[u8] = b"KDFSaplingForTezosV1";
+const SEED_MIN_LENGTH: usize = 32;
+const SEED_MAX_LENGTH: usize = 252;
+const KEY_LENGTH: usize = 32;
+const POINT_BYTES_LENGTH: usize = 32;
+const EXTENDED_KEY_BYTES_LENGTH: usize = 169;
+const DIVERSIFIER_LENGTH: usize = 11;
+const MAX_DIVERSIFIER_INDEX: u128 = (1 << (8 * DIVERSIFIER_LENGTH)) - 1;
+const NONCE_LENGTH: usize = 24;
+const PLAINTEXT_HEADER_LENGTH: usize = DIVERSIFIER_LENGTH + 8 + KEY_LENGTH + 4;
+
+/// A Sapling extended full viewing key, which can generate addresses and see the incoming
+/// and outgoing transactions of a [SaplingSpendingKey], but not spend them.
+///
+/// Its binary form (see [SaplingViewingKey::to_bytes]) is the one exported by `octez-client`.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SaplingViewingKey {
+    depth: u8,
+    parent_fvk_tag: [u8; 4],
+    child_index: u32,
+    chain_code: [u8; KEY_LENGTH],
+    ak: [u8; POINT_BYTES_LENGTH],
+    nk: [u8; POINT_BYTES_LENGTH],
+    ovk: [u8; KEY_LENGTH],
+    dk: [u8; KEY_LENGTH],
+}
+
+/// An encrypted Sapling output, as found in the `commitments_and_ciphertexts` of a `sapling_state` diff.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SaplingCiphertext {
+    /// The value commitment.
+    pub cv: [u8; POINT_BYTES_LENGTH],
+    /// The ephemeral public key of the sender.
+    pub epk: [u8; POINT_BYTES_LENGTH],
+    /// The note encrypted for the receiver.
+    pub payload_enc: Vec<u8>,
+    pub nonce_enc: [u8; NONCE_LENGTH],
+    /// The data allowing the sender to recover the note with its outgoing viewing key.
+    pub payload_out: Vec<u8>,
+    pub nonce_out: [u8; NONCE_LENGTH],
+}
+
+/// A note decrypted with a [SaplingViewingKey].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SaplingNote {
+    /// The address the note was sent to.
+    pub address: SaplingAddress,
+    /// The amount in mutez.
+    pub amount: u64,
+    /// The randomness of the note commitment.
+    pub rcm: [u8; KEY_LENGTH],
+    pub memo: Vec<u8>,
+}
+
+impl SaplingCiphertext {
+    /// Encodes the ciphertext as `cv || epk || payload_enc || nonce_enc || payload_out || nonce_out`,
+    /// where `payload_enc` is prefixed with its length like in a `sapling_transaction`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        [
+            &self.cv[..],
+            &self.epk,
+            &(self.payload_enc.len() as u32).to_be_bytes(),
+            &self.payload_enc,
+            &self.nonce_enc,
+            &self.payload_out,
+            &self.nonce_out,
+        ]
+        .concat()
+    }
+}
+
+impl SaplingSpendingKey {
+    /// Creates the ZIP-32 master spending key from the seed (e.g. a BIP39 seed) of 32 to 252 bytes.
+    pub fn from_seed(seed: &[u8]) -> Result<Self> {
+        if !(SEED_MIN_LENGTH..=SEED_MAX_LENGTH).contains(&seed.len()) {
+            return Err(Error::InvalidSaplingSeed);
+        }
+        let output = Zeroizing::new(blake2b_512_personal(seed, MASTER_KEY_PERSONALIZATION)?);
+        let (key, chain_code) = output.split_at(KEY_LENGTH);
+        let expand = |domain: u8| -> Result<Zeroizing<Vec<u8>>> {
+            Ok(Zeroizing::new(blake2b_512_personal(
+                &[key, &[domain]].concat(),
+                EXPAND_SEED_PERSONALIZATION,
+            )?))
+        };
+        let ask = to_scalar(&expand(0)?);
+        let nsk = to_scalar(&expand(1)?);
+
+        let mut bytes = Zeroizing::new(Vec::with_capacity(EXTENDED_KEY_BYTES_LENGTH));
+        bytes.extend_from_slice(&[0u8; 9]);
+        bytes.extend_from_slice(chain_code);
+        bytes.extend_from_slice(ask.as_slice());
+        bytes.extend_from_slice(nsk.as_slice());
+        bytes.extend_from_slice(&expand(2)?[..KEY_LENGTH]);
+        bytes.extend_from_slice(&expand(0x10)?[..KEY_LENGTH]);
+
+        Self::from_bytes(&bytes)
+    }
+
+    /// Derives the extended full viewing key of this spending key.
+    pub fn viewing_key(&self) -> Result<SaplingViewingKey> {
+        let bytes = Zeroizing::new(self.to_bytes()?);
+        if bytes.len() != EXTENDED_KEY_BYTES_LENGTH {
+            return Err(Error::InvalidSecretKeyBytes);
+        }
+        let ask = scalar(&bytes[41..73]).ok_or(Error::InvalidSecretKeyBytes)?;
+        let nsk = scalar(&bytes[73..105]).ok_or(Error::InvalidSecretKeyBytes)?;
+
+        let g = find_group_hash(SPENDING_KEY_GENERATOR_PERSONALIZATION, &[])?;
+        let h = find_group_hash(NULLIFIER_KEY_GENERATOR_PERSONALIZATION, &[])?;
+
+        Ok(SaplingViewingKey {
+            depth: bytes[0],
+            parent_fvk_tag: bytes[1..5].try_into().unwrap(),
+            child_index: u32::from_le_bytes(bytes[5..9].try_into().unwrap()),
+            chain_code: bytes[9..41].try_into().unwrap(),
+            ak: (g * ask).to_bytes(),
+            nk: (h * nsk).to_bytes(),
+            ovk: bytes[105..137].try_into().unwrap(),
+            dk: bytes[137..169].try_into().unwrap(),
+        })
+    }
+
+    /// Generates the address of the first valid diversifier index starting at `index`.
+    ///
+    /// See [SaplingViewingKey::address].
+    pub fn address(&self, index: u128) -> Result<(SaplingAddress, u128)> {
+        self.viewing_key()?.address(index)
+    }
+}
+
+impl SaplingViewingKey {
+    /// Returns the depth of the key in the ZIP-32 derivation tree.
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    /// Returns the tag of the parent full viewing key, zero for the master key.
+    pub fn parent_fvk_tag(&self) -> &[u8; 4] {
+        &self.parent_fvk_tag
+    }
+
+    /// Returns the index of the key in the ZIP-32 derivation tree.
+    pub fn child_index(&self) -> u32 {
+        self.child_index
+    }
+
+    /// Returns the chain code.
+    pub fn chain_code(&self) -> &[u8; KEY_LENGTH] {
+        &self.chain_code
+    }
+
+    /// Returns the encoded spend validating key `ak`.
+    pub fn ak(&self) -> &[u8; POINT_BYTES_LENGTH] {
+        &self.ak
+    }
+
+    /// Returns the encoded nullifier deriving key `nk`.
+    pub fn nk(&self) -> &[u8; POINT_BYTES_LENGTH] {
+        &self.nk
+    }
+
+    /// Returns the outgoing viewing key.
+    pub fn ovk(&self) -> &[u8; KEY_LENGTH] {
+        &self.ovk
+    }
+
+    /// Returns the diversifier key.
+    pub fn dk(&self) -> &[u8; KEY_LENGTH] {
+        &self.dk
+    }
+
+    /// Computes the incoming viewing key `ivk`, the 251 bits scalar the addresses are derived with.
+    pub fn incoming_viewing_key(&self) -> Result<[u8; KEY_LENGTH]> {
+        let mut ivk: [u8; KEY_LENGTH] =
+            blake2s_256_personal(&[self.ak, self.nk].concat(), IVK_PERSONALIZATION)?
+                .try_into()
+                .unwrap();
+        ivk[KEY_LENGTH - 1] &= 0x07;
+
+        Ok(ivk)
+    }
+
+    /// Generates the address of the first valid diversifier index starting at `index`, and returns
+    /// it along with that index.
+    ///
+    /// About half of the indices produce a diversifier that can't be used, so the returned index
+    /// may be greater than the requested one. The same index always produces the same address.
+    pub fn address(&self, index: u128) -> Result<(SaplingAddress, u128)> {
+        if index > MAX_DIVERSIFIER_INDEX {
+            return Err(Error::InvalidDiversifierIndex);
+        }
+        let ivk = self.ivk()?;
+        for index in index..=MAX_DIVERSIFIER_INDEX {
+            let diversifier = diversifier(&self.dk, index)?;
+            if let Some(g_d) = group_hash(DIVERSIFIER_PERSONALIZATION, &diversifier)? {
+                let pk_d = (g_d * ivk).to_bytes();
+                let address = SaplingAddress::from_bytes(&[&diversifier[..], &pk_d].concat())?;
+                return Ok((address, index));
+            }
+        }
+
+        Err(Error::InvalidDiversifierIndex)
+    }
+
+    /// Decrypts the ciphertext if it was sent to one of the addresses of this key, returns `None` otherwise.
+    ///
+    /// The note commitment is not recomputed, so the amount is only as trustworthy as the sender.
+    pub fn decrypt(&self, ciphertext: &SaplingCiphertext) -> Result<Option<SaplingNote>> {
+        decrypt(&self.ivk()?, ciphertext)
+    }
+
+    /// Decrypts the ciphertexts sent to this key, and returns the notes along with the index of
+    /// their ciphertext.
+    pub fn scan<'a, I>(&self, ciphertexts: I) -> Result<Vec<(usize, SaplingNote)>>
+    where
+        I: IntoIterator<Item = &'a SaplingCiphertext>,
+    {
+        let ivk = self.ivk()?;
+        let mut notes = Vec::new();
+        for (index, ciphertext) in ciphertexts.into_iter().enumerate() {
+            if let Some(note) = decrypt(&ivk, ciphertext)? {
+                notes.push((index, note));
+            }
+        }
+        Ok(notes)
+    }
+
+    /// Encodes the key as `depth || parent_fvk_tag || child_index || chain_code || ak || nk || ovk || dk`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(EXTENDED_KEY_BYTES_LENGTH);
+        bytes.push(self.depth);
+        bytes.extend_from_slice(&self.parent_fvk_tag);
+        bytes.extend_from_slice(&self.child_index.to_le_bytes());
+        bytes.extend_from_slice(&self.chain_code);
+        bytes.extend_from_slice(&self.ak);
+        bytes.extend_from_slice(&self.nk);
+        bytes.extend_from_slice(&self.ovk);
+        bytes.extend_from_slice(&self.dk);
+        bytes
+    }
+
+    /// Decodes a key encoded with [SaplingViewingKey::to_bytes].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() != EXTENDED_KEY_BYTES_LENGTH {
+            return Err(Error::InvalidPublicKeyBytes);
+        }
+        if point(&bytes[41..73]).is_none() || point(&bytes[73..105]).is_none() {
+            return Err(Error::InvalidPublicKeyBytes);
+        }
+
+        Ok(Self {
+            depth: bytes[0],
+            parent_fvk_tag: bytes[1..5].try_into().unwrap(),
+            child_index: u32::from_le_bytes(bytes[5..9].try_into().unwrap()),
+            chain_code: bytes[9..41].try_into().unwrap(),
+            ak: bytes[41..73].try_into().unwrap(),
+            nk: bytes[73..105].try_into().unwrap(),
+            ovk: bytes[105..137].try_into().unwrap(),
+            dk: bytes[137..169].try_into().unwrap(),
+        })
+    }
+
+    fn ivk(&self) -> Result<Fr> {
+        let ivk = Zeroizing::new(self.incoming_viewing_key()?);
+        scalar(ivk.as_slice()).ok_or(Error::InvalidPublicKeyBytes)
+    }
+}
+
+/// Derives the symmetric key from the Diffie-Hellman agreement between the incoming viewing key
+/// and the ephemeral key, then decrypts and decodes the payload
+/// `diversifier || amount || rcm || memo length || memo`.
+fn decrypt(ivk: &Fr, ciphertext: &SaplingCiphertext) -> Result<Option<SaplingNote>> {
+    let epk: Option<ExtendedPoint> = ExtendedPoint::from_bytes(&ciphertext.epk).into();
+    let epk = match epk {
+        Some(epk) => epk,
+        None => return Ok(None),
+    };
+    let shared_secret = Zeroizing::new((epk.clear_cofactor() * ivk).to_bytes());
+    let key = Zeroizing::new(blake2b_256_keyed(
+        &[&shared_secret[..], &ciphertext.epk].concat(),
+        KDF_KEY,
+    )?);
+    let plaintext = match XSalsa20Poly1305::new(GenericArray::from_slice(&key)).decrypt(
+        GenericArray::from_slice(&ciphertext.nonce_enc),
+        ciphertext.payload_enc.as_slice(),
+    ) {
+        Ok(plaintext) => Zeroizing::new(plaintext),
+        Err(_) => return Ok(None),
+    };
+    if plaintext.len() < PLAINTEXT_HEADER_LENGTH {
+        return Ok(None);
+    }
+    let (header, memo) = plaintext.split_at(PLAINTEXT_HEADER_LENGTH);
+    let (diversifier, header) = header.split_at(DIVERSIFIER_LENGTH);
+    let (amount, header) = header.split_at(8);
+    let (rcm, memo_length) = header.split_at(KEY_LENGTH);
+    if u32::from_be_bytes(memo_length.try_into().unwrap()) as usize != memo.len() {
+        return Ok(None);
+    }
+
+    let g_d = match group_hash(DIVERSIFIER_PERSONALIZATION, diversifier)? {
+        Some(g_d) => g_d,
+        None => return Ok(None),
+    };
+    let pk_d = (g_d * ivk).to_bytes();
+
+    Ok(Some(SaplingNote {
+        address: SaplingAddress::from_bytes(&[diversifier, &pk_d].concat())?,
+        amount: u64::from_be_bytes(amount.try_into().unwrap()),
+        rcm: rcm.try_into().unwrap(),
+        memo: memo.to_vec(),
+    }))
+}
+
+/// Encrypts the diversifier index with FF1-AES256 keyed by the diversifier key.
+fn diversifier(dk: &[u8; KEY_LENGTH], index: u128) -> Result<[u8; DIVERSIFIER_LENGTH]> {
+    let ff1 = FF1::<aes::Aes256>::new(dk, 2).map_err(|_| Error::InvalidDiversifierIndex)?;
+    let index = BinaryNumeralString::from_bytes_le(&index.to_le_bytes()[..DIVERSIFIER_LENGTH]);
+    ff1.encrypt(&[], &index)
+        .map_err(|_| Error::InvalidDiversifierIndex)?
+        .to_bytes_le()
+        .try_into()
+        .map_err(|_| Error::InvalidDiversifierIndex)
+}
+
+/// Reads a point of the prime order subgroup, returns `None` if the bytes don't encode one.
+fn point(bytes: &[u8]) -> Option<SubgroupPoint> {
+    let bytes: [u8; POINT_BYTES_LENGTH] = bytes.try_into().ok()?;
+    SubgroupPoint::from_bytes(&bytes).into()
+}
+
+/// Reads a canonical little-endian scalar of the Jubjub subgroup.
+fn scalar(bytes: &[u8]) -> Option<Fr> {
+    let bytes: Zeroizing<[u8; KEY_LENGTH]> = Zeroizing::new(bytes.try_into().ok()?);
+    Fr::from_bytes(&bytes).into()
+}
+
+/// Reduces the little-endian bytes modulo the Jubjub subgroup order, as the ZIP-32 `ToScalar`.
+fn to_scalar(bytes: &[u8]) -> Zeroizing<[u8; KEY_LENGTH]> {
+    let mut wide = Zeroizing::new([0u8; 64]);
+    wide.copy_from_slice(bytes);
+    Zeroizing::new(Fr::from_bytes_wide(&wide).to_bytes())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // ZIP-32 Sapling test vector 1, the master key of the seed `0x00, 0x01, ..., 0x1f`
+    const SPENDING_KEY: &str = "sask27SLmU9herddJBRVPqW87mW9oZ9cAUyTH9eWKvLGMhtHEkVSUVTR2Gb3VQENvomb6o2gY4xv2MVK7ec78ytmXvimhSA3BHa9BjrWDj4FWHsrZRvvMuWMwQCJoLkBoDEn1LmtANeWZQDUuHvMuSeeJtDgKx1hYfNEHWs8grJcHpbszseNT5VpzrYLjVkUerxmCnUSKe2RiUc32PzxmAUk3ZVd99eCvzYDkX47PsSnuM7uC";
+
+    fn spending_key() -> SaplingSpendingKey {
+        SaplingSpendingKey::new(SPENDING_KEY.into()).unwrap()
+    }
+
+    #[test]
+    fn test_from_seed() -> Result<()> {
+        let seed: Vec<u8> = (0u8..32).collect();
+        let spending_key = SaplingSpendingKey::from_seed(&seed)?;
+        assert_eq!(spending_key.value(), SPENDING_KEY);
+
+        let bytes = spending_key.to_bytes()?;
+        assert_eq!(
+            hex::encode(&bytes[41..73]),
+            "b6c00c93d36032b9a268e99e86a860776560bf0e83c1a10b51f607c954742506"
+        );
+        assert_eq!(
+            hex::encode(&bytes[105..137]),
+            "395884890323b9d4933c021db89bcf767df21977b2ff0683848321a4df4afb21"
+        );
+
+        assert!(matches!(
+            SaplingSpendingKey::from_seed(&[0u8; 31]),
+            Err(Error::InvalidSaplingSeed)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_spending_key_debug_redacted() {
+        let debug = format!("{:?}", spending_key());
+        assert_eq!(debug, "SaplingSpendingKey(\"<redacted>\")");
+        assert!(!debug.contains(SPENDING_KEY));
+    }
+
+    #[test]
+    fn test_viewing_key() -> Result<()> {
+        let viewing_key = spending_key().viewing_key()?;
+        assert_eq!(
+            hex::encode(viewing_key.ak()),
+            "93442e5feffbff16e7217202dc7306729ffffe85af5683bce2642e3eeb5d3871"
+        );
+        assert_eq!(
+            hex::encode(viewing_key.nk()),
+            "dce8e7edece04b8950417f85ba57691b783c45b1a27422db1693dceb67b10106"
+        );
+        assert_eq!(
+            hex::encode(viewing_key.incoming_viewing_key()?),
+            "4847a130e799d3dbea36a1c16467d621fb2d80e30b3b1d1a426893415dad6601"
+        );
+
+        let bytes = viewing_key.to_bytes();
+        assert_eq!(bytes.len(), EXTENDED_KEY_BYTES_LENGTH);
+        assert!(SaplingViewingKey::from_bytes(&bytes)? == viewing_key);
+        assert!(matches!(
+            SaplingViewingKey::from_bytes(&bytes[1..]),
+            Err(Error::InvalidPublicKeyBytes)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_diversifier() -> Result<()> {
+        // ZIP-32 Sapling test vector 1, the default diversifiers of the master key
+        let dk: [u8; KEY_LENGTH] =
+            hex::decode("77c17cb75b7796afb39f0f3e91c924607da56fa9a20e283509bc8a3ef996a172")
+                .unwrap()
+                .try_into()
+                .unwrap();
+        let values = [
+            (0, "d8621b981cf300e9d4cc89"),
+            (1, "48ea17a199c84bd1baa5d4"),
+            (2, "83909a0422b4cdc65ea409"),
+        ];
+        for (index, expected) in values {
+            assert_eq!(hex::encode(diversifier(&dk, index)?), expected);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_address() -> Result<()> {
+        let spending_key = spending_key();
+        let values = [
+            (
+                0,
+                0,
+                "zet14CKqHBhiTwEBh3DJJcEp3dPvA6a4xcyoaVUFT7fz23zErGQ2inFmzaTFt7iAmw9sf",
+            ),
+            (
+                1,
+                1,
+                "zet131AmBrGAmayScS5cvxuE8B31b6nkNxtAuNnXtTHMuPjBCbKmoet1fCmDBTL6rA8GK",
+            ),
+            (
+                2,
+                5,
+                "zet12boPYiWJuFGnpPaCmy7dP8umpkZWPTcPNUzTDPRbbsuDJ9Rc1524zH3x6EPsazfXj",
+            ),
+            (
+                10,
+                10,
+                "zet14TJt9mrqFGtEFBqB4FGJJq744xvvBqHzvhQ8WW4CWJch3DptHVQPauoT7EZB2UHSy",
+            ),
+        ];
+        for (index, expected_index, expected_address) in values {
+            let (address, address_index) = spending_key.address(index)?;
+            assert_eq!(address_index, expected_index);
+            assert_eq!(address.value(), expected_address);
+        }
+
+        assert!(matches!(
+            spending_key.address(MAX_DIVERSIFIER_INDEX + 1),
+            Err(Error::InvalidDiversifierIndex)
+        ));
+        Ok(())
+    }
+
+    /// Encrypts a note to the address the way a sender does, with the ephemeral secret `esk`.
+    fn encrypt(address: &SaplingAddress, amount: u64, memo: &[u8], esk: u64) -> SaplingCiphertext {
+        let address = address.to_bytes().unwrap();
+        let (diversifier, pk_d) = address.split_at(DIVERSIFIER_LENGTH);
+        let g_d = group_hash(DIVERSIFIER_PERSONALIZATION, diversifier)
+            .unwrap()
+            .unwrap();
+        let pk_d = point(pk_d).unwrap();
+        let esk = Fr::from(esk);
+        let epk = (g_d * esk).to_bytes();
+        let shared_secret = (ExtendedPoint::from(pk_d).clear_cofactor() * esk).to_bytes();
+        let key = blake2b_256_keyed(&[&shared_secret[..], &epk].concat(), KDF_KEY).unwrap();
+
+        let plaintext = [
+            diversifier,
+            &amount.to_be_bytes(),
+            &[7u8; KEY_LENGTH],
+            &(memo.len() as u32).to_be_bytes(),
+            memo,
+        ]
+        .concat();
+        let nonce_enc = [1u8; NONCE_LENGTH];
+        let payload_enc = XSalsa20Poly1305::new(GenericArray::from_slice(&key))
+            .encrypt(GenericArray::from_slice(&nonce_enc), plaintext.as_slice())
+            .unwrap();
+
+        SaplingCiphertext {
+            cv: [0u8; POINT_BYTES_LENGTH],
+            epk,
+            payload_enc,
+            nonce_enc,
+            payload_out: vec![0u8; 80],
+            nonce_out: [2u8; NONCE_LENGTH],
+        }
+    }
+
+    #[test]
+    fn test_decrypt() -> Result<()> {
+        let viewing_key = spending_key().viewing_key()?;
+        let (address, _) = viewing_key.address(10)?;
+        let ciphertext = encrypt(&address, 1_000_000, b"memo", 123456789);
+
+        let note = viewing_key
+            .decrypt(&ciphertext)?
+            .expect("note sent to the key");
+        assert_eq!(note.address, address);
+        assert_eq!(note.amount, 1_000_000);
+        assert_eq!(note.rcm, [7u8; KEY_LENGTH]);
+        assert_eq!(note.memo, b"memo");
+
+        let other_viewing_key = SaplingSpendingKey::from_seed(&[1u8; 32])?.viewing_key()?;
+        assert_eq!(other_viewing_key.decrypt(&ciphertext)?, None);
+
+        let mut tampered = ciphertext;
+        tampered.payload_enc[0] ^= 1;
+        assert_eq!(viewing_key.decrypt(&tampered)?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_scan() -> Result<()> {
+        let viewing_key = spending_key().viewing_key()?;
+        let other_viewing_key = SaplingSpendingKey::from_seed(&[1u8; 32])?.viewing_key()?;
+        let (address, _) = viewing_key.address(0)?;
+        let (other_address, _) = other_viewing_key.address(0)?;
+        let ciphertexts = [
+            encrypt(&other_address, 1, &[], 1),
+            encrypt(&address, 2, &[], 2),
+            encrypt(&other_address, 3, &[], 3),
+            encrypt(&address, 4, &[0u8; 8], 4),
+        ];
+
+        let notes = viewing_key.scan(&ciphertexts)?;
+        assert_eq!(
+            notes
+                .iter()
+                .map(|(index, note)| (*index, note.amount))
+                .collect::<Vec<_>>(),
+            vec![(1, 2), (3, 4)]
+        );
+        assert_eq!(notes[1].1.memo, [0u8; 8]);
+        Ok(())
+    }
+}
@@ -0,0 +1,983 @@
+use std::{collections::HashSet, fs, path::Path, string::ToString, vec::Vec};
+
+use crypto_secretbox::{
+    aead::{generic_array::GenericArray, Aead, KeyInit},
+    XSalsa20Poly1305,
+};
+use group::{cofactor::CofactorGroup, ff::Field, GroupEncoding};
+use jubjub::{ExtendedPoint, Fr};
+use rand_core::{CryptoRng, RngCore};
+use sapling_crypto::{
+    circuit::{Output, OutputParameters, Spend, SpendParameters, ValueCommitmentOpening},
+    keys::ExpandedSpendingKey,
+    merkle_hash,
+    prover::{OutputProver, SpendProver},
+    value::{NoteValue, TrapdoorSum, ValueCommitTrapdoor, ValueCommitment},
+    Note, NullifierDerivingKey, PaymentAddress, Rseed, NOTE_COMMITMENT_TREE_DEPTH,
+};
+use zeroize::Zeroizing;
+
+use super::{
+    point, scalar, SaplingCiphertext, SaplingNote, SaplingViewingKey, DIVERSIFIER_LENGTH, KDF_KEY,
+    KEY_LENGTH, NONCE_LENGTH, POINT_BYTES_LENGTH,
+};
+use crate::{
+    internal::crypto::{blake2b, blake2b_256_keyed},
+    types::encoded::{
+        ChainId, ContractAddress, Encoded, ImplicitAddress, SaplingAddress, SaplingSpendingKey,
+    },
+    Error, Result,
+};
+
+const OCK_KEY: &[u8] = b"OCK_keystringderivation_TEZOS";
+const PROOF_LENGTH: usize = 192;
+const SIGNATURE_LENGTH: usize = 64;
+const ADDRESS_LENGTH: usize = DIVERSIFIER_LENGTH + POINT_BYTES_LENGTH;
+const PARAMETERS_HASH_LENGTH: usize = 64;
+const SPEND_PARAMETERS_HASH: &str = "8270785a1a0d0bc77196f000ee6d221c9c9894f55307bd9357c3f0105d31ca63991ab91324160d8f53e2bbd3c2633a6eb8bdf5205d822e7f3f73edac51b2b70c";
+const OUTPUT_PARAMETERS_HASH: &str = "657e3d38dbb5cb5e7dd2970e8b03d69b4787dd907285b5a7f0790dcc8072f60bf593b32cc2d1c030e00ff5ae64bf84c5c3beb84ddc841d48264b4a171744d028";
+/// The uncommitted leaf of the commitment tree, the scalar `1`.
+const EMPTY_LEAF: [u8; KEY_LENGTH] = {
+    let mut leaf = [0u8; KEY_LENGTH];
+    leaf[0] = 1;
+    leaf
+};
+
+/// The Groth16 parameters of the Sapling spend and output circuits, the `sapling-spend.params`
+/// and `sapling-output.params` files `octez-client` uses (usually found in `~/.zcash-params`).
+pub struct SaplingProver {
+    spend: SpendParameters,
+    output: OutputParameters,
+}
+
+impl SaplingProver {
+    /// Loads the parameters from the `sapling-spend.params` and `sapling-output.params` files.
+    pub fn from_files<P: AsRef<Path>, Q: AsRef<Path>>(spend: P, output: Q) -> Result<Self> {
+        let read = |path: &Path| {
+            fs::read(path).map_err(|error| Error::InvalidSaplingParameters {
+                description: std::format!("{}: {}", path.display(), error),
+            })
+        };
+        Self::from_bytes(&read(spend.as_ref())?, &read(output.as_ref())?)
+    }
+
+    /// Reads the parameters from the content of the `sapling-spend.params` and `sapling-output.params` files.
+    ///
+    /// The files are checked against the BLAKE2b hashes of the published parameters, which is
+    /// much faster than validating each point while reading them.
+    pub fn from_bytes(spend: &[u8], output: &[u8]) -> Result<Self> {
+        check_parameters_hash("sapling-spend.params", spend, SPEND_PARAMETERS_HASH)?;
+        check_parameters_hash("sapling-output.params", output, OUTPUT_PARAMETERS_HASH)?;
+        let invalid = |error: std::io::Error| Error::InvalidSaplingParameters {
+            description: error.to_string(),
+        };
+
+        Ok(Self {
+            spend: SpendParameters::read(spend, false).map_err(invalid)?,
+            output: OutputParameters::read(output, false).map_err(invalid)?,
+        })
+    }
+}
+
+/// The content of a `sapling_state`: the memo size and the commitments, ciphertexts and
+/// nullifiers of all the transactions applied to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SaplingState {
+    /// The size of the memos of the notes, the `memo_size` of the `sapling_state` type.
+    pub memo_size: u16,
+    /// The commitments of the notes in the order of their position in the commitment tree,
+    /// along with their ciphertext.
+    pub commitments_and_ciphertexts: Vec<([u8; KEY_LENGTH], SaplingCiphertext)>,
+    /// The nullifiers of the spent notes.
+    pub nullifiers: Vec<[u8; KEY_LENGTH]>,
+}
+
+/// A note of a [SaplingState] that hasn't been spent yet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SaplingInput {
+    pub note: SaplingNote,
+    /// The position of the note commitment in the commitment tree.
+    pub position: u64,
+    pub nullifier: [u8; KEY_LENGTH],
+}
+
+/// A note to create.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SaplingOutput {
+    pub address: SaplingAddress,
+    /// The amount in mutez.
+    pub amount: u64,
+    /// The memo, padded with zeros up to the memo size of the state.
+    pub memo: Vec<u8>,
+}
+
+/// A spent note of a [SaplingTransaction].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SaplingSpendDescription {
+    /// The value commitment.
+    pub cv: [u8; POINT_BYTES_LENGTH],
+    pub nullifier: [u8; KEY_LENGTH],
+    /// The randomized spend validating key.
+    pub rk: [u8; POINT_BYTES_LENGTH],
+    pub proof: [u8; PROOF_LENGTH],
+    /// The signature of the spend by the randomized spend authorizing key.
+    pub signature: [u8; SIGNATURE_LENGTH],
+}
+
+/// A created note of a [SaplingTransaction].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SaplingOutputDescription {
+    /// The note commitment.
+    pub cm: [u8; KEY_LENGTH],
+    pub proof: [u8; PROOF_LENGTH],
+    pub ciphertext: SaplingCiphertext,
+}
+
+/// A shielded transaction, the `sapling_transaction` parameter of a shielded contract call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SaplingTransaction {
+    pub inputs: Vec<SaplingSpendDescription>,
+    pub outputs: Vec<SaplingOutputDescription>,
+    pub binding_signature: [u8; SIGNATURE_LENGTH],
+    /// The mutez leaving the shielded pool, negative when more are shielded than unshielded.
+    pub balance: i64,
+    /// The root of the commitment tree the inputs are proven against.
+    pub root: [u8; KEY_LENGTH],
+    /// The data the transaction is bound to, e.g. the `PACK`ed key hash receiving unshielded mutez.
+    pub bound_data: Vec<u8>,
+}
+
+/// Builds the [SaplingTransaction]s of a shielded contract, proving them with a [SaplingProver].
+///
+/// The transactions are bound to the contract and the chain they are built for, and to the
+/// current [SaplingState] of the contract: they become invalid once the tree root is too old.
+///
+/// # Example
+///
+/// ```no_run
+/// use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+/// use tezos_core::{
+///     sapling::{SaplingProver, SaplingState, SaplingTransactionBuilder},
+///     types::encoded::{ChainId, ContractAddress, Encoded, SaplingSpendingKey},
+/// };
+///
+/// let prover = SaplingProver::from_files(
+///     "/home/tezos/.zcash-params/sapling-spend.params",
+///     "/home/tezos/.zcash-params/sapling-output.params",
+/// )
+/// .unwrap();
+/// let state = SaplingState::new(8);
+/// let contract: ContractAddress = "KT1UcwQtaztLSq8oufdXAtWpRTfFySCj7gFM".try_into().unwrap();
+/// let chain_id: ChainId = "NetXdQprcVkpaWU".try_into().unwrap();
+/// let (address, _) = SaplingSpendingKey::from_seed(&[0u8; 32])
+///     .unwrap()
+///     .address(0)
+///     .unwrap();
+/// // In practice, use a generator seeded from a secure source of entropy, e.g. `rand_core::OsRng`.
+/// let mut rng = ChaCha20Rng::seed_from_u64(0);
+///
+/// let transaction = SaplingTransactionBuilder::new(&prover, &state, &contract, &chain_id)
+///     .shield(&address, 1_000_000, b"memo", &mut rng)
+///     .unwrap();
+/// let bytes = transaction.to_bytes();
+/// ```
+pub struct SaplingTransactionBuilder<'a> {
+    prover: &'a SaplingProver,
+    state: &'a SaplingState,
+    anti_replay: Vec<u8>,
+}
+
+impl SaplingState {
+    /// Creates an empty state whose notes have memos of `memo_size` bytes.
+    pub fn new(memo_size: u16) -> Self {
+        Self {
+            memo_size,
+            commitments_and_ciphertexts: Vec::new(),
+            nullifiers: Vec::new(),
+        }
+    }
+
+    /// Computes the root of the commitment tree.
+    pub fn root(&self) -> [u8; KEY_LENGTH] {
+        self.merkle_paths(&[]).0
+    }
+
+    /// Finds the notes sent to the viewing key which haven't been spent yet.
+    ///
+    /// Unlike [SaplingViewingKey::scan], the notes whose commitment doesn't match the one in the
+    /// state are skipped, as they can't be spent.
+    pub fn unspent_notes(&self, viewing_key: &SaplingViewingKey) -> Result<Vec<SaplingInput>> {
+        let nk = NullifierDerivingKey(point(viewing_key.nk()).ok_or(Error::InvalidPublicKeyBytes)?);
+        let nullifiers: HashSet<&[u8; KEY_LENGTH]> = self.nullifiers.iter().collect();
+        let notes = viewing_key.scan(
+            self.commitments_and_ciphertexts
+                .iter()
+                .map(|(_, ciphertext)| ciphertext),
+        )?;
+
+        let mut inputs = Vec::new();
+        for (position, note) in notes {
+            let commitment = sapling_note(&note)?;
+            if commitment.cmu().to_bytes() != self.commitments_and_ciphertexts[position].0 {
+                continue;
+            }
+            let position = position as u64;
+            let nullifier = commitment.nf(&nk, position).0;
+            if !nullifiers.contains(&nullifier) {
+                inputs.push(SaplingInput {
+                    note,
+                    position,
+                    nullifier,
+                });
+            }
+        }
+        Ok(inputs)
+    }
+
+    /// Applies the transaction to the state, as the `SAPLING_VERIFY_UPDATE` instruction does once
+    /// the transaction is verified.
+    pub fn apply(&mut self, transaction: &SaplingTransaction) {
+        self.commitments_and_ciphertexts.extend(
+            transaction
+                .outputs
+                .iter()
+                .map(|output| (output.cm, output.ciphertext.clone())),
+        );
+        self.nullifiers
+            .extend(transaction.inputs.iter().map(|input| input.nullifier));
+    }
+
+    /// Computes the root of the commitment tree and the authentication paths of the leaves at
+    /// `positions`, from the bottom of the tree to the top.
+    fn merkle_paths(&self, positions: &[u64]) -> ([u8; KEY_LENGTH], Vec<Vec<[u8; KEY_LENGTH]>>) {
+        let depth = NOTE_COMMITMENT_TREE_DEPTH as usize;
+        let mut level: Vec<[u8; KEY_LENGTH]> = self
+            .commitments_and_ciphertexts
+            .iter()
+            .map(|(commitment, _)| *commitment)
+            .collect();
+        let mut empty = EMPTY_LEAF;
+        let mut paths = std::vec![Vec::with_capacity(depth); positions.len()];
+
+        for height in 0..depth {
+            for (path, position) in paths.iter_mut().zip(positions) {
+                let sibling = ((position >> height) ^ 1) as usize;
+                path.push(level.get(sibling).copied().unwrap_or(empty));
+            }
+            level = level
+                .chunks(2)
+                .map(|pair| merkle_hash(height, &pair[0], pair.get(1).unwrap_or(&empty)))
+                .collect();
+            empty = merkle_hash(height, &empty, &empty);
+        }
+
+        (level.first().copied().unwrap_or(empty), paths)
+    }
+}
+
+impl SaplingTransaction {
+    /// Encodes the transaction as the bytes of a `sapling_transaction` value.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let inputs: Vec<u8> = self
+            .inputs
+            .iter()
+            .flat_map(|input| input.to_bytes())
+            .collect();
+        let outputs: Vec<u8> = self
+            .outputs
+            .iter()
+            .flat_map(|output| output.to_bytes())
+            .collect();
+
+        [
+            &(inputs.len() as u32).to_be_bytes()[..],
+            &inputs,
+            &(outputs.len() as u32).to_be_bytes(),
+            &outputs,
+            &self.binding_signature,
+            &self.balance.to_be_bytes(),
+            &self.root,
+            &(self.bound_data.len() as u32).to_be_bytes(),
+            &self.bound_data,
+        ]
+        .concat()
+    }
+}
+
+impl SaplingSpendDescription {
+    /// Encodes the input as `cv || nullifier || rk || proof || signature`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        [self.unsigned_bytes(), self.signature.to_vec()].concat()
+    }
+
+    fn unsigned_bytes(&self) -> Vec<u8> {
+        [&self.cv[..], &self.nullifier, &self.rk, &self.proof].concat()
+    }
+}
+
+impl SaplingOutputDescription {
+    /// Encodes the output as `cm || proof || ciphertext`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        [&self.cm[..], &self.proof, &self.ciphertext.to_bytes()].concat()
+    }
+}
+
+impl<'a> SaplingTransactionBuilder<'a> {
+    /// Creates a builder for the transactions of the `contract` on the chain `chain_id`,
+    /// whose current state is `state`.
+    pub fn new(
+        prover: &'a SaplingProver,
+        state: &'a SaplingState,
+        contract: &ContractAddress,
+        chain_id: &ChainId,
+    ) -> Self {
+        Self {
+            prover,
+            state,
+            anti_replay: [contract.value(), chain_id.value()].concat().into_bytes(),
+        }
+    }
+
+    /// Builds a transaction creating a note of `amount` mutez, which must be sent along with the
+    /// contract call.
+    pub fn shield<R: RngCore + CryptoRng>(
+        &self,
+        to: &SaplingAddress,
+        amount: u64,
+        memo: &[u8],
+        rng: &mut R,
+    ) -> Result<SaplingTransaction> {
+        let output = SaplingOutput {
+            address: to.clone(),
+            amount,
+            memo: memo.to_vec(),
+        };
+        self.build(None, &[], &[output], &[], rng)
+    }
+
+    /// Builds a transaction sending `amount` mutez from the unspent notes of the spending key to
+    /// the address, the change going back to the first address of the key.
+    pub fn transfer<R: RngCore + CryptoRng>(
+        &self,
+        from: &SaplingSpendingKey,
+        to: &SaplingAddress,
+        amount: u64,
+        memo: &[u8],
+        rng: &mut R,
+    ) -> Result<SaplingTransaction> {
+        let (inputs, mut outputs) = self.select_notes(from, amount)?;
+        outputs.insert(
+            0,
+            SaplingOutput {
+                address: to.clone(),
+                amount,
+                memo: memo.to_vec(),
+            },
+        );
+        self.build(Some(from), &inputs, &outputs, &[], rng)
+    }
+
+    /// Builds a transaction taking `amount` mutez out of the unspent notes of the spending key,
+    /// which the contract sends to the implicit account, the change going back to the first
+    /// address of the key.
+    ///
+    /// The transaction is bound to the `PACK`ed key hash of the account, as the shielded tez
+    /// contract of the Tezos test suite expects.
+    pub fn unshield<R: RngCore + CryptoRng>(
+        &self,
+        from: &SaplingSpendingKey,
+        to: &ImplicitAddress,
+        amount: u64,
+        rng: &mut R,
+    ) -> Result<SaplingTransaction> {
+        let (inputs, outputs) = self.select_notes(from, amount)?;
+        let key_hash = to.to_bytes()?;
+        let bound_data = [
+            &[0x05, 0x0a][..],
+            &(key_hash.len() as u32).to_be_bytes(),
+            &key_hash,
+        ]
+        .concat();
+        self.build(Some(from), &inputs, &outputs, &bound_data, rng)
+    }
+
+    /// Builds a transaction spending the inputs, which must be unspent notes of the spending
+    /// key, and creating the outputs.
+    ///
+    /// The spending key is also used to encrypt the outputs for the sender, a random outgoing
+    /// viewing key is used when no key is given, in which case there must be no inputs.
+    pub fn build<R: RngCore + CryptoRng>(
+        &self,
+        spending_key: Option<&SaplingSpendingKey>,
+        inputs: &[SaplingInput],
+        outputs: &[SaplingOutput],
+        bound_data: &[u8],
+        rng: &mut R,
+    ) -> Result<SaplingTransaction> {
+        let key = match spending_key {
+            Some(key) => Some(expanded_spending_key(key)?),
+            None if inputs.is_empty() => None,
+            None => return Err(Error::InvalidSaplingNote),
+        };
+        let ovk = match &key {
+            Some(key) => Zeroizing::new(key.ovk.0),
+            None => {
+                let mut ovk = Zeroizing::new([0u8; KEY_LENGTH]);
+                rng.fill_bytes(ovk.as_mut_slice());
+                ovk
+            }
+        };
+
+        let positions: Vec<u64> = inputs.iter().map(|input| input.position).collect();
+        let (root, paths) = self.state.merkle_paths(&positions);
+        let anchor: Option<bls12_381::Scalar> = bls12_381::Scalar::from_bytes(&root).into();
+        let anchor = anchor.ok_or(Error::InvalidSaplingNote)?;
+        let mut bsk = TrapdoorSum::zero();
+        let mut balance = 0i128;
+
+        let mut spend_descriptions = Vec::with_capacity(inputs.len());
+        for (input, path) in inputs.iter().zip(paths) {
+            // `key` is set whenever there are inputs.
+            let key = key.as_ref().ok_or(Error::InvalidSaplingNote)?;
+            let (description, rcv) = self.spend(key, input, path, anchor, rng)?;
+            spend_descriptions.push(description);
+            bsk += &rcv;
+            balance += i128::from(input.note.amount);
+        }
+
+        let mut output_descriptions = Vec::with_capacity(outputs.len());
+        for output in outputs {
+            let (description, rcv) = self.output(&ovk, output, rng)?;
+            output_descriptions.push(description);
+            bsk -= &rcv;
+            balance -= i128::from(output.amount);
+        }
+
+        let sighash =
+            self.binding_sighash(&spend_descriptions, &output_descriptions, bound_data)?;
+
+        Ok(SaplingTransaction {
+            inputs: spend_descriptions,
+            outputs: output_descriptions,
+            binding_signature: bsk.into_bsk().sign(&mut *rng, &sighash).into(),
+            balance: balance.try_into().map_err(|_| Error::MutezOverflow)?,
+            root,
+            bound_data: bound_data.to_vec(),
+        })
+    }
+
+    /// Selects unspent notes of the key, in the order of their position, until they cover the
+    /// amount, and returns them along with the change output if any.
+    fn select_notes(
+        &self,
+        key: &SaplingSpendingKey,
+        amount: u64,
+    ) -> Result<(Vec<SaplingInput>, Vec<SaplingOutput>)> {
+        let viewing_key = key.viewing_key()?;
+        let mut inputs = Vec::new();
+        let mut total = 0u64;
+        for input in self.state.unspent_notes(&viewing_key)? {
+            if total >= amount && !inputs.is_empty() {
+                break;
+            }
+            total = total
+                .checked_add(input.note.amount)
+                .ok_or(Error::MutezOverflow)?;
+            inputs.push(input);
+        }
+        if total < amount {
+            return Err(Error::InsufficientSaplingBalance);
+        }
+
+        let mut outputs = Vec::new();
+        if total > amount {
+            outputs.push(SaplingOutput {
+                address: viewing_key.address(0)?.0,
+                amount: total - amount,
+                memo: Vec::new(),
+            });
+        }
+        Ok((inputs, outputs))
+    }
+
+    /// Proves and signs the spend of the input, returns it along with its value commitment trapdoor.
+    fn spend<R: RngCore + CryptoRng>(
+        &self,
+        key: &ExpandedSpendingKey,
+        input: &SaplingInput,
+        path: Vec<[u8; KEY_LENGTH]>,
+        anchor: bls12_381::Scalar,
+        rng: &mut R,
+    ) -> Result<(SaplingSpendDescription, ValueCommitTrapdoor)> {
+        let proof_generation_key = key.proof_generation_key();
+        let viewing_key = proof_generation_key.to_viewing_key();
+        let note = sapling_note(&input.note)?;
+        if viewing_key.to_payment_address(*note.recipient().diversifier()) != Some(note.recipient())
+            || self
+                .state
+                .commitments_and_ciphertexts
+                .get(input.position as usize)
+                .map(|(commitment, _)| *commitment)
+                != Some(note.cmu().to_bytes())
+        {
+            return Err(Error::InvalidSaplingNote);
+        }
+
+        let auth_path = path
+            .iter()
+            .enumerate()
+            .map(|(height, node)| {
+                let node: Option<bls12_381::Scalar> = bls12_381::Scalar::from_bytes(node).into();
+                Ok(Some((
+                    node.ok_or(Error::InvalidSaplingNote)?,
+                    (input.position >> height) & 1 == 1,
+                )))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let value = NoteValue::from_raw(input.note.amount);
+        let rcv = ValueCommitTrapdoor::random(&mut *rng);
+        let ar = Fr::random(&mut *rng);
+        let circuit = Spend {
+            value_commitment_opening: Some(ValueCommitmentOpening {
+                value,
+                randomness: rcv.inner(),
+            }),
+            proof_generation_key: Some(proof_generation_key),
+            payment_address: Some(note.recipient()),
+            commitment_randomness: Some(note.rcm()),
+            ar: Some(ar),
+            auth_path,
+            anchor: Some(anchor),
+        };
+
+        let mut description = SaplingSpendDescription {
+            cv: ValueCommitment::derive(value, rcv.clone()).to_bytes(),
+            nullifier: note.nf(&viewing_key.nk, input.position).0,
+            rk: viewing_key.rk(ar).into(),
+            proof: SpendParameters::encode_proof(self.prover.spend.create_proof(circuit, rng)),
+            signature: [0u8; SIGNATURE_LENGTH],
+        };
+        let sighash = self.hash(&description.unsigned_bytes())?;
+        description.signature = key.ask.randomize(&ar).sign(&mut *rng, &sighash).into();
+
+        Ok((description, rcv))
+    }
+
+    /// Proves and encrypts the output, returns it along with its value commitment trapdoor.
+    fn output<R: RngCore + CryptoRng>(
+        &self,
+        ovk: &[u8; KEY_LENGTH],
+        output: &SaplingOutput,
+        rng: &mut R,
+    ) -> Result<(SaplingOutputDescription, ValueCommitTrapdoor)> {
+        let memo_size = self.state.memo_size as usize;
+        if output.memo.len() > memo_size {
+            return Err(Error::InvalidSaplingMemo);
+        }
+        let address: [u8; ADDRESS_LENGTH] = output
+            .address
+            .to_bytes()?
+            .try_into()
+            .map_err(|_| Error::InvalidAddress)?;
+        let address = PaymentAddress::from_bytes(&address).ok_or(Error::InvalidAddress)?;
+        let g_d = address.diversifier().g_d().ok_or(Error::InvalidAddress)?;
+
+        let value = NoteValue::from_raw(output.amount);
+        let rcv = ValueCommitTrapdoor::random(&mut *rng);
+        let rcm = Fr::random(&mut *rng);
+        let esk = Fr::random(&mut *rng);
+        let note = Note::from_parts(address, value, Rseed::BeforeZip212(rcm));
+        let circuit = Output {
+            value_commitment_opening: Some(ValueCommitmentOpening {
+                value,
+                randomness: rcv.inner(),
+            }),
+            payment_address: Some(address),
+            commitment_randomness: Some(rcm),
+            esk: Some(esk),
+        };
+
+        let cv = ValueCommitment::derive(value, rcv.clone()).to_bytes();
+        let cm = note.cmu().to_bytes();
+        let epk = (g_d * esk).to_bytes();
+        let pk_d = address.pk_d().inner();
+        let shared_secret =
+            Zeroizing::new((ExtendedPoint::from(pk_d).clear_cofactor() * esk).to_bytes());
+        let key = Zeroizing::new(blake2b_256_keyed(
+            &[&shared_secret[..], &epk].concat(),
+            KDF_KEY,
+        )?);
+        let mut memo = output.memo.clone();
+        memo.resize(memo_size, 0);
+        let plaintext = Zeroizing::new(
+            [
+                &address.diversifier().0[..],
+                &output.amount.to_be_bytes(),
+                &rcm.to_bytes(),
+                &(memo_size as u32).to_be_bytes(),
+                &memo,
+            ]
+            .concat(),
+        );
+        let nonce_enc = random_nonce(rng);
+        let payload_enc = encrypt(&key, &nonce_enc, &plaintext)?;
+
+        let ock = Zeroizing::new(blake2b_256_keyed(
+            &[&ovk[..], &cv, &cm, &epk].concat(),
+            OCK_KEY,
+        )?);
+        let nonce_out = random_nonce(rng);
+        let payload_out = encrypt(
+            &ock,
+            &nonce_out,
+            &Zeroizing::new([&pk_d.to_bytes()[..], &esk.to_bytes()].concat()),
+        )?;
+
+        Ok((
+            SaplingOutputDescription {
+                cm,
+                proof: OutputParameters::encode_proof(
+                    self.prover.output.create_proof(circuit, rng),
+                ),
+                ciphertext: SaplingCiphertext {
+                    cv,
+                    epk,
+                    payload_enc,
+                    nonce_enc,
+                    payload_out,
+                    nonce_out,
+                },
+            },
+            rcv,
+        ))
+    }
+
+    /// Hashes the encoded inputs and outputs with the bound data, the message of the binding signature.
+    fn binding_sighash(
+        &self,
+        inputs: &[SaplingSpendDescription],
+        outputs: &[SaplingOutputDescription],
+        bound_data: &[u8],
+    ) -> Result<[u8; KEY_LENGTH]> {
+        let inputs = inputs.iter().flat_map(|input| input.to_bytes());
+        let outputs = outputs.iter().flat_map(|output| output.to_bytes());
+        self.hash(
+            &inputs
+                .chain(outputs)
+                .chain(bound_data.iter().copied())
+                .collect::<Vec<u8>>(),
+        )
+    }
+
+    /// Hashes the message keyed by the contract and the chain, so that the signatures can't be
+    /// replayed on another contract or chain.
+    fn hash(&self, message: &[u8]) -> Result<[u8; KEY_LENGTH]> {
+        blake2b_256_keyed(message, &self.anti_replay)?
+            .try_into()
+            .map_err(|_| Error::InvalidBytes)
+    }
+}
+
+/// Expands the `ask || nsk || ovk` part of the extended spending key.
+fn expanded_spending_key(key: &SaplingSpendingKey) -> Result<ExpandedSpendingKey> {
+    let bytes = Zeroizing::new(key.to_bytes()?);
+    let expanded = bytes.get(41..137).ok_or(Error::InvalidSecretKeyBytes)?;
+    ExpandedSpendingKey::from_bytes(expanded).map_err(|_| Error::InvalidSecretKeyBytes)
+}
+
+/// Converts the decrypted note to the `sapling-crypto` note it commits to.
+fn sapling_note(note: &SaplingNote) -> Result<Note> {
+    let address: [u8; ADDRESS_LENGTH] = note
+        .address
+        .to_bytes()?
+        .try_into()
+        .map_err(|_| Error::InvalidAddress)?;
+    let address = PaymentAddress::from_bytes(&address).ok_or(Error::InvalidAddress)?;
+    let rcm = scalar(&note.rcm).ok_or(Error::InvalidSaplingNote)?;
+    Ok(Note::from_parts(
+        address,
+        NoteValue::from_raw(note.amount),
+        Rseed::BeforeZip212(rcm),
+    ))
+}
+
+fn check_parameters_hash(name: &str, bytes: &[u8], expected: &str) -> Result<()> {
+    if hex::encode(blake2b(bytes, PARAMETERS_HASH_LENGTH)?) != expected {
+        return Err(Error::InvalidSaplingParameters {
+            description: std::format!("{} doesn't match the published parameters", name),
+        });
+    }
+    Ok(())
+}
+
+fn encrypt(key: &[u8], nonce: &[u8; NONCE_LENGTH], plaintext: &[u8]) -> Result<Vec<u8>> {
+    XSalsa20Poly1305::new(GenericArray::from_slice(key))
+        .encrypt(GenericArray::from_slice(nonce), plaintext)
+        .map_err(|_| Error::Internal {
+            description: "sapling note encryption failed".into(),
+        })
+}
+
+fn random_nonce<R: RngCore + CryptoRng>(rng: &mut R) -> [u8; NONCE_LENGTH] {
+    let mut nonce = [0u8; NONCE_LENGTH];
+    rng.fill_bytes(&mut nonce);
+    nonce
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::OnceLock;
+
+    use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+    use sapling_crypto::{
+        note::ExtractedNoteCommitment, CommitmentTree, Node, SaplingVerificationContext,
+    };
+
+    use super::*;
+
+    fn prover() -> &'static SaplingProver {
+        static PROVER: OnceLock<SaplingProver> = OnceLock::new();
+        PROVER.get_or_init(|| {
+            let (spend, output) = wagyu_zcash_parameters::load_sapling_parameters();
+            SaplingProver::from_bytes(&spend, &output).unwrap()
+        })
+    }
+
+    fn contract() -> ContractAddress {
+        "KT1UcwQtaztLSq8oufdXAtWpRTfFySCj7gFM".try_into().unwrap()
+    }
+
+    fn chain_id() -> ChainId {
+        "NetXdQprcVkpaWU".try_into().unwrap()
+    }
+
+    /// Verifies the proofs and signatures of the transaction the way `SAPLING_VERIFY_UPDATE` does.
+    fn verify(builder: &SaplingTransactionBuilder, transaction: &SaplingTransaction) -> bool {
+        let mut context = SaplingVerificationContext::new();
+        let anchor = bls12_381::Scalar::from_bytes(&transaction.root).unwrap();
+        let spend_key = builder.prover.spend.prepared_verifying_key();
+        let output_key = builder.prover.output.prepared_verifying_key();
+
+        for input in &transaction.inputs {
+            if !context.check_spend(
+                &ValueCommitment::from_bytes_not_small_order(&input.cv).unwrap(),
+                anchor,
+                &input.nullifier,
+                redjubjub::VerificationKey::try_from(input.rk).unwrap(),
+                &builder.hash(&input.unsigned_bytes()).unwrap(),
+                input.signature.into(),
+                bellman::groth16::Proof::read(&input.proof[..]).unwrap(),
+                &spend_key,
+            ) {
+                return false;
+            }
+        }
+        for output in &transaction.outputs {
+            if !context.check_output(
+                &ValueCommitment::from_bytes_not_small_order(&output.ciphertext.cv).unwrap(),
+                ExtractedNoteCommitment::from_bytes(&output.cm).unwrap(),
+                ExtendedPoint::from_bytes(&output.ciphertext.epk).unwrap(),
+                bellman::groth16::Proof::read(&output.proof[..]).unwrap(),
+                &output_key,
+            ) {
+                return false;
+            }
+        }
+        let sighash = builder
+            .binding_sighash(
+                &transaction.inputs,
+                &transaction.outputs,
+                &transaction.bound_data,
+            )
+            .unwrap();
+        context.final_check(
+            transaction.balance,
+            &sighash,
+            transaction.binding_signature.into(),
+        )
+    }
+
+    fn amounts(state: &SaplingState, key: &SaplingSpendingKey) -> Vec<u64> {
+        state
+            .unspent_notes(&key.viewing_key().unwrap())
+            .unwrap()
+            .iter()
+            .map(|input| input.note.amount)
+            .collect()
+    }
+
+    #[test]
+    fn test_root() {
+        let mut state = SaplingState::new(8);
+        assert_eq!(
+            hex::encode(state.root()),
+            "fbc2f4300c01f0b7820d00e3347c8da4ee614674376cbc45359daa54f9b5493e"
+        );
+
+        let mut tree = CommitmentTree::empty();
+        for index in 0..5u64 {
+            let commitment = bls12_381::Scalar::from(index * 1000 + 7).to_bytes();
+            tree.append(Node::from_bytes(commitment).unwrap()).unwrap();
+            state.commitments_and_ciphertexts.push((
+                commitment,
+                SaplingCiphertext {
+                    cv: [0u8; POINT_BYTES_LENGTH],
+                    epk: [0u8; POINT_BYTES_LENGTH],
+                    payload_enc: Vec::new(),
+                    nonce_enc: [0u8; NONCE_LENGTH],
+                    payload_out: Vec::new(),
+                    nonce_out: [0u8; NONCE_LENGTH],
+                },
+            ));
+            assert_eq!(state.root(), tree.root().to_bytes());
+        }
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        let (spend, output) = wagyu_zcash_parameters::load_sapling_parameters();
+        assert!(matches!(
+            SaplingProver::from_bytes(&output, &spend),
+            Err(Error::InvalidSaplingParameters { .. })
+        ));
+        assert!(matches!(
+            SaplingProver::from_bytes(&spend[..spend.len() - 1], &output),
+            Err(Error::InvalidSaplingParameters { .. })
+        ));
+        assert!(matches!(
+            SaplingProver::from_files(
+                "/nonexistent/sapling-spend.params",
+                "/nonexistent/sapling-output.params"
+            ),
+            Err(Error::InvalidSaplingParameters { .. })
+        ));
+    }
+
+    #[test]
+    fn test_shield_transfer_unshield() -> Result<()> {
+        let prover = prover();
+        let mut state = SaplingState::new(8);
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let alice = SaplingSpendingKey::from_seed(&[1u8; 32])?;
+        let bob = SaplingSpendingKey::from_seed(&[2u8; 32])?;
+        let (alice_address, _) = alice.address(0)?;
+        let (bob_address, _) = bob.address(3)?;
+
+        let builder = SaplingTransactionBuilder::new(prover, &state, &contract(), &chain_id());
+        let transaction = builder.shield(&alice_address, 1_000_000, b"memo", &mut rng)?;
+        assert!(transaction.inputs.is_empty());
+        assert_eq!(transaction.outputs.len(), 1);
+        assert_eq!(transaction.balance, -1_000_000);
+        assert_eq!(transaction.root, state.root());
+        assert!(verify(&builder, &transaction));
+        state.apply(&transaction);
+
+        let inputs = state.unspent_notes(&alice.viewing_key()?)?;
+        assert_eq!(inputs.len(), 1);
+        assert_eq!(inputs[0].position, 0);
+        assert_eq!(inputs[0].note.address, alice_address);
+        assert_eq!(inputs[0].note.amount, 1_000_000);
+        assert_eq!(inputs[0].note.memo, b"memo\0\0\0\0");
+        assert!(amounts(&state, &bob).is_empty());
+
+        let builder = SaplingTransactionBuilder::new(prover, &state, &contract(), &chain_id());
+        let transaction = builder.transfer(&alice, &bob_address, 300_000, b"", &mut rng)?;
+        assert_eq!(transaction.inputs.len(), 1);
+        assert_eq!(transaction.inputs[0].nullifier, inputs[0].nullifier);
+        assert_eq!(transaction.outputs.len(), 2);
+        assert_eq!(transaction.balance, 0);
+        assert!(verify(&builder, &transaction));
+
+        let mut tampered = transaction.clone();
+        tampered.bound_data = vec![0];
+        assert!(!verify(&builder, &tampered));
+        let other_contract: ContractAddress = "KT1BEqzn5Wx8uJrZNvuS9DVHmLvG9td3fDLi".try_into()?;
+        let other_builder =
+            SaplingTransactionBuilder::new(prover, &state, &other_contract, &chain_id());
+        assert!(!verify(&other_builder, &transaction));
+
+        state.apply(&transaction);
+        assert_eq!(amounts(&state, &alice), vec![700_000]);
+        assert_eq!(amounts(&state, &bob), vec![300_000]);
+
+        let account: ImplicitAddress = "tz1fJGtrdmckD3VkiDxqUEci5h4gGcvocw6e".try_into()?;
+        let builder = SaplingTransactionBuilder::new(prover, &state, &contract(), &chain_id());
+        let transaction = builder.unshield(&alice, &account, 200_000, &mut rng)?;
+        assert_eq!(transaction.inputs.len(), 1);
+        assert_eq!(transaction.outputs.len(), 1);
+        assert_eq!(transaction.balance, 200_000);
+        assert_eq!(
+            hex::encode(&transaction.bound_data),
+            "050a0000001500d7a60d4e90e8a33ec835159191b14ce4452f12f8"
+        );
+        assert!(verify(&builder, &transaction));
+        state.apply(&transaction);
+        assert_eq!(amounts(&state, &alice), vec![500_000]);
+        assert_eq!(state.nullifiers.len(), 2);
+
+        let builder = SaplingTransactionBuilder::new(prover, &state, &contract(), &chain_id());
+        assert!(matches!(
+            builder.transfer(&alice, &bob_address, 500_001, b"", &mut rng),
+            Err(Error::InsufficientSaplingBalance)
+        ));
+        assert!(matches!(
+            builder.shield(&bob_address, 1, b"too long memo", &mut rng),
+            Err(Error::InvalidSaplingMemo)
+        ));
+        assert!(matches!(
+            builder.build(
+                None,
+                &state.unspent_notes(&bob.viewing_key()?)?,
+                &[],
+                &[],
+                &mut rng
+            ),
+            Err(Error::InvalidSaplingNote)
+        ));
+        assert!(matches!(
+            builder.build(
+                Some(&alice),
+                &state.unspent_notes(&bob.viewing_key()?)?,
+                &[],
+                &[],
+                &mut rng
+            ),
+            Err(Error::InvalidSaplingNote)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_bytes() -> Result<()> {
+        let prover = prover();
+        let state = SaplingState::new(8);
+        let mut rng = ChaCha20Rng::seed_from_u64(1);
+        let (address, _) = SaplingSpendingKey::from_seed(&[1u8; 32])?.address(0)?;
+        let builder = SaplingTransactionBuilder::new(prover, &state, &contract(), &chain_id());
+        let transaction = builder.shield(&address, 42, b"", &mut rng)?;
+        let output = &transaction.outputs[0];
+        let bytes = transaction.to_bytes();
+
+        // 8 bytes of memo, the header of the plaintext and the MAC
+        assert_eq!(output.ciphertext.payload_enc.len(), 8 + 55 + 16);
+        assert_eq!(output.ciphertext.payload_out.len(), 80);
+        let ciphertext = output.ciphertext.to_bytes();
+        assert_eq!(ciphertext.len(), 32 + 32 + 4 + 79 + 24 + 80 + 24);
+        let output_length = 32 + PROOF_LENGTH + ciphertext.len();
+        assert_eq!(bytes.len(), 4 + 4 + output_length + 64 + 8 + 32 + 4);
+
+        assert_eq!(bytes[..4], [0, 0, 0, 0]);
+        assert_eq!(bytes[4..8], (output_length as u32).to_be_bytes());
+        assert_eq!(bytes[8..40], output.cm);
+        assert_eq!(bytes[40..232], output.proof);
+        assert_eq!(bytes[232..232 + ciphertext.len()], ciphertext);
+        let rest = &bytes[8 + output_length..];
+        assert_eq!(rest[..64], transaction.binding_signature);
+        assert_eq!(rest[64..72], (-42i64).to_be_bytes());
+        assert_eq!(rest[72..104], state.root());
+        assert_eq!(rest[104..], [0, 0, 0, 0]);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,53 @@
+//! The Sapling group hash onto the prime order subgroup of the Jubjub curve.
+
+use group::{cofactor::CofactorGroup, Group, GroupEncoding};
+use jubjub::{ExtendedPoint, SubgroupPoint};
+
+use crate::{internal::crypto::blake2s_256_personal, Error, Result};
+
+/// The "uniform random string" hashed in front of the group hash inputs.
+const GROUP_HASH_URS: &[u8] = b"096b36a5804bfacef1691e173c366a47ff5ba84a44f26ddd7e8d9f79d5b42df0";
+
+/// Hashes the message to a point of the prime order subgroup, returns `None` if the hash
+/// does not encode a point or the resulting point is the identity.
+pub(super) fn group_hash(personal: &[u8], message: &[u8]) -> Result<Option<SubgroupPoint>> {
+    let hash: [u8; 32] = blake2s_256_personal(&[GROUP_HASH_URS, message].concat(), personal)?
+        .try_into()
+        .unwrap();
+    let point: Option<ExtendedPoint> = ExtendedPoint::from_bytes(&hash).into();
+    Ok(point
+        .map(|point| point.clear_cofactor())
+        .filter(|point| !bool::from(point.is_identity())))
+}
+
+/// Returns the first successful [group_hash] of the message followed by a counter byte.
+pub(super) fn find_group_hash(personal: &[u8], message: &[u8]) -> Result<SubgroupPoint> {
+    for i in 0..=u8::MAX {
+        if let Some(point) = group_hash(personal, &[message, &[i]].concat())? {
+            return Ok(point);
+        }
+    }
+    Err(Error::Internal {
+        description: "group hash not found".into(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_generators() -> Result<()> {
+        let g = find_group_hash(b"Zcash_G_", &[])?;
+        assert_eq!(
+            hex::encode(g.to_bytes()),
+            "30b5f2aaad325630bcdddbce4d67656d05fd1cc2d037bb5375b6e96d9e01a1d7"
+        );
+        let h = find_group_hash(b"Zcash_H_", &[])?;
+        assert_eq!(
+            hex::encode(h.to_bytes()),
+            "e7e85de0f7f97a46d249a1f5ea51df50cc48490f8401c9de7a2adf1807d1b6d4"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,582 @@
+//! Timelock encryption backing the Michelson `chest` and `chest_key` types.
+//!
+//! A [Chest] encrypts a payload so that it can only be decrypted after `time` sequential squarings
+//! modulo the RSA-2048 challenge modulus, whose factorization is unknown. The resulting [ChestKey]
+//! holds a Wesolowski proof that the squarings were performed, which allows to verify it cheaply,
+//! as the `OPEN_CHEST` instruction does.
+//!
+//! The binary encodings of [Chest] and [ChestKey] match the ones used by the protocol, so they can be
+//! used as the content of Michelson `bytes` literals.
+//!
+//! # Example
+//!
+//! ```rust
+//! use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+//! use tezos_core::timelock::{Chest, Opening};
+//!
+//! // In practice, use a generator seeded from a secure source of entropy, e.g. `rand_core::OsRng`.
+//! let mut rng = ChaCha20Rng::seed_from_u64(0);
+//! let (chest, chest_key) = Chest::create(b"bid: 100", 100, &mut rng).unwrap();
+//! assert_eq!(chest.open(&chest_key, 100), Opening::Correct(b"bid: 100".to_vec()));
+//!
+//! // Without the creator's key, the chest can be opened by performing the squarings.
+//! let computed_key = chest.compute_key(100).unwrap();
+//! assert_eq!(chest.open(&computed_key, 100), Opening::Correct(b"bid: 100".to_vec()));
+//! ```
+
+use alloc::{string::ToString, vec, vec::Vec};
+
+use crypto_secretbox::{
+    aead::{generic_array::GenericArray, Aead, KeyInit},
+    XSalsa20Poly1305,
+};
+use ibig::{modular::ModuloRing, UBig};
+use rand_core::{CryptoRng, RngCore};
+use zeroize::Zeroizing;
+
+use crate::{
+    internal::{
+        consumable_list::{ConsumableBytes, ConsumableList},
+        crypto::blake2b_256_keyed,
+    },
+    types::number::Nat,
+    Error, Result,
+};
+
+/// The RSA-2048 challenge modulus used by the protocol.
+const RSA2048: &str = "25195908475657893494027183240048398571429282126204032027777137836043662020707595556264018525880784406918290641249515082189298559149176184502808489120072844992687392807287776735971418347270261896375014971824691165077613379859095700097330459748808428401797429100642458691817195118746121515172654632282216869987549182422433637259085141865462043576798423387184774447920739934236584823824281198163815010674810451660377306056201619676256133844143603833904414952634432190114657544454178424020924616515723350778707749817125772467962926386356373289912154831438167899885040445364023527381951378636564391212010397122822120720357";
+const RSA2048_BYTES_LENGTH: usize = 256;
+const HASH_TO_PRIME_KEY: &[u8] = b"\x20";
+const KDF_KEY: &[u8] = b"Tezoskdftimelockv1";
+const NONCE_LENGTH: usize = 24;
+const TAG_LENGTH: usize = 16;
+const RANDOMIZER_BYTES_LENGTH: usize = 32;
+const MILLER_RABIN_BASES: [u8; 25] = [
+    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97,
+];
+
+/// A timelock encrypted payload, the content of a Michelson `chest` value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chest {
+    pub locked_value: Nat,
+    pub ciphertext: Ciphertext,
+}
+
+/// The payload of a [Chest], encrypted with a NaCl secretbox.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ciphertext {
+    pub nonce: [u8; NONCE_LENGTH],
+    pub payload: Vec<u8>,
+}
+
+/// The key opening a [Chest], the content of a Michelson `chest_key` value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChestKey {
+    pub vdf_tuple: VdfTuple,
+    pub nonce: Nat,
+}
+
+/// A value, the result of squaring it `time` times and the proof of the squarings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VdfTuple {
+    pub locked_value: Nat,
+    pub unlocked_value: Nat,
+    pub vdf_proof: Nat,
+}
+
+/// Result of opening a [Chest] with a [ChestKey], following the `OPEN_CHEST` semantics.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Opening {
+    /// The key is valid and the payload was decrypted.
+    Correct(Vec<u8>),
+    /// The key is valid but the payload could not be decrypted, the chest was built incorrectly.
+    BogusCipher,
+    /// The key does not open the chest with the given time.
+    BogusOpening,
+}
+
+impl Chest {
+    /// Creates a chest encrypting the payload, which can be opened by anyone after `time` sequential
+    /// squarings, and the key opening it. The creation costs as much computation as the opening.
+    pub fn create<R: RngCore + CryptoRng>(
+        payload: &[u8],
+        time: u64,
+        rng: &mut R,
+    ) -> Result<(Chest, ChestKey)> {
+        if time == 0 {
+            return Err(Error::InvalidTimelockTime);
+        }
+        let modulus = rsa2048();
+        let locked_value = random_locked_value(&modulus, rng);
+        let unlocked_value = unlock(&modulus, &locked_value, time);
+        let vdf_proof = prove_wesolowski(&modulus, time, &locked_value, &unlocked_value);
+        let nonce = random_below(&modulus, RANDOMIZER_BYTES_LENGTH, rng);
+
+        let chest_key = ChestKey {
+            vdf_tuple: VdfTuple {
+                locked_value: locked_value.clone().into(),
+                unlocked_value: unlocked_value.into(),
+                vdf_proof: vdf_proof.into(),
+            },
+            nonce: nonce.clone().into(),
+        };
+        let mut ciphertext_nonce = [0u8; NONCE_LENGTH];
+        rng.fill_bytes(&mut ciphertext_nonce);
+        let encrypted = cipher(&chest_key.symmetric_key(&modulus))
+            .encrypt(&GenericArray::from(ciphertext_nonce), payload)
+            .map_err(|_| Error::Internal {
+                description: "chest payload encryption failed".into(),
+            })?;
+        let chest = Chest {
+            locked_value: ModuloRing::new(&modulus)
+                .from(&locked_value)
+                .pow(&nonce)
+                .residue()
+                .into(),
+            ciphertext: Ciphertext {
+                nonce: ciphertext_nonce,
+                payload: encrypted,
+            },
+        };
+
+        Ok((chest, chest_key))
+    }
+
+    /// Computes the key opening the chest by performing the `time` sequential squarings.
+    pub fn compute_key(&self, time: u64) -> Result<ChestKey> {
+        if time == 0 {
+            return Err(Error::InvalidTimelockTime);
+        }
+        let modulus = rsa2048();
+        let locked_value: UBig = self.locked_value.clone().into();
+        if !is_valid_locked_value(&modulus, &locked_value) {
+            return Err(Error::InvalidTimelockValue);
+        }
+        let unlocked_value = unlock(&modulus, &locked_value, time);
+        let vdf_proof = prove_wesolowski(&modulus, time, &locked_value, &unlocked_value);
+
+        Ok(ChestKey {
+            vdf_tuple: VdfTuple {
+                locked_value: locked_value.into(),
+                unlocked_value: unlocked_value.into(),
+                vdf_proof: vdf_proof.into(),
+            },
+            nonce: UBig::from(1u8).into(),
+        })
+    }
+
+    /// Opens the chest with the given key, as the `OPEN_CHEST` instruction does.
+    pub fn open(&self, chest_key: &ChestKey, time: u64) -> Opening {
+        if !chest_key.verify(self, time) {
+            return Opening::BogusOpening;
+        }
+        let key = chest_key.symmetric_key(&rsa2048());
+        match cipher(&key).decrypt(
+            &GenericArray::from(self.ciphertext.nonce),
+            self.ciphertext.payload.as_slice(),
+        ) {
+            Ok(payload) => Opening::Correct(payload),
+            Err(_) => Opening::BogusCipher,
+        }
+    }
+
+    /// Encodes the chest to its binary representation.
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let payload_length: u32 = self.ciphertext.payload.len().try_into()?;
+
+        Ok([
+            self.locked_value.to_bytes()?,
+            self.ciphertext.nonce.to_vec(),
+            payload_length.to_be_bytes().to_vec(),
+            self.ciphertext.payload.clone(),
+        ]
+        .concat())
+    }
+
+    /// Decodes a chest from its binary representation.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let bytes = &mut ConsumableBytes::new(bytes);
+        let locked_value = Nat::from_consumable_bytes(bytes)?;
+        let nonce: [u8; NONCE_LENGTH] = bytes
+            .consume_until(NONCE_LENGTH)?
+            .try_into()
+            .map_err(|_| Error::InvalidBytes)?;
+        let payload_length = u32::from_be_bytes(
+            bytes
+                .consume_until(4)?
+                .try_into()
+                .map_err(|_| Error::InvalidBytes)?,
+        ) as usize;
+        let payload = bytes.consume_until(payload_length)?.to_vec();
+        if !bytes.is_empty() || payload.len() <= TAG_LENGTH {
+            return Err(Error::InvalidBytes);
+        }
+
+        Ok(Self {
+            locked_value,
+            ciphertext: Ciphertext { nonce, payload },
+        })
+    }
+}
+
+impl ChestKey {
+    /// Verifies that the key opens the chest with the given time, without decrypting the payload.
+    pub fn verify(&self, chest: &Chest, time: u64) -> bool {
+        if time == 0 {
+            return false;
+        }
+        let modulus = rsa2048();
+        let chest_locked_value: UBig = chest.locked_value.clone().into();
+        let locked_value: UBig = self.vdf_tuple.locked_value.clone().into();
+        let unlocked_value: UBig = self.vdf_tuple.unlocked_value.clone().into();
+        let vdf_proof: UBig = self.vdf_tuple.vdf_proof.clone().into();
+        let nonce: UBig = self.nonce.clone().into();
+        if !is_valid_locked_value(&modulus, &chest_locked_value)
+            || !is_valid_locked_value(&modulus, &locked_value)
+            || unlocked_value >= modulus
+            || vdf_proof >= modulus
+        {
+            return false;
+        }
+
+        let ring = ModuloRing::new(&modulus);
+        ring.from(&locked_value).pow(&nonce).residue() == chest_locked_value
+            && verify_wesolowski(&modulus, time, &locked_value, &unlocked_value, &vdf_proof)
+    }
+
+    /// Encodes the key to its binary representation.
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        Ok([
+            self.vdf_tuple.locked_value.to_bytes()?,
+            self.vdf_tuple.unlocked_value.to_bytes()?,
+            self.vdf_tuple.vdf_proof.to_bytes()?,
+            self.nonce.to_bytes()?,
+        ]
+        .concat())
+    }
+
+    /// Decodes a key from its binary representation.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let bytes = &mut ConsumableBytes::new(bytes);
+        let chest_key = Self {
+            vdf_tuple: VdfTuple {
+                locked_value: Nat::from_consumable_bytes(bytes)?,
+                unlocked_value: Nat::from_consumable_bytes(bytes)?,
+                vdf_proof: Nat::from_consumable_bytes(bytes)?,
+            },
+            nonce: Nat::from_consumable_bytes(bytes)?,
+        };
+        if !bytes.is_empty() {
+            return Err(Error::InvalidBytes);
+        }
+
+        Ok(chest_key)
+    }
+
+    /// The secretbox key is derived from the unlocked value raised to the nonce.
+    fn symmetric_key(&self, modulus: &UBig) -> Zeroizing<Vec<u8>> {
+        let unlocked_value: UBig = self.vdf_tuple.unlocked_value.clone().into();
+        let nonce: UBig = self.nonce.clone().into();
+        let value = ModuloRing::new(modulus)
+            .from(&unlocked_value)
+            .pow(&nonce)
+            .residue();
+
+        Zeroizing::new(blake2b_256_keyed(value.to_string().as_bytes(), KDF_KEY).unwrap())
+    }
+}
+
+impl TryFrom<&[u8]> for Chest {
+    type Error = Error;
+
+    fn try_from(value: &[u8]) -> Result<Self> {
+        Self::from_bytes(value)
+    }
+}
+
+impl TryFrom<&Chest> for Vec<u8> {
+    type Error = Error;
+
+    fn try_from(value: &Chest) -> Result<Self> {
+        value.to_bytes()
+    }
+}
+
+impl TryFrom<&[u8]> for ChestKey {
+    type Error = Error;
+
+    fn try_from(value: &[u8]) -> Result<Self> {
+        Self::from_bytes(value)
+    }
+}
+
+impl TryFrom<&ChestKey> for Vec<u8> {
+    type Error = Error;
+
+    fn try_from(value: &ChestKey) -> Result<Self> {
+        value.to_bytes()
+    }
+}
+
+fn rsa2048() -> UBig {
+    UBig::from_str_radix(RSA2048, 10).unwrap()
+}
+
+fn cipher(key: &[u8]) -> XSalsa20Poly1305 {
+    XSalsa20Poly1305::new(GenericArray::from_slice(key))
+}
+
+fn is_valid_locked_value(modulus: &UBig, value: &UBig) -> bool {
+    value > &UBig::from(1u8) && value < modulus
+}
+
+fn random_below<R: RngCore + CryptoRng>(modulus: &UBig, length: usize, rng: &mut R) -> UBig {
+    let mut bytes = Zeroizing::new(vec![0u8; length]);
+    rng.fill_bytes(&mut bytes);
+
+    UBig::from_le_bytes(&bytes) % modulus
+}
+
+fn random_locked_value<R: RngCore + CryptoRng>(modulus: &UBig, rng: &mut R) -> UBig {
+    loop {
+        let value = random_below(modulus, RSA2048_BYTES_LENGTH + RANDOMIZER_BYTES_LENGTH, rng);
+        if is_valid_locked_value(modulus, &value) {
+            return value;
+        }
+    }
+}
+
+/// Squares the value `time` times.
+fn unlock(modulus: &UBig, locked_value: &UBig, time: u64) -> UBig {
+    let ring = ModuloRing::new(modulus);
+    let mut value = ring.from(locked_value);
+    for _ in 0..time {
+        value = &value * &value;
+    }
+
+    value.residue()
+}
+
+fn prove_wesolowski(modulus: &UBig, time: u64, locked_value: &UBig, unlocked_value: &UBig) -> UBig {
+    let l = hash_to_prime(modulus, time, locked_value, unlocked_value);
+    let pi = (UBig::from(1u8) << time as usize) / l;
+
+    ModuloRing::new(modulus)
+        .from(locked_value)
+        .pow(&pi)
+        .residue()
+}
+
+fn verify_wesolowski(
+    modulus: &UBig,
+    time: u64,
+    locked_value: &UBig,
+    unlocked_value: &UBig,
+    vdf_proof: &UBig,
+) -> bool {
+    let l = hash_to_prime(modulus, time, locked_value, unlocked_value);
+    let r = ModuloRing::new(&l)
+        .from(2u8)
+        .pow(&UBig::from(time))
+        .residue();
+    let ring = ModuloRing::new(modulus);
+
+    (ring.from(vdf_proof).pow(&l) * ring.from(locked_value).pow(&r)).residue() == *unlocked_value
+}
+
+/// The Wesolowski challenge, the next prime after the hash of the modulus, the time and the values.
+fn hash_to_prime(modulus: &UBig, time: u64, value: &UBig, key: &UBig) -> UBig {
+    let message = [modulus, &UBig::from(time), value, key]
+        .iter()
+        .flat_map(|value| to_bits(value))
+        .collect::<Vec<_>>();
+    let hash = blake2b_256_keyed(&message, HASH_TO_PRIME_KEY).unwrap();
+
+    next_prime(&UBig::from_le_bytes(&hash))
+}
+
+/// Little-endian bytes padded to a multiple of 64 bits, like the limbs of the `Z.to_bits` representation.
+fn to_bits(value: &UBig) -> Vec<u8> {
+    let mut bytes = if value == &UBig::from(0u8) {
+        vec![]
+    } else {
+        value.to_le_bytes()
+    };
+    bytes.resize(bytes.len().div_ceil(8) * 8, 0);
+    bytes
+}
+
+/// Returns the smallest prime strictly greater than the value.
+fn next_prime(value: &UBig) -> UBig {
+    let two = UBig::from(2u8);
+    if value < &two {
+        return two;
+    }
+    let mut candidate = value + UBig::from(1u8);
+    if &candidate % &two == UBig::from(0u8) {
+        candidate += UBig::from(1u8);
+    }
+    while !is_probable_prime(&candidate) {
+        candidate += &two;
+    }
+
+    candidate
+}
+
+/// Miller-Rabin test of an odd value greater than 2.
+fn is_probable_prime(value: &UBig) -> bool {
+    for base in MILLER_RABIN_BASES {
+        let base = UBig::from(base);
+        if value == &base {
+            return true;
+        }
+        if value % &base == UBig::from(0u8) {
+            return false;
+        }
+    }
+
+    let one = UBig::from(1u8);
+    let value_minus_one = value - &one;
+    let shift = value_minus_one.trailing_zeros().unwrap_or(0);
+    let odd = &value_minus_one >> shift;
+    let ring = ModuloRing::new(value);
+    let (one, minus_one) = (ring.from(&one), ring.from(&value_minus_one));
+
+    MILLER_RABIN_BASES.iter().all(|base| {
+        let mut x = ring.from(*base).pow(&odd);
+        if x == one || x == minus_one {
+            return true;
+        }
+        for _ in 1..shift {
+            x = &x * &x;
+            if x == minus_one {
+                return true;
+            }
+        }
+        false
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+
+    use super::*;
+
+    const TIME: u64 = 500;
+
+    fn create(payload: &[u8]) -> Result<(Chest, ChestKey)> {
+        Chest::create(payload, TIME, &mut ChaCha20Rng::seed_from_u64(0))
+    }
+
+    #[test]
+    fn test_create_and_open() -> Result<()> {
+        let (chest, chest_key) = create(b"timelocked")?;
+
+        assert!(chest_key.verify(&chest, TIME));
+        assert_eq!(
+            chest.open(&chest_key, TIME),
+            Opening::Correct(b"timelocked".to_vec())
+        );
+        assert_eq!(chest.open(&chest_key, TIME - 1), Opening::BogusOpening);
+        assert_eq!(chest.open(&chest_key, 0), Opening::BogusOpening);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_compute_key() -> Result<()> {
+        let (chest, _) = create(b"timelocked")?;
+        let chest_key = chest.compute_key(TIME)?;
+
+        assert_eq!(chest_key.nonce, UBig::from(1u8).into());
+        assert_eq!(
+            chest.open(&chest_key, TIME),
+            Opening::Correct(b"timelocked".to_vec())
+        );
+        assert!(matches!(
+            chest.compute_key(0),
+            Err(Error::InvalidTimelockTime)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_bogus_opening() -> Result<()> {
+        let (chest, chest_key) = create(b"timelocked")?;
+        let (other_chest, other_chest_key) =
+            Chest::create(b"other", TIME, &mut ChaCha20Rng::seed_from_u64(1))?;
+
+        assert_eq!(chest.open(&other_chest_key, TIME), Opening::BogusOpening);
+        assert_eq!(other_chest.open(&chest_key, TIME), Opening::BogusOpening);
+
+        let mut forged_key = chest_key.clone();
+        forged_key.vdf_tuple.unlocked_value = UBig::from(4u8).into();
+        assert_eq!(chest.open(&forged_key, TIME), Opening::BogusOpening);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_bogus_cipher() -> Result<()> {
+        let (mut chest, chest_key) = create(b"timelocked")?;
+        chest.ciphertext.payload[0] ^= 1;
+
+        assert!(chest_key.verify(&chest, TIME));
+        assert_eq!(chest.open(&chest_key, TIME), Opening::BogusCipher);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_bytes() -> Result<()> {
+        let (chest, chest_key) = create(b"timelocked")?;
+
+        let bytes = chest.to_bytes()?;
+        let payload_length = chest.ciphertext.payload.len();
+        assert_eq!(payload_length, 10 + TAG_LENGTH);
+        assert_eq!(
+            &bytes[bytes.len() - payload_length - 4..bytes.len() - payload_length],
+            &(payload_length as u32).to_be_bytes()
+        );
+        assert_eq!(Chest::from_bytes(&bytes)?, chest);
+        assert!(Chest::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        assert!(Chest::from_bytes(&[bytes.as_slice(), &[0]].concat()).is_err());
+
+        let bytes = chest_key.to_bytes()?;
+        assert_eq!(ChestKey::from_bytes(&bytes)?, chest_key);
+        assert!(ChestKey::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_bits() {
+        assert_eq!(to_bits(&UBig::from(0u8)), Vec::<u8>::new());
+        assert_eq!(
+            to_bits(&UBig::from(0x0102u16)),
+            vec![2, 1, 0, 0, 0, 0, 0, 0]
+        );
+        assert_eq!(to_bits(&rsa2048()).len(), RSA2048_BYTES_LENGTH);
+    }
+
+    #[test]
+    fn test_next_prime() {
+        let values = [
+            (0u32, 2u32),
+            (2, 3),
+            (3, 5),
+            (24, 29),
+            (7919, 7927),
+            (104_723, 104_729),
+        ];
+        for (value, expected) in values {
+            assert_eq!(next_prime(&UBig::from(value)), UBig::from(expected));
+        }
+        assert_eq!(
+            next_prime(&(UBig::from(1u8) << 255)),
+            (UBig::from(1u8) << 255) + UBig::from(95u8)
+        );
+    }
+}
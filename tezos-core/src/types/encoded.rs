@@ -8,11 +8,13 @@ mod signature;
 
 pub use self::{
     address::{Address, ContractAddress, ImplicitAddress},
-    key::{Key, PublicKey, SecretKey},
+    key::{EncryptedSecretKey, Key, PublicKey, SecretKey},
     meta_encoded::{MetaEncoded, TraitMetaEncoded},
     signature::Signature,
 };
 
+use alloc::{string::String, vec::Vec};
+
 use crate::{
     internal::{
         coder::{ConsumingDecoder, Decoder, Encoder},
@@ -110,6 +112,79 @@ make_encoded_structs!(
             ],
         }
     },
+    {
+        use {
+            internal::coder::EncodedBytesCoder,
+        }
+        struct bls12_381_encrypted_secret_key::Bls12381EncryptedSecretKey;
+        coder: EncodedBytesCoder;
+        meta {
+            base58_prefix: "BLesk",
+            base58_length: 88,
+            bytes_prefix: [2, 5, 30, 53, 25,],
+            bytes_length: 56,
+        }
+    },
+    {
+        use {
+            internal::coder::EncodedBytesCoder,
+        }
+        struct bls12_381_public_key::Bls12381PublicKey;
+        coder: EncodedBytesCoder;
+        meta {
+            base58_prefix: "BLpk",
+            base58_length: 76,
+            bytes_prefix: [6, 149, 135, 204,],
+            bytes_length: 48,
+        }
+    },
+    {
+        use {
+            internal::coder::EncodedBytesCoder,
+        }
+        struct bls12_381_public_key_hash::Bls12381PublicKeyHash;
+        coder: EncodedBytesCoder;
+        meta {
+            base58_prefix: "tz4",
+            base58_length: 36,
+            bytes_prefix: [6, 161, 166,],
+            bytes_length: 20,
+        }
+        test {
+            string_value: "tz4B6LKkUnKS5MCQDnx2DJtUSGv2sDP3wGHv",
+            bytes_value: [
+                22u8, 230, 73, 148, 194, 221, 189, 41, 54, 149, 182, 62, 76, 173, 224, 41, 211, 200,
+                181, 227,
+            ],
+        }
+    },
+    {
+        use {
+            internal::coder::EncodedBytesCoder,
+        }
+        #[secret]
+        struct bls12_381_secret_key::Bls12381SecretKey;
+        coder: EncodedBytesCoder;
+        meta {
+            base58_prefix: "BLsk",
+            base58_length: 54,
+            bytes_prefix: [3, 150, 192, 40,],
+            bytes_length: 32,
+        }
+    },
+    {
+        use {
+            internal::coder::EncodedBytesCoder,
+        }
+        struct bls12_381_signature::Bls12381Signature;
+        coder: EncodedBytesCoder;
+        meta {
+            base58_prefix: "BLsig",
+            base58_length: 142,
+            bytes_prefix: [40, 171, 64, 207,],
+            bytes_length: 96,
+        }
+    },
     {
         use {
             internal::coder::EncodedBytesCoder,
@@ -243,6 +318,7 @@ make_encoded_structs!(
         use {
             internal::coder::EncodedBytesCoder,
         }
+        #[secret]
         struct ed25519_secret_key::Ed25519SecretKey;
         coder: EncodedBytesCoder;
         meta {
@@ -256,6 +332,7 @@ make_encoded_structs!(
         use {
             internal::coder::EncodedBytesCoder,
         }
+        #[secret]
         struct ed25519_seed::Ed25519Seed;
         coder: EncodedBytesCoder;
         meta {
@@ -475,6 +552,7 @@ make_encoded_structs!(
         use {
             internal::coder::EncodedBytesCoder,
         }
+        #[secret]
         struct p256_secret_key::P256SecretKey;
         coder: EncodedBytesCoder;
         meta {
@@ -538,6 +616,7 @@ make_encoded_structs!(
         use {
             internal::coder::EncodedBytesCoder,
         }
+        #[secret]
         struct sapling_spending_key::SaplingSpendingKey;
         coder: EncodedBytesCoder;
         meta {
@@ -636,6 +715,7 @@ make_encoded_structs!(
         use {
             internal::coder::EncodedBytesCoder,
         }
+        #[secret]
         struct secp256_k1_secret_key::Secp256K1SecretKey;
         coder: EncodedBytesCoder;
         meta {
@@ -800,3 +880,100 @@ make_encoded_structs!(
         }
     },
 );
+
+/// Detects the type of the base58 encoded value and returns its metadata.
+///
+/// Unlike [MetaEncoded::recognize_base58], the value is base58check decoded and recognized by its
+/// versioned bytes prefix, so its checksum is verified and a type is never mistaken for another
+/// one with a similar base58 prefix.
+pub fn detect(value: &str) -> Result<&'static MetaEncoded> {
+    Ok(decode_any(value)?.meta())
+}
+
+/// Decodes a base58 encoded value of any type.
+///
+/// ```rust
+/// use tezos_core::types::encoded::{decode_any, AnyEncoded, Encoded};
+///
+/// let value = decode_any("NetXdQprcVkpaWU").unwrap();
+/// assert!(matches!(value, AnyEncoded::ChainId(_)));
+/// assert_eq!(value.meta().base58_prefix, "Net");
+/// ```
+pub fn decode_any(value: &str) -> Result<AnyEncoded> {
+    AnyEncoded::new(value.into())
+}
+
+/// Decodes a value of any type from its bytes, which must start with the versioned bytes prefix
+/// of the type (e.g. `[6, 161, 159]` for `tz1` addresses).
+pub fn decode_any_bytes(value: &[u8]) -> Result<AnyEncoded> {
+    AnyEncoded::from_bytes(value)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_decode_any() -> Result<()> {
+        let values = [
+            "exprv6UsC1sN3Fk2XfgcJCL8NCerP5rCGy1PRESZAqr7L2JdzX55EN",
+            "sr1Ghq66tYK9y3r8CC1Tf8i8m5nxh8nTvZEf",
+            "BLsig4XnuGo4NgSHnCoDJNtJs8zz8qz3BYcHDZ9pyKY1gcQCAepcsdMcsmytX7LgqoxCT92adgcbJM8z4yh61nHyXQQey81Wt1RCy7aL4GAwyVYAvEWmxv5ttEH2QygsccDzbgT2F6zynU",
+            "NetXdQprcVkpaWU",
+            "tz1Mj7RzPmMAqDUNFBn5t5VbXmWW4cSUAdtT",
+            "KT1QTcAXeefhJ3iXLurRt81WRKdv7YqyYFmo",
+            "BLsqrZ5VimZ5ZJf4s256PH9JP4GAsKnaLsb8BxTkZJN2ijq77KA",
+            "edskRv7VyXGVZb8EsrR7D9XKUbbAQNQGtALP6QeB16ZCD7SmmJpzyeneJVg3Mq56YLbxRA1kSdAXiswwPiaVfR3NHGMCXCziuZ",
+        ];
+        for value in values {
+            let decoded = decode_any(value)?;
+            assert_eq!(decoded.value(), value);
+            assert!(decoded.meta().is_valid_base58(value));
+            assert_eq!(detect(value)?, decoded.meta());
+        }
+
+        assert!(matches!(
+            decode_any("NetXdQprcVkpaWU")?,
+            AnyEncoded::ChainId(value) if value.value() == "NetXdQprcVkpaWU"
+        ));
+        assert!(matches!(
+            decode_any("edskRv7VyXGVZb8EsrR7D9XKUbbAQNQGtALP6QeB16ZCD7SmmJpzyeneJVg3Mq56YLbxRA1kSdAXiswwPiaVfR3NHGMCXCziuZ")?,
+            AnyEncoded::Ed25519SecretKey(_)
+        ));
+        assert!(matches!(
+            decode_any("tz4B6LKkUnKS5MCQDnx2DJtUSGv2sDP3wGHv")?,
+            AnyEncoded::Bls12381PublicKeyHash(_)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_any_invalid() {
+        let values = [
+            "",
+            "NetXdQprcVkpaWV",
+            "tz1Mj7RzPmMAqDUNFBn5t5VbXmWW4cSUAdt",
+            "not base58 0OIl",
+        ];
+        for value in values {
+            assert!(decode_any(value).is_err(), "{}", value);
+            assert!(detect(value).is_err(), "{}", value);
+        }
+    }
+
+    #[test]
+    fn test_decode_any_bytes() -> Result<()> {
+        let value = decode_any("tz1Mj7RzPmMAqDUNFBn5t5VbXmWW4cSUAdtT")?;
+        let bytes = Ed25519PublicKeyHash::try_from("tz1Mj7RzPmMAqDUNFBn5t5VbXmWW4cSUAdtT")?
+            .to_bytes()?;
+        let prefixed = [value.meta().versioned_bytes_prefix(), &bytes].concat();
+
+        assert_eq!(decode_any_bytes(&prefixed)?, value);
+        assert_eq!(AnyEncoded::try_from(prefixed.as_slice())?, value);
+        assert_eq!(value.to_bytes()?, bytes);
+        assert!(decode_any_bytes(&bytes).is_err());
+
+        Ok(())
+    }
+}
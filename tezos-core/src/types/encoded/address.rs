@@ -1,13 +1,23 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use super::{
-    contract_hash::ContractHash, ed25519_public_key_hash::Ed25519PublicKeyHash,
+    bls12_381_public_key_hash::Bls12381PublicKeyHash, contract_hash::ContractHash,
+    ed25519_blinded_public_key_hash::Ed25519BlindedPublicKeyHash,
+    ed25519_public_key_hash::Ed25519PublicKeyHash, operation_hash::OperationHash,
     p256_public_key_hash::P256PublicKeyHash, secp256_k1_public_key_hash::Secp256K1PublicKeyHash,
     Encoded, MetaEncoded, TraitMetaEncoded,
 };
 use crate::{
-    internal::coder::{AddressBytesCoder, ContractAddressBytesCoder, ImplicitAddressBytesCoder},
+    internal::{
+        coder::{AddressBytesCoder, ContractAddressBytesCoder, ImplicitAddressBytesCoder},
+        crypto::{blake2b, blake2b_160_keyed},
+    },
     Error, Result,
 };
 
@@ -142,6 +152,7 @@ impl TryFrom<Address> for ContractHash {
 /// - [Ed25519PublicKeyHash]
 /// - [Secp256K1PublicKeyHash]
 /// - [P256PublicKeyHash]
+/// - [Bls12381PublicKeyHash]
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(
     feature = "serde",
@@ -152,6 +163,7 @@ pub enum ImplicitAddress {
     TZ1(Ed25519PublicKeyHash),
     TZ2(Secp256K1PublicKeyHash),
     TZ3(P256PublicKeyHash),
+    TZ4(Bls12381PublicKeyHash),
 }
 
 impl ImplicitAddress {
@@ -159,12 +171,14 @@ impl ImplicitAddress {
         Ed25519PublicKeyHash::is_valid_base58(value)
             || Secp256K1PublicKeyHash::is_valid_base58(value)
             || P256PublicKeyHash::is_valid_base58(value)
+            || Bls12381PublicKeyHash::is_valid_base58(value)
     }
 
     pub fn is_valid_bytes(value: &[u8]) -> bool {
         Ed25519PublicKeyHash::is_valid_prefixed_bytes(value)
             || Secp256K1PublicKeyHash::is_valid_prefixed_bytes(value)
             || P256PublicKeyHash::is_valid_prefixed_bytes(value)
+            || Bls12381PublicKeyHash::is_valid_prefixed_bytes(value)
     }
 }
 
@@ -176,6 +190,7 @@ impl Encoded for ImplicitAddress {
             Self::TZ1(address) => address.value(),
             Self::TZ2(address) => address.value(),
             Self::TZ3(address) => address.value(),
+            Self::TZ4(address) => address.value(),
         }
     }
 
@@ -184,6 +199,7 @@ impl Encoded for ImplicitAddress {
             Self::TZ1(address) => address.meta(),
             Self::TZ2(address) => address.meta(),
             Self::TZ3(address) => address.meta(),
+            Self::TZ4(address) => address.meta(),
         }
     }
 
@@ -197,6 +213,9 @@ impl Encoded for ImplicitAddress {
         if P256PublicKeyHash::is_valid_base58(&value) {
             return Ok(Self::TZ3(P256PublicKeyHash::new(value)?));
         }
+        if Bls12381PublicKeyHash::is_valid_base58(&value) {
+            return Ok(Self::TZ4(Bls12381PublicKeyHash::new(value)?));
+        }
         Err(Error::InvalidBase58EncodedData { description: value })
     }
 }
@@ -207,6 +226,7 @@ impl From<ImplicitAddress> for String {
             ImplicitAddress::TZ1(value) => value.into(),
             ImplicitAddress::TZ2(value) => value.into(),
             ImplicitAddress::TZ3(value) => value.into(),
+            ImplicitAddress::TZ4(value) => value.into(),
         }
     }
 }
@@ -272,6 +292,30 @@ impl From<P256PublicKeyHash> for ImplicitAddress {
     }
 }
 
+impl From<Bls12381PublicKeyHash> for ImplicitAddress {
+    fn from(value: Bls12381PublicKeyHash) -> Self {
+        Self::TZ4(value)
+    }
+}
+
+impl Ed25519PublicKeyHash {
+    const ACTIVATION_SECRET_SIZE: usize = 20;
+
+    /// Computes the **btz1** blinded public key hash committed to for a fundraiser account, given
+    /// the 20 bytes activation secret of the account.
+    ///
+    /// An `activate_account` operation succeeds only if the blinded public key hash matches one of the
+    /// commitments stored in the context.
+    pub fn blinded(&self, activation_secret: &[u8]) -> Result<Ed25519BlindedPublicKeyHash> {
+        if activation_secret.len() != Self::ACTIVATION_SECRET_SIZE {
+            return Err(Error::InvalidActivationSecret);
+        }
+        let hash = blake2b_160_keyed(&self.to_bytes()?, activation_secret)?;
+
+        (&hash).try_into()
+    }
+}
+
 /// A base58 encoded contract address with optianally an entrypoint.
 ///
 /// See also: [ContractHash].
@@ -305,6 +349,18 @@ impl ContractAddress {
         Self(value)
     }
 
+    /// Computes the address of a contract originated by the operation with the given hash.
+    ///
+    /// The `index` is the position of the origination among all the originations performed by the operation,
+    /// starting at 0 and including the internal originations, in their order of application.
+    pub fn from_origination(operation_hash: &OperationHash, index: u32) -> Result<Self> {
+        let nonce = [operation_hash.to_bytes()?, index.to_be_bytes().to_vec()].concat();
+        let hash = blake2b(&nonce, ContractHash::meta_value().bytes_length)?;
+        let contract_hash: ContractHash = (&hash).try_into()?;
+
+        Ok(Self::from_components(&contract_hash, None))
+    }
+
     /// Returns true if the provided value is a valid contract address, false otherwise.
     pub fn is_valid_base58(value: &str) -> bool {
         if let Ok((value, _)) = Self::split_to_components(value) {
@@ -416,6 +472,41 @@ impl From<ContractAddress> for ContractHash {
 mod test {
     use super::*;
 
+    #[test]
+    fn test_blinded_public_key_hash() -> Result<()> {
+        let public_key_hash: Ed25519PublicKeyHash =
+            "tz1PokEhtiBGCmekQrcN87pCDmqy99TjaLuN".try_into()?;
+        let secret = hex::decode("7b27ba02550e6834b50173c8c506de42d901c606").unwrap();
+
+        assert_eq!(
+            public_key_hash.blinded(&secret)?.value(),
+            "btz1VdTKLHhJJXu7Hyx5DrPZ5zT4rLJeKSniS"
+        );
+        assert!(matches!(
+            public_key_hash.blinded(&secret[1..]),
+            Err(Error::InvalidActivationSecret)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_contract_address_from_origination() -> Result<()> {
+        let operation_hash: OperationHash =
+            "onpLA98fWzC1xEhfJ19PvvAn4NireAKGvzXZbdCgQyRvbxRjFuD".try_into()?;
+        let values = [
+            (0, "KT1V7NAjryaEac9zVHYvtKj66TCgWmS4qzus"),
+            (1, "KT1JVqd3AYWfFDoB32WQrcnUqodoA6ugq7CR"),
+            (7, "KT1QXx5RcVKp2CwNhPKwfeVpF69uZHZHTjDt"),
+        ];
+        for (index, expected) in values {
+            let address = ContractAddress::from_origination(&operation_hash, index)?;
+            assert_eq!(address.value(), expected);
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn test_tz1_address() -> Result<()> {
         let address: Address = "tz1Mj7RzPmMAqDUNFBn5t5VbXmWW4cSUAdtT".try_into()?;
@@ -485,4 +576,17 @@ mod test {
         }
         Err(Error::InvalidConversion)
     }
+
+    #[test]
+    fn test_tz4_implicit_address() -> Result<()> {
+        let address: Address = "tz4NwCZN4oPZRo7xHPHRW4an6hzxdhVCHoer".try_into()?;
+        if let Address::Implicit(ImplicitAddress::TZ4(value)) = &address {
+            assert_eq!(value.value(), "tz4NwCZN4oPZRo7xHPHRW4an6hzxdhVCHoer");
+            let bytes = address.to_bytes()?;
+            assert_eq!(bytes[..2], [0, 3]);
+            assert_eq!(Address::from_bytes(&bytes)?, address);
+            return Ok(());
+        }
+        Err(Error::InvalidConversion)
+    }
 }
@@ -1,15 +1,28 @@
+use alloc::{format, string::{String, ToString}, vec::Vec};
+use core::fmt;
+
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+use zeroize::{Zeroize, Zeroizing};
 
 use crate::{
-    internal::coder::{EncodedBytesCoder, PublicKeyBytesCoder},
+    internal::{
+        coder::{EncodedBytesCoder, PublicKeyBytesCoder},
+        crypto::blake2b,
+    },
     types::encoded::{
-        Ed25519PublicKey, Ed25519SecretKey, Encoded, MetaEncoded, P256PublicKey, P256SecretKey,
-        Secp256K1PublicKey, Secp256K1SecretKey,
+        Address, Bls12381EncryptedSecretKey, Bls12381PublicKey, Bls12381PublicKeyHash,
+        Bls12381SecretKey, Ed25519EncryptedSeed, Ed25519PublicKey, Ed25519PublicKeyHash,
+        Ed25519SecretKey, Ed25519Seed, Encoded, ImplicitAddress, MetaEncoded,
+        P256EncryptedSecretKey, P256PublicKey, P256PublicKeyHash, P256SecretKey,
+        Secp256K1EncryptedSecretKey, Secp256K1PublicKey, Secp256K1PublicKeyHash,
+        Secp256K1SecretKey, TraitMetaEncoded,
     },
-    Error, Result,
+    Error, Result, Tezos,
 };
 
+const PUBLIC_KEY_HASH_SIZE: usize = 20;
+
 /// Group of base58 encoded cryptographic keys, either secret or public.
 ///
 /// See:
@@ -50,7 +63,9 @@ impl Encoded for Key {
         if PublicKey::is_valid_base58(&value) {
             return Ok(Self::Public(PublicKey::new(value)?));
         }
-        Err(Error::InvalidBase58EncodedData { description: value })
+        Err(Error::InvalidBase58EncodedData {
+            description: SecretKey::describe_invalid(value),
+        })
     }
 
     fn to_bytes(&self) -> Result<Vec<u8>> {
@@ -118,7 +133,10 @@ impl TryFrom<&Key> for Vec<u8> {
 /// - [Ed25519SecretKey]
 /// - [Secp256K1SecretKey]
 /// - [P256SecretKey]
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// - [Bls12381SecretKey]
+///
+/// The wrapped keys are zeroized on drop and redacted from the `Debug` output.
+#[derive(Clone, PartialEq, Eq)]
 #[cfg_attr(
     feature = "serde",
     derive(Serialize, Deserialize),
@@ -128,6 +146,7 @@ pub enum SecretKey {
     Ed25519(Ed25519SecretKey),
     Secp256K1(Secp256K1SecretKey),
     P256(P256SecretKey),
+    Bls12381(Bls12381SecretKey),
 }
 
 impl SecretKey {
@@ -135,12 +154,146 @@ impl SecretKey {
         Ed25519SecretKey::is_valid_base58(value)
             || Secp256K1SecretKey::is_valid_base58(value)
             || P256SecretKey::is_valid_base58(value)
+            || Bls12381SecretKey::is_valid_base58(value)
     }
 
     pub fn is_valid_bytes(value: &[u8]) -> bool {
         Ed25519SecretKey::is_valid_bytes(value)
             || Secp256K1SecretKey::is_valid_bytes(value)
             || P256SecretKey::is_valid_bytes(value)
+            || Bls12381SecretKey::is_valid_bytes(value)
+    }
+
+    /// Zeroizes the invalid value so that a mistyped secret does not end up in the error description.
+    fn describe_invalid(mut value: String) -> String {
+        value.zeroize();
+        "<redacted>".into()
+    }
+
+    /// Derives the [PublicKey] matching the secret key using the crypto providers
+    /// configured in the given [Tezos] instance.
+    pub fn public_key_with(&self, tezos: &Tezos) -> Result<PublicKey> {
+        match self {
+            Self::Ed25519(key) => key.public_key_with(tezos).map(|key| key.into()),
+            Self::Secp256K1(key) => key.public_key_with(tezos).map(|key| key.into()),
+            Self::P256(key) => key.public_key_with(tezos).map(|key| key.into()),
+            Self::Bls12381(key) => key.public_key_with(tezos).map(|key| key.into()),
+        }
+    }
+
+    /// Derives the [PublicKey] matching the secret key.
+    pub fn public_key(&self) -> Result<PublicKey> {
+        let tezos: Tezos = Default::default();
+
+        self.public_key_with(&tezos)
+    }
+}
+
+impl fmt::Debug for SecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ed25519(value) => f.debug_tuple("Ed25519").field(value).finish(),
+            Self::Secp256K1(value) => f.debug_tuple("Secp256K1").field(value).finish(),
+            Self::P256(value) => f.debug_tuple("P256").field(value).finish(),
+            Self::Bls12381(value) => f.debug_tuple("Bls12381").field(value).finish(),
+        }
+    }
+}
+
+impl Ed25519SecretKey {
+    /// Derives the [Ed25519PublicKey] matching the secret key using the crypto providers
+    /// configured in the given [Tezos] instance.
+    pub fn public_key_with(&self, tezos: &Tezos) -> Result<Ed25519PublicKey> {
+        let secret = Zeroizing::new(self.to_bytes()?);
+        let public_key = tezos.get_crypto().public_key_ed25519(&secret)?;
+
+        (&public_key).try_into()
+    }
+
+    /// Derives the [Ed25519PublicKey] matching the secret key.
+    pub fn public_key(&self) -> Result<Ed25519PublicKey> {
+        let tezos: Tezos = Default::default();
+
+        self.public_key_with(&tezos)
+    }
+
+    /// Returns the [Ed25519Seed] the secret key has been expanded from.
+    pub fn seed(&self) -> Result<Ed25519Seed> {
+        let bytes = Zeroizing::new(self.to_bytes()?);
+
+        Ed25519Seed::from_bytes(&bytes[..Ed25519Seed::meta_value().bytes_length])
+    }
+}
+
+impl Ed25519Seed {
+    /// Expands the seed to an [Ed25519SecretKey] using the crypto providers
+    /// configured in the given [Tezos] instance.
+    pub fn to_secret_key_with(&self, tezos: &Tezos) -> Result<Ed25519SecretKey> {
+        let seed = Zeroizing::new(self.to_bytes()?);
+        let public_key = tezos.get_crypto().public_key_ed25519(&seed)?;
+
+        Ed25519SecretKey::from_bytes(&Zeroizing::new([seed.as_slice(), &public_key].concat()))
+    }
+
+    /// Expands the seed to an [Ed25519SecretKey].
+    pub fn to_secret_key(&self) -> Result<Ed25519SecretKey> {
+        let tezos: Tezos = Default::default();
+
+        self.to_secret_key_with(&tezos)
+    }
+}
+
+impl Secp256K1SecretKey {
+    /// Derives the [Secp256K1PublicKey] matching the secret key using the crypto providers
+    /// configured in the given [Tezos] instance.
+    pub fn public_key_with(&self, tezos: &Tezos) -> Result<Secp256K1PublicKey> {
+        let secret = Zeroizing::new(self.to_bytes()?);
+        let public_key = tezos.get_crypto().public_key_secp256_k1(&secret)?;
+
+        (&public_key).try_into()
+    }
+
+    /// Derives the [Secp256K1PublicKey] matching the secret key.
+    pub fn public_key(&self) -> Result<Secp256K1PublicKey> {
+        let tezos: Tezos = Default::default();
+
+        self.public_key_with(&tezos)
+    }
+}
+
+impl P256SecretKey {
+    /// Derives the [P256PublicKey] matching the secret key using the crypto providers
+    /// configured in the given [Tezos] instance.
+    pub fn public_key_with(&self, tezos: &Tezos) -> Result<P256PublicKey> {
+        let secret = Zeroizing::new(self.to_bytes()?);
+        let public_key = tezos.get_crypto().public_key_p256(&secret)?;
+
+        (&public_key).try_into()
+    }
+
+    /// Derives the [P256PublicKey] matching the secret key.
+    pub fn public_key(&self) -> Result<P256PublicKey> {
+        let tezos: Tezos = Default::default();
+
+        self.public_key_with(&tezos)
+    }
+}
+
+impl Bls12381SecretKey {
+    /// Derives the [Bls12381PublicKey] matching the secret key using the crypto providers
+    /// configured in the given [Tezos] instance.
+    pub fn public_key_with(&self, tezos: &Tezos) -> Result<Bls12381PublicKey> {
+        let secret = Zeroizing::new(self.to_bytes()?);
+        let public_key = tezos.get_crypto().public_key_bls12_381(&secret)?;
+
+        (&public_key).try_into()
+    }
+
+    /// Derives the [Bls12381PublicKey] matching the secret key.
+    pub fn public_key(&self) -> Result<Bls12381PublicKey> {
+        let tezos: Tezos = Default::default();
+
+        self.public_key_with(&tezos)
     }
 }
 
@@ -152,6 +305,7 @@ impl Encoded for SecretKey {
             Self::Ed25519(value) => value.value(),
             Self::Secp256K1(value) => value.value(),
             Self::P256(value) => value.value(),
+            Self::Bls12381(value) => value.value(),
         }
     }
 
@@ -160,6 +314,7 @@ impl Encoded for SecretKey {
             Self::Ed25519(value) => value.meta(),
             Self::Secp256K1(value) => value.meta(),
             Self::P256(value) => value.meta(),
+            Self::Bls12381(value) => value.meta(),
         }
     }
 
@@ -173,7 +328,12 @@ impl Encoded for SecretKey {
         if P256SecretKey::is_valid_base58(&value) {
             return Ok(Self::P256(P256SecretKey::new(value)?));
         }
-        Err(Error::InvalidBase58EncodedData { description: value })
+        if Bls12381SecretKey::is_valid_base58(&value) {
+            return Ok(Self::Bls12381(Bls12381SecretKey::new(value)?));
+        }
+        Err(Error::InvalidBase58EncodedData {
+            description: Self::describe_invalid(value),
+        })
     }
 
     fn from_bytes(bytes: &[u8]) -> Result<Self> {
@@ -186,6 +346,9 @@ impl Encoded for SecretKey {
         if P256SecretKey::is_valid_bytes(bytes) {
             return Ok(Self::P256(P256SecretKey::from_bytes(bytes)?));
         }
+        if Bls12381SecretKey::is_valid_bytes(bytes) {
+            return Ok(Self::Bls12381(Bls12381SecretKey::from_bytes(bytes)?));
+        }
         Err(Error::InvalidBytes)
     }
 }
@@ -196,6 +359,7 @@ impl From<SecretKey> for String {
             SecretKey::Ed25519(value) => value.into(),
             SecretKey::Secp256K1(value) => value.into(),
             SecretKey::P256(value) => value.into(),
+            SecretKey::Bls12381(value) => value.into(),
         }
     }
 }
@@ -250,12 +414,198 @@ impl From<P256SecretKey> for SecretKey {
     }
 }
 
+impl From<Bls12381SecretKey> for SecretKey {
+    fn from(value: Bls12381SecretKey) -> Self {
+        Self::Bls12381(value)
+    }
+}
+
+/// Group of base58 encoded password-protected secret keys.
+///
+/// See:
+/// - [Ed25519EncryptedSeed]
+/// - [Secp256K1EncryptedSecretKey]
+/// - [P256EncryptedSecretKey]
+/// - [Bls12381EncryptedSecretKey]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(try_from = "String", untagged)
+)]
+pub enum EncryptedSecretKey {
+    Ed25519(Ed25519EncryptedSeed),
+    Secp256K1(Secp256K1EncryptedSecretKey),
+    P256(P256EncryptedSecretKey),
+    Bls12381(Bls12381EncryptedSecretKey),
+}
+
+impl EncryptedSecretKey {
+    const URI_SCHEME: &'static str = "encrypted:";
+
+    pub fn is_valid_base58(value: &str) -> bool {
+        Ed25519EncryptedSeed::is_valid_base58(value)
+            || Secp256K1EncryptedSecretKey::is_valid_base58(value)
+            || P256EncryptedSecretKey::is_valid_base58(value)
+            || Bls12381EncryptedSecretKey::is_valid_base58(value)
+    }
+
+    pub fn is_valid_bytes(value: &[u8]) -> bool {
+        Ed25519EncryptedSeed::is_valid_bytes(value)
+            || Secp256K1EncryptedSecretKey::is_valid_bytes(value)
+            || P256EncryptedSecretKey::is_valid_bytes(value)
+            || Bls12381EncryptedSecretKey::is_valid_bytes(value)
+    }
+
+    /// Creates an [EncryptedSecretKey] from an `encrypted:` entry of the octez-client `secret_keys` file.
+    pub fn from_uri(uri: &str) -> Result<Self> {
+        let value = uri
+            .strip_prefix(Self::URI_SCHEME)
+            .ok_or_else(|| Error::InvalidBase58EncodedData {
+                description: uri.into(),
+            })?;
+
+        Self::new(value.into())
+    }
+
+    /// Returns the `encrypted:` entry of the key as found in the octez-client `secret_keys` file.
+    pub fn to_uri(&self) -> String {
+        format!("{}{}", Self::URI_SCHEME, self.value())
+    }
+}
+
+impl Encoded for EncryptedSecretKey {
+    type Coder = EncodedBytesCoder;
+
+    fn value(&self) -> &str {
+        match self {
+            Self::Ed25519(value) => value.value(),
+            Self::Secp256K1(value) => value.value(),
+            Self::P256(value) => value.value(),
+            Self::Bls12381(value) => value.value(),
+        }
+    }
+
+    fn meta(&self) -> &'static MetaEncoded {
+        match self {
+            Self::Ed25519(value) => value.meta(),
+            Self::Secp256K1(value) => value.meta(),
+            Self::P256(value) => value.meta(),
+            Self::Bls12381(value) => value.meta(),
+        }
+    }
+
+    fn new(value: String) -> Result<Self> {
+        if Ed25519EncryptedSeed::is_valid_base58(&value) {
+            return Ok(Self::Ed25519(Ed25519EncryptedSeed::new(value)?));
+        }
+        if Secp256K1EncryptedSecretKey::is_valid_base58(&value) {
+            return Ok(Self::Secp256K1(Secp256K1EncryptedSecretKey::new(value)?));
+        }
+        if P256EncryptedSecretKey::is_valid_base58(&value) {
+            return Ok(Self::P256(P256EncryptedSecretKey::new(value)?));
+        }
+        if Bls12381EncryptedSecretKey::is_valid_base58(&value) {
+            return Ok(Self::Bls12381(Bls12381EncryptedSecretKey::new(value)?));
+        }
+        Err(Error::InvalidBase58EncodedData { description: value })
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if Ed25519EncryptedSeed::is_valid_bytes(bytes) {
+            return Ok(Self::Ed25519(Ed25519EncryptedSeed::from_bytes(bytes)?));
+        }
+        if Secp256K1EncryptedSecretKey::is_valid_bytes(bytes) {
+            return Ok(Self::Secp256K1(Secp256K1EncryptedSecretKey::from_bytes(
+                bytes,
+            )?));
+        }
+        if P256EncryptedSecretKey::is_valid_bytes(bytes) {
+            return Ok(Self::P256(P256EncryptedSecretKey::from_bytes(bytes)?));
+        }
+        if Bls12381EncryptedSecretKey::is_valid_bytes(bytes) {
+            return Ok(Self::Bls12381(Bls12381EncryptedSecretKey::from_bytes(
+                bytes,
+            )?));
+        }
+        Err(Error::InvalidBytes)
+    }
+}
+
+impl From<EncryptedSecretKey> for String {
+    fn from(value: EncryptedSecretKey) -> Self {
+        match value {
+            EncryptedSecretKey::Ed25519(value) => value.into(),
+            EncryptedSecretKey::Secp256K1(value) => value.into(),
+            EncryptedSecretKey::P256(value) => value.into(),
+            EncryptedSecretKey::Bls12381(value) => value.into(),
+        }
+    }
+}
+
+impl TryFrom<&Vec<u8>> for EncryptedSecretKey {
+    type Error = Error;
+
+    fn try_from(value: &Vec<u8>) -> Result<Self> {
+        Self::from_bytes(value)
+    }
+}
+
+impl TryFrom<String> for EncryptedSecretKey {
+    type Error = Error;
+
+    fn try_from(value: String) -> Result<Self> {
+        EncryptedSecretKey::new(value)
+    }
+}
+
+impl TryFrom<&str> for EncryptedSecretKey {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self> {
+        EncryptedSecretKey::new(value.to_string())
+    }
+}
+
+impl TryFrom<&EncryptedSecretKey> for Vec<u8> {
+    type Error = Error;
+
+    fn try_from(value: &EncryptedSecretKey) -> Result<Self> {
+        value.to_bytes()
+    }
+}
+
+impl From<Ed25519EncryptedSeed> for EncryptedSecretKey {
+    fn from(value: Ed25519EncryptedSeed) -> Self {
+        Self::Ed25519(value)
+    }
+}
+
+impl From<Secp256K1EncryptedSecretKey> for EncryptedSecretKey {
+    fn from(value: Secp256K1EncryptedSecretKey) -> Self {
+        Self::Secp256K1(value)
+    }
+}
+
+impl From<P256EncryptedSecretKey> for EncryptedSecretKey {
+    fn from(value: P256EncryptedSecretKey) -> Self {
+        Self::P256(value)
+    }
+}
+
+impl From<Bls12381EncryptedSecretKey> for EncryptedSecretKey {
+    fn from(value: Bls12381EncryptedSecretKey) -> Self {
+        Self::Bls12381(value)
+    }
+}
+
 /// Group of base58 encoded public keys.
 ///
 /// See:
 /// - [Ed25519PublicKey]
 /// - [Secp256K1PublicKey]
 /// - [P256PublicKey]
+/// - [Bls12381PublicKey]
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(
     feature = "serde",
@@ -266,6 +616,7 @@ pub enum PublicKey {
     Ed25519(Ed25519PublicKey),
     Secp256K1(Secp256K1PublicKey),
     P256(P256PublicKey),
+    Bls12381(Bls12381PublicKey),
 }
 
 impl PublicKey {
@@ -273,12 +624,65 @@ impl PublicKey {
         Ed25519PublicKey::is_valid_base58(value)
             || Secp256K1PublicKey::is_valid_base58(value)
             || P256PublicKey::is_valid_base58(value)
+            || Bls12381PublicKey::is_valid_base58(value)
     }
 
     pub fn is_valid_bytes(value: &[u8]) -> bool {
         Ed25519PublicKey::is_valid_bytes(value)
             || Secp256K1PublicKey::is_valid_bytes(value)
             || P256PublicKey::is_valid_bytes(value)
+            || Bls12381PublicKey::is_valid_bytes(value)
+    }
+
+    /// Computes the public key hash, i.e. the implicit (**tz1**, **tz2**, **tz3** or **tz4**) address of the key.
+    pub fn public_key_hash(&self) -> Result<ImplicitAddress> {
+        match self {
+            Self::Ed25519(key) => key.public_key_hash().map(|hash| hash.into()),
+            Self::Secp256K1(key) => key.public_key_hash().map(|hash| hash.into()),
+            Self::P256(key) => key.public_key_hash().map(|hash| hash.into()),
+            Self::Bls12381(key) => key.public_key_hash().map(|hash| hash.into()),
+        }
+    }
+
+    /// Computes the [Address] of the key. See [PublicKey::public_key_hash].
+    pub fn to_address(&self) -> Result<Address> {
+        self.public_key_hash().map(|hash| hash.into())
+    }
+}
+
+impl Ed25519PublicKey {
+    /// Computes the **tz1** public key hash of the key.
+    pub fn public_key_hash(&self) -> Result<Ed25519PublicKeyHash> {
+        let hash = blake2b(&self.to_bytes()?, PUBLIC_KEY_HASH_SIZE)?;
+
+        (&hash).try_into()
+    }
+}
+
+impl Secp256K1PublicKey {
+    /// Computes the **tz2** public key hash of the key.
+    pub fn public_key_hash(&self) -> Result<Secp256K1PublicKeyHash> {
+        let hash = blake2b(&self.to_bytes()?, PUBLIC_KEY_HASH_SIZE)?;
+
+        (&hash).try_into()
+    }
+}
+
+impl P256PublicKey {
+    /// Computes the **tz3** public key hash of the key.
+    pub fn public_key_hash(&self) -> Result<P256PublicKeyHash> {
+        let hash = blake2b(&self.to_bytes()?, PUBLIC_KEY_HASH_SIZE)?;
+
+        (&hash).try_into()
+    }
+}
+
+impl Bls12381PublicKey {
+    /// Computes the **tz4** public key hash of the key.
+    pub fn public_key_hash(&self) -> Result<Bls12381PublicKeyHash> {
+        let hash = blake2b(&self.to_bytes()?, PUBLIC_KEY_HASH_SIZE)?;
+
+        (&hash).try_into()
     }
 }
 
@@ -290,6 +694,7 @@ impl Encoded for PublicKey {
             Self::Ed25519(value) => value.value(),
             Self::Secp256K1(value) => value.value(),
             Self::P256(value) => value.value(),
+            Self::Bls12381(value) => value.value(),
         }
     }
 
@@ -298,6 +703,7 @@ impl Encoded for PublicKey {
             Self::Ed25519(value) => value.meta(),
             Self::Secp256K1(value) => value.meta(),
             Self::P256(value) => value.meta(),
+            Self::Bls12381(value) => value.meta(),
         }
     }
 
@@ -311,6 +717,9 @@ impl Encoded for PublicKey {
         if P256PublicKey::is_valid_base58(&value) {
             return Ok(Self::P256(P256PublicKey::new(value)?));
         }
+        if Bls12381PublicKey::is_valid_base58(&value) {
+            return Ok(Self::Bls12381(Bls12381PublicKey::new(value)?));
+        }
         Err(Error::InvalidBase58EncodedData { description: value })
     }
 }
@@ -321,6 +730,7 @@ impl From<PublicKey> for String {
             PublicKey::Ed25519(value) => value.into(),
             PublicKey::Secp256K1(value) => value.into(),
             PublicKey::P256(value) => value.into(),
+            PublicKey::Bls12381(value) => value.into(),
         }
     }
 }
@@ -375,6 +785,12 @@ impl From<P256PublicKey> for PublicKey {
     }
 }
 
+impl From<Bls12381PublicKey> for PublicKey {
+    fn from(value: Bls12381PublicKey) -> Self {
+        Self::Bls12381(value)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -431,6 +847,81 @@ mod test {
         Err(Error::InvalidConversion)
     }
 
+    #[test]
+    fn test_secret_key_debug_redacted() -> Result<()> {
+        let value = "edskRhKTQkgxb7CNTr31rzy3xdkyKaYX9hySAnZYJTPmUzPB7WU4NL7C8pmtQDgRqQ4jDw4Ugh6Y1UW5nvo7UYrRbyhVYK1YuR";
+        let key: SecretKey = value.try_into()?;
+        let debug = format!("{:?}", key);
+        assert_eq!(debug, "Ed25519(Ed25519SecretKey(\"<redacted>\"))");
+        assert!(!debug.contains(value));
+
+        let seed: Ed25519Seed = "edsk2ym28S1pkkCgG5KqWTWCNu5VVUMm6yKgRn7drdixVeppRQvqvb".try_into()?;
+        assert_eq!(format!("{:?}", seed), "Ed25519Seed(\"<redacted>\")");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_secret_key_redacted() -> Result<()> {
+        let value = "edskRhKTQkgxb7CNTr31rzy3xdkyKaYX9hySAnZYJTPmUzPB7WU4NL7C8pmtQDgRqQ4jDw4Ugh6Y1UW5nvo7UYrRbyhVYK1Yu";
+        let result: Result<SecretKey> = value.try_into();
+        assert!(matches!(
+            result,
+            Err(Error::InvalidBase58EncodedData { description }) if description == "<redacted>"
+        ));
+        let result: Result<Ed25519SecretKey> = value.try_into();
+        assert!(matches!(
+            result,
+            Err(Error::InvalidBase58EncodedData { description }) if description == "<redacted>"
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_bls12_381_public_key_bytes() -> Result<()> {
+        let key: PublicKey =
+            "BLpk1zKCZWUm6fXHxeLZY8jyv8m4cNKvYtM1yVC6uE9fViEGwU5CMRF8kL1DUKtdaqYmX71FJPCe"
+                .try_into()?;
+        let bytes = key.to_bytes()?;
+        assert_eq!(
+            hex::encode(&bytes),
+            "03b8f3a6c1e371d3bdba1a04e72f3fab9ee04f9e572399cf736323dd6023459625eb44feb02ddd8aa89bf4a59bdf9859df"
+        );
+        assert_eq!(PublicKey::from_bytes(&bytes)?, key);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_encrypted_secret_key() -> Result<()> {
+        let key = EncryptedSecretKey::from_uri(
+            "encrypted:edesk1GBQ31Tu4Cffk2AayCNiUBLRePCxB5fhPfPanj1oqCUy7uvpxUYsoz5UvfYBkRqaQfRWkbEZFWZJDa4WJ2P",
+        )?;
+        assert!(matches!(key, EncryptedSecretKey::Ed25519(_)));
+        assert_eq!(
+            key.to_uri(),
+            "encrypted:edesk1GBQ31Tu4Cffk2AayCNiUBLRePCxB5fhPfPanj1oqCUy7uvpxUYsoz5UvfYBkRqaQfRWkbEZFWZJDa4WJ2P"
+        );
+
+        let key: EncryptedSecretKey =
+            "spesk1RzvxguVHK8kCEeiVL2KZ81iPecQUeABgEQDgZ8STMeL2wur1X78MuFjZbqh2iHkABpkFkqLbibayVWCQNa"
+                .try_into()?;
+        assert!(matches!(key, EncryptedSecretKey::Secp256K1(_)));
+
+        let key: EncryptedSecretKey =
+            "p2esk2TFqgNcoT4u99ut5doGTUFNwo9x4nNvkpM6YMLqXrt4SbFdQnqLM3hoAXLMB2uZYazj6LZGvcoYzk16H6Et"
+                .try_into()?;
+        assert!(matches!(key, EncryptedSecretKey::P256(_)));
+
+        assert!(EncryptedSecretKey::from_uri(
+            "unencrypted:edskRhKTQkgxb7CNTr31rzy3xdkyKaYX9hySAnZYJTPmUzPB7WU4NL7C8pmtQDgRqQ4jDw4Ugh6Y1UW5nvo7UYrRbyhVYK1YuR"
+        )
+        .is_err());
+
+        Ok(())
+    }
+
     #[test]
     fn test_secp_256_k1_key_1() -> Result<()> {
         let key: Key = "sppkDN74FpFyXiHUe7MZS7rwDzzwb2esc21355LEcSExN67KdNnAfqA".try_into()?;
@@ -536,4 +1027,155 @@ mod test {
         }
         Err(Error::InvalidConversion)
     }
+
+    #[cfg(feature = "ed25519")]
+    #[test]
+    fn test_ed25519_public_key_from_secret_key() -> Result<()> {
+        let secret_key: SecretKey = "edskRv7VyXGVZb8EsrR7D9XKUbbAQNQGtALP6QeB16ZCD7SmmJpzyeneJVg3Mq56YLbxRA1kSdAXiswwPiaVfR3NHGMCXCziuZ".try_into()?;
+        let public_key = secret_key.public_key()?;
+        assert_eq!(
+            public_key.value(),
+            "edpkttZKC51wemRqL2QxwpMnEKxWnbd35pq47Y6xsCHp5M1f7LN8NP"
+        );
+        assert_eq!(
+            public_key.public_key_hash()?.value(),
+            "tz1RFxSHjxxyaaXyAvJG3uRQeJUCCm4jk2LX"
+        );
+
+        Ok(())
+    }
+
+    #[cfg(feature = "secp256_k1")]
+    #[test]
+    fn test_secp256_k1_public_key_from_secret_key() -> Result<()> {
+        let secret_key: SecretKey =
+            "spsk1SsrWCpufeXkNruaG9L3Mf9dRyd4D8HsM8ftqseN1fne3x9LNk".try_into()?;
+        let public_key = secret_key.public_key()?;
+        assert_eq!(
+            public_key.value(),
+            "sppk7ZpH5qAjTDZn1o1TW7z2QbQZUcMHRn2wtV4rRfz15eLQrvPkt6k"
+        );
+        assert_eq!(
+            public_key.public_key_hash()?.value(),
+            "tz2R3oTJR3cLfSyJVQiv8NGN4wXTQj58UYjp"
+        );
+
+        Ok(())
+    }
+
+    #[cfg(feature = "p256")]
+    #[test]
+    fn test_p256_public_key_from_secret_key() -> Result<()> {
+        let secret_key: SecretKey =
+            "p2sk2rVhhi5EfEdhJ3wQGsdc4ZEN3i7Z8f73Bn1xp1JKjETNyJ85oW".try_into()?;
+        let public_key = secret_key.public_key()?;
+        assert_eq!(
+            public_key.value(),
+            "p2pk67fo5oy6byruqDtzVixbM7L3cVBDRMcFhA33XD5w2HF4fRXDJhw"
+        );
+        assert_eq!(
+            public_key.public_key_hash()?.value(),
+            "tz3duiskLgZdaEvkgEwWYF4mUnVXde7JTtef"
+        );
+
+        Ok(())
+    }
+
+    #[cfg(feature = "bls")]
+    #[test]
+    fn test_bls12_381_public_key_from_secret_key() -> Result<()> {
+        let secret_key: SecretKey =
+            "BLsk1WMaoyRDXHuLDViHoExYpeCE52AH9y3n2YZUrF1yYPqgkMxLQB".try_into()?;
+        let public_key = secret_key.public_key()?;
+        assert_eq!(
+            public_key.value(),
+            "BLpk1zKCZWUm6fXHxeLZY8jyv8m4cNKvYtM1yVC6uE9fViEGwU5CMRF8kL1DUKtdaqYmX71FJPCe"
+        );
+        assert_eq!(
+            public_key.public_key_hash()?.value(),
+            "tz4NwCZN4oPZRo7xHPHRW4an6hzxdhVCHoer"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_public_key_to_address() -> Result<()> {
+        let public_key: PublicKey =
+            "edpkttZKC51wemRqL2QxwpMnEKxWnbd35pq47Y6xsCHp5M1f7LN8NP".try_into()?;
+        assert_eq!(
+            public_key.to_address()?.value(),
+            "tz1RFxSHjxxyaaXyAvJG3uRQeJUCCm4jk2LX"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_public_key_without_crypto_provider() -> Result<()> {
+        let tezos = Tezos::new(Box::new(NoCryptoConfig));
+        let secret_key: SecretKey = "edskRv7VyXGVZb8EsrR7D9XKUbbAQNQGtALP6QeB16ZCD7SmmJpzyeneJVg3Mq56YLbxRA1kSdAXiswwPiaVfR3NHGMCXCziuZ".try_into()?;
+        assert!(matches!(
+            secret_key.public_key_with(&tezos),
+            Err(Error::CryptoProviderNotSet)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_public_key_with_provider_without_derivation() -> Result<()> {
+        let tezos = Tezos::new(Box::new(SignOnlyCryptoConfig));
+        let secret_key: SecretKey = "edskRv7VyXGVZb8EsrR7D9XKUbbAQNQGtALP6QeB16ZCD7SmmJpzyeneJVg3Mq56YLbxRA1kSdAXiswwPiaVfR3NHGMCXCziuZ".try_into()?;
+        assert!(matches!(
+            secret_key.public_key_with(&tezos),
+            Err(Error::PublicKeyDerivationNotSupported)
+        ));
+
+        Ok(())
+    }
+
+    struct NoCryptoConfig;
+
+    impl crate::CryptoConfig for NoCryptoConfig {
+        fn get_ed25519_crypto_provider(&self) -> Option<Box<dyn crate::CryptoProvider>> {
+            None
+        }
+
+        fn get_secp256_k1_crypto_provider(&self) -> Option<Box<dyn crate::CryptoProvider>> {
+            None
+        }
+
+        fn get_p256_crypto_provider(&self) -> Option<Box<dyn crate::CryptoProvider>> {
+            None
+        }
+    }
+
+    struct SignOnlyCryptoProvider;
+
+    impl crate::CryptoProvider for SignOnlyCryptoProvider {
+        fn sign(&self, _message: &[u8], _secret: &[u8]) -> Result<Vec<u8>> {
+            Ok(vec![0u8; 64])
+        }
+
+        fn verify(&self, _message: &[u8], _signature: &[u8], _public_key: &[u8]) -> Result<bool> {
+            Ok(false)
+        }
+    }
+
+    struct SignOnlyCryptoConfig;
+
+    impl crate::CryptoConfig for SignOnlyCryptoConfig {
+        fn get_ed25519_crypto_provider(&self) -> Option<Box<dyn crate::CryptoProvider>> {
+            Some(Box::new(SignOnlyCryptoProvider))
+        }
+
+        fn get_secp256_k1_crypto_provider(&self) -> Option<Box<dyn crate::CryptoProvider>> {
+            None
+        }
+
+        fn get_p256_crypto_provider(&self) -> Option<Box<dyn crate::CryptoProvider>> {
+            None
+        }
+    }
 }
@@ -9,6 +9,7 @@ macro_rules! make_encoded_structs {
                         )+
                     }
                 )?
+                $(#[$kind:ident])?
                 struct $mod_name:ident::$name:ident;
                 coder: $coder:ty;
                 meta {
@@ -45,6 +46,86 @@ macro_rules! make_encoded_structs {
             )+
         ];
 
+        /// Any of the base58 encoded Tezos values, detected from the content of the value.
+        ///
+        /// The type is recognized from the versioned bytes prefix of the base58check decoded value. Values
+        /// shared by several types (e.g. `tz4` addresses) resolve to the type declared first.
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        #[cfg_attr(
+            feature = "serde",
+            derive(serde::Serialize, serde::Deserialize),
+            serde(try_from = "alloc::string::String", untagged)
+        )]
+        pub enum AnyEncoded {
+            $(
+                $name($name),
+            )+
+        }
+
+        impl $crate::types::encoded::Encoded for AnyEncoded {
+            type Coder = $crate::internal::coder::EncodedBytesCoder;
+
+            fn value(&self) -> &str {
+                match self {
+                    $(
+                        Self::$name(value) => value.value(),
+                    )+
+                }
+            }
+
+            fn meta(&self) -> &'static $crate::types::encoded::MetaEncoded {
+                match self {
+                    $(
+                        Self::$name(value) => value.meta(),
+                    )+
+                }
+            }
+
+            fn new(value: alloc::string::String) -> $crate::Result<Self> {
+                if let Ok(bytes) = bs58::decode(&value).with_check(None).into_vec() {
+                    let bytes = zeroize::Zeroizing::new(bytes);
+                    $(
+                        if $mod_name::META.is_valid_prefixed_bytes(&bytes) {
+                            return $name::new(value).map(Self::$name);
+                        }
+                    )+
+                }
+                Err($crate::Error::InvalidBase58EncodedData { description: value })
+            }
+        }
+
+        $(
+            impl From<$name> for AnyEncoded {
+                fn from(value: $name) -> Self {
+                    Self::$name(value)
+                }
+            }
+        )+
+
+        impl TryFrom<alloc::string::String> for AnyEncoded {
+            type Error = $crate::Error;
+
+            fn try_from(value: alloc::string::String) -> $crate::Result<Self> {
+                Self::new(value)
+            }
+        }
+
+        impl TryFrom<&str> for AnyEncoded {
+            type Error = $crate::Error;
+
+            fn try_from(value: &str) -> $crate::Result<Self> {
+                Self::new(value.into())
+            }
+        }
+
+        impl TryFrom<&[u8]> for AnyEncoded {
+            type Error = $crate::Error;
+
+            fn try_from(value: &[u8]) -> $crate::Result<Self> {
+                Self::from_bytes(value)
+            }
+        }
+
         $(
             make_encoded_struct!(
                 $(
@@ -54,6 +135,7 @@ macro_rules! make_encoded_structs {
                         )+
                     }
                 )?
+                $(#[$kind])?
                 struct $mod_name::$name;
                 coder: $coder;
                 meta {
@@ -89,6 +171,7 @@ macro_rules! make_encoded_struct {
                 )+
             }
         )?
+        $(#[$kind:ident])?
         struct $mod_name:ident::$name:ident;
         coder: $coder:ty;
         meta {
@@ -117,18 +200,14 @@ macro_rules! make_encoded_struct {
                     $($type_path, )+
                 };
             )?
+            use alloc::{string::{String, ToString}, vec::Vec};
             use $crate::{
                 types::encoded::{Encoded, MetaEncoded, TraitMetaEncoded},
                 Error, Result,
                 internal::{consumable_list::ConsumableList, coder::ConsumingDecoder},
             };
-            #[cfg(feature = "serde")]
-            use serde::{Deserialize, Serialize};
 
-            /// Structure representing a base58 encoded Tezos value
-            #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-            #[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde( try_from = "String"))]
-            pub struct $name(String);
+            $crate::types::encoded::macros::make_encoded_struct_type!($($kind)? $name);
 
             impl $name {
                 pub fn is_valid_base58(value: &str) -> bool {
@@ -167,7 +246,7 @@ macro_rules! make_encoded_struct {
                     if META.is_valid_base58(&value) {
                         return Ok($name(value));
                     }
-                    return Err(Error::InvalidBase58EncodedData { description: value });
+                    return Err(Error::InvalidBase58EncodedData { description: Self::describe_invalid(value) });
                 }
 
                 fn from_bytes(bytes: &[u8]) -> Result<Self> {
@@ -190,12 +269,6 @@ macro_rules! make_encoded_struct {
 
             pub const META: MetaEncoded = MetaEncoded::new($b58_prefix, $b58_length, &[$($b_prefix, )+], $b_length);
 
-            impl From<$name> for String {
-                fn from(value: $name) -> Self {
-                    value.0
-                }
-            }
-
             impl TryFrom<&Vec<u8>> for $name {
                 type Error = Error;
 
@@ -276,4 +349,83 @@ macro_rules! make_encoded_struct {
     };
 }
 
-pub(crate) use {make_encoded_struct, make_encoded_structs};
+macro_rules! make_encoded_struct_type {
+    (secret $name:ident) => {
+        #[cfg(feature = "serde")]
+        use serde::{Deserialize, Serialize};
+        use zeroize::{Zeroize, ZeroizeOnDrop};
+
+        /// Structure representing a base58 encoded Tezos secret value.
+        ///
+        /// The value is zeroized on drop and redacted from the `Debug` output.
+        #[derive(Clone, PartialEq, Eq, Hash)]
+        #[cfg_attr(
+            feature = "serde",
+            derive(Serialize, Deserialize),
+            serde(try_from = "String")
+        )]
+        pub struct $name(String);
+
+        impl $name {
+            fn describe_invalid(mut value: String) -> String {
+                value.zeroize();
+                "<redacted>".into()
+            }
+        }
+
+        impl core::fmt::Debug for $name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.debug_tuple(stringify!($name))
+                    .field(&"<redacted>")
+                    .finish()
+            }
+        }
+
+        impl Zeroize for $name {
+            fn zeroize(&mut self) {
+                self.0.zeroize()
+            }
+        }
+
+        impl Drop for $name {
+            fn drop(&mut self) {
+                self.zeroize()
+            }
+        }
+
+        impl ZeroizeOnDrop for $name {}
+
+        impl From<$name> for String {
+            fn from(mut value: $name) -> Self {
+                core::mem::take(&mut value.0)
+            }
+        }
+    };
+    ($name:ident) => {
+        #[cfg(feature = "serde")]
+        use serde::{Deserialize, Serialize};
+
+        /// Structure representing a base58 encoded Tezos value
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        #[cfg_attr(
+            feature = "serde",
+            derive(Serialize, Deserialize),
+            serde(try_from = "String")
+        )]
+        pub struct $name(String);
+
+        impl $name {
+            fn describe_invalid(value: String) -> String {
+                value
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+    };
+}
+
+pub(crate) use {make_encoded_struct, make_encoded_struct_type, make_encoded_structs};
@@ -1,3 +1,7 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -7,11 +11,12 @@ use crate::{
         consumable_list::ConsumableList,
     },
     types::encoded::{
+        bls12_381_public_key::Bls12381PublicKey, bls12_381_signature::Bls12381Signature,
         ed25519_signature::Ed25519Signature, generic_signature::GenericSignature,
         p256_signature::P256Signature, secp256_k1_signature::Secp256K1Signature, Encoded,
         MetaEncoded,
     },
-    Error, Result,
+    Error, Result, Tezos,
 };
 
 /// Group of base58 encoded signatures.
@@ -21,6 +26,7 @@ use crate::{
 /// - [Ed25519Signature]
 /// - [Secp256K1Signature]
 /// - [P256Signature]
+/// - [Bls12381Signature]
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(
     feature = "serde",
@@ -32,6 +38,7 @@ pub enum Signature {
     Ed25519(Ed25519Signature),
     Secp256K1(Secp256K1Signature),
     P256(P256Signature),
+    Bls12381(Bls12381Signature),
 }
 
 impl Signature {
@@ -41,10 +48,71 @@ impl Signature {
             Signature::Ed25519(value) => (&value.to_bytes()?).try_into(),
             Signature::Secp256K1(value) => (&value.to_bytes()?).try_into(),
             Signature::P256(value) => (&value.to_bytes()?).try_into(),
+            Signature::Bls12381(_) => Err(Error::InvalidConversion),
         }
     }
 }
 
+impl Bls12381Signature {
+    /// Aggregates the given signatures into a single signature using the crypto providers
+    /// configured in the given [Tezos] instance.
+    pub fn aggregate_with(signatures: &[Bls12381Signature], tezos: &Tezos) -> Result<Self> {
+        let signatures = signatures
+            .iter()
+            .map(|signature| signature.to_bytes())
+            .collect::<Result<Vec<_>>>()?;
+        let signatures = signatures
+            .iter()
+            .map(|signature| signature.as_slice())
+            .collect::<Vec<_>>();
+        let signature = tezos
+            .get_crypto()
+            .aggregate_signatures_bls12_381(&signatures)?;
+
+        (&signature).try_into()
+    }
+
+    /// Aggregates the given signatures into a single signature.
+    pub fn aggregate(signatures: &[Bls12381Signature]) -> Result<Self> {
+        let tezos: Tezos = Default::default();
+
+        Self::aggregate_with(signatures, &tezos)
+    }
+
+    /// Verifies the aggregated signature, where the message at each index has been signed by the key at the same index,
+    /// using the crypto providers configured in the given [Tezos] instance.
+    pub fn aggregate_verify_with(
+        &self,
+        messages: &[&[u8]],
+        public_keys: &[Bls12381PublicKey],
+        tezos: &Tezos,
+    ) -> Result<bool> {
+        let public_keys = public_keys
+            .iter()
+            .map(|public_key| public_key.to_bytes())
+            .collect::<Result<Vec<_>>>()?;
+        let public_keys = public_keys
+            .iter()
+            .map(|key| key.as_slice())
+            .collect::<Vec<_>>();
+
+        tezos
+            .get_crypto()
+            .aggregate_verify_bls12_381(messages, &self.to_bytes()?, &public_keys)
+    }
+
+    /// Verifies the aggregated signature, where the message at each index has been signed by the key at the same index.
+    pub fn aggregate_verify(
+        &self,
+        messages: &[&[u8]],
+        public_keys: &[Bls12381PublicKey],
+    ) -> Result<bool> {
+        let tezos: Tezos = Default::default();
+
+        self.aggregate_verify_with(messages, public_keys, &tezos)
+    }
+}
+
 impl Encoded for Signature {
     type Coder = EncodedBytesCoder;
 
@@ -54,6 +122,7 @@ impl Encoded for Signature {
             Self::Ed25519(value) => value.value(),
             Self::Secp256K1(value) => value.value(),
             Self::P256(value) => value.value(),
+            Self::Bls12381(value) => value.value(),
         }
     }
 
@@ -63,6 +132,7 @@ impl Encoded for Signature {
             Self::Ed25519(value) => value.meta(),
             Self::Secp256K1(value) => value.meta(),
             Self::P256(value) => value.meta(),
+            Self::Bls12381(value) => value.meta(),
         }
     }
 
@@ -79,6 +149,9 @@ impl Encoded for Signature {
         if P256Signature::is_valid_base58(&base58) {
             return Ok(Self::P256(P256Signature::new(base58)?));
         }
+        if Bls12381Signature::is_valid_base58(&base58) {
+            return Ok(Self::Bls12381(Bls12381Signature::new(base58)?));
+        }
         Err(Error::InvalidBase58EncodedData {
             description: base58,
         })
@@ -90,6 +163,7 @@ impl Encoded for Signature {
             Self::Ed25519(value) => value.to_bytes(),
             Self::Secp256K1(value) => value.to_bytes(),
             Self::P256(value) => value.to_bytes(),
+            Self::Bls12381(value) => value.to_bytes(),
         }
     }
 
@@ -106,6 +180,9 @@ impl Encoded for Signature {
         if P256Signature::is_valid_bytes(bytes) {
             return Ok(Self::P256(P256Signature::from_bytes(bytes)?));
         }
+        if Bls12381Signature::is_valid_bytes(bytes) {
+            return Ok(Self::Bls12381(Bls12381Signature::from_bytes(bytes)?));
+        }
 
         Ok(Self::Generic(GenericSignature::from_bytes(bytes)?))
     }
@@ -132,6 +209,11 @@ impl Encoded for Signature {
         if P256Signature::is_valid_prefixed_consumable_bytes(bytes.inner_value()) {
             return Ok(Self::P256(P256Signature::from_consumable_bytes(bytes)?));
         }
+        if Bls12381Signature::is_valid_prefixed_consumable_bytes(bytes.inner_value()) {
+            return Ok(Self::Bls12381(Bls12381Signature::from_consumable_bytes(
+                bytes,
+            )?));
+        }
 
         Ok(Self::Generic(GenericSignature::from_consumable_bytes(
             bytes,
@@ -163,6 +245,12 @@ impl From<P256Signature> for Signature {
     }
 }
 
+impl From<Bls12381Signature> for Signature {
+    fn from(value: Bls12381Signature) -> Self {
+        Self::Bls12381(value)
+    }
+}
+
 impl TryFrom<&Vec<u8>> for Signature {
     type Error = Error;
 
@@ -206,4 +294,47 @@ mod test {
         assert_eq!(generic.value(), "sigTAzhy1HsZDLNETmuf9RuinhXRb5jvmscjCoPPBujWZgFmCFLffku7JXYtu8aYQFVHnCUghmd4t39RuR6ANV76bCCYTR9u");
         Ok(())
     }
+
+    #[test]
+    fn test_bls12_381_signature() -> Result<()> {
+        let signature: Signature = "BLsig9x7su643xDsVesPNiR2cttEcCF2g62F81Kk58HRs3aXUWe2PinjVKV7wHG4aTk2gxNieJ4mN5Q18Tp8inH2MfJUK9vj4wGkQMPaB3ZKyepY7Jcbv2HbnALh5oH4quy9gW6yf8u71j".try_into()?;
+        assert!(matches!(signature, Signature::Bls12381(_)));
+        assert_eq!(Signature::from_bytes(&signature.to_bytes()?)?, signature);
+        assert!(matches!(
+            signature.to_generic_signature(),
+            Err(Error::InvalidConversion)
+        ));
+
+        Ok(())
+    }
+
+    #[cfg(feature = "bls")]
+    #[test]
+    fn test_bls12_381_aggregate_verify() -> Result<()> {
+        use crate::types::encoded::SecretKey;
+
+        let tezos: Tezos = Default::default();
+        let secret_keys: Vec<SecretKey> = vec![
+            "BLsk1WMaoyRDXHuLDViHoExYpeCE52AH9y3n2YZUrF1yYPqgkMxLQB".try_into()?,
+            "BLsk1kWBktGaWXEwzpGibK2BZTmqd1e2KsMXHS2RPozzdr3c9hExVR".try_into()?,
+        ];
+        let messages: [&[u8]; 2] = [b"attestation", b"tezos"];
+        let mut public_keys = vec![];
+        let mut signatures = vec![];
+        for (secret_key, message) in secret_keys.iter().zip(messages) {
+            if let SecretKey::Bls12381(secret_key) = secret_key {
+                public_keys.push(secret_key.public_key()?);
+                let signature = tezos
+                    .get_crypto()
+                    .sign_bls12_381(message, &secret_key.to_bytes()?)?;
+                signatures.push(Bls12381Signature::try_from(&signature)?);
+            }
+        }
+
+        let signature = Bls12381Signature::aggregate(&signatures)?;
+        assert!(signature.aggregate_verify(&messages, &public_keys)?);
+        assert!(!signature.aggregate_verify(&[b"tezos", b"attestation"], &public_keys)?);
+
+        Ok(())
+    }
 }
@@ -1,15 +1,10 @@
 //! Hex String type.
 
-use lazy_static::lazy_static;
-use regex::Regex;
+use alloc::{format, string::String, vec::Vec};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::{Error, Result};
-
-lazy_static! {
-    static ref REGEX: Regex = Regex::new("^(0x)?([0-9a-fA-F]{2})*$").unwrap();
-}
+use crate::{internal::utils::is_hex_string, Error, Result};
 
 /// Hexadecimal [String] type-safe representation.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -62,7 +57,7 @@ impl HexString {
     }
 
     fn is_valid(value: &str) -> bool {
-        REGEX.is_match(value)
+        is_hex_string(value)
     }
 }
 
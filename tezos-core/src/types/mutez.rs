@@ -1,30 +1,30 @@
 //! Tezos Mutez type.
 
-use std::{fmt::Debug, str::FromStr};
+use alloc::{format, string::{String, ToString}, vec::Vec};
+use core::{
+    fmt::Debug,
+    iter::Sum,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign},
+    str::FromStr,
+};
 
 use derive_more;
 use derive_more::{
-    Add, AddAssign, BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Display, Div,
-    DivAssign, Mul, MulAssign, Not, Octal, Product, Rem, RemAssign, Shl, ShlAssign, Shr, ShrAssign,
-    Sub, SubAssign, Sum,
+    BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Display, Octal, Rem, RemAssign,
+    Shr, ShrAssign,
 };
-use lazy_static::lazy_static;
-use ibig::UBig;
+use ibig::{IBig, UBig};
 use num_traits::ToPrimitive;
-use regex::Regex;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use crate::internal::coder::{ConsumingDecoder, Decoder, Encoder, MutezBytesCoder};
 use crate::internal::consumable_list::ConsumableList;
+use crate::internal::utils::is_natural_string;
 use crate::{Error, Result};
 
 use super::number::{Nat, Int};
 
-lazy_static! {
-    static ref REGEX: Regex = Regex::new(r"^[0-9]+$").unwrap();
-}
-
 /// Tezos Mutez type. It can be encoded into and initialized from bytes and many other number
 /// representations.
 ///
@@ -37,9 +37,19 @@ lazy_static! {
 /// ```
 ///
 /// Internally the number is represented with an [i64], but negative values are invalid.
+///
+/// The arithmetic operators panic if the result is negative or exceeds [Mutez::MAX], use the `checked_*`
+/// and `saturating_*` methods to handle these cases.
+///
+/// Amounts can also be parsed from and formatted to decimal tez strings:
+///
+/// ```
+/// use tezos_core::types::mutez::Mutez;
+/// let amount = Mutez::from_tez("1.25").expect("valid tez string");
+/// assert_eq!(amount, 1_250_000u32.into());
+/// assert_eq!(amount.to_tez_string(), "1.25");
+/// ```
 #[derive(
-    Add,
-    AddAssign,
     PartialEq,
     PartialOrd,
     Debug,
@@ -53,28 +63,13 @@ lazy_static! {
     BitOrAssign,
     BitXor,
     BitXorAssign,
-    Div,
-    DivAssign,
-    Mul,
-    MulAssign,
-    Not,
     Octal,
-    Product,
     Rem,
     RemAssign,
-    Shl,
-    ShlAssign,
     Shr,
     ShrAssign,
-    Sub,
-    SubAssign,
-    Sum,
     Hash,
 )]
-#[div(forward)]
-#[div_assign(forward)]
-#[mul(forward)]
-#[mul_assign(forward)]
 #[rem(forward)]
 #[rem_assign(forward)]
 #[cfg_attr(
@@ -85,8 +80,116 @@ lazy_static! {
 pub struct Mutez(#[cfg_attr(feature = "serde", serde(serialize_with = "i64_to_string"))] i64);
 
 impl Mutez {
+    /// The smallest valid amount.
+    pub const ZERO: Mutez = Mutez(0);
+    /// The largest amount a Michelson `mutez` value can hold (2^63 - 1).
+    pub const MAX: Mutez = Mutez(i64::MAX);
+    /// The number of decimal digits of a tez amount.
+    pub const TEZ_DECIMALS: usize = 6;
+    /// The number of mutez in one tez.
+    pub const MUTEZ_PER_TEZ: i64 = 1_000_000;
+
+    const TEZ_SYMBOL: char = 'ꜩ';
+
     pub fn is_valid(value: &str) -> bool {
-        REGEX.is_match(value)
+        is_natural_string(value)
+    }
+
+    /// Parses a decimal tez amount, e.g. `"1.25"` or `"0.000001 ꜩ"`, into [Mutez].
+    ///
+    /// At most [Mutez::TEZ_DECIMALS] fractional digits are accepted, so the conversion is always exact.
+    pub fn from_tez(value: &str) -> Result<Self> {
+        let value = value.trim();
+        let value = value
+            .strip_suffix(Self::TEZ_SYMBOL)
+            .map(str::trim_end)
+            .unwrap_or(value);
+        let (integer, fraction) = value.split_once('.').unwrap_or((value, ""));
+        if !is_natural_string(integer)
+            || (value.contains('.') && !is_natural_string(fraction))
+            || fraction.len() > Self::TEZ_DECIMALS
+        {
+            return Err(Error::InvalidTezString);
+        }
+
+        let integer = integer
+            .parse::<i64>()
+            .map_err(|_| Error::MutezOverflow)?
+            .checked_mul(Self::MUTEZ_PER_TEZ)
+            .ok_or(Error::MutezOverflow)?;
+        let fraction = format!("{:0<width$}", fraction, width = Self::TEZ_DECIMALS);
+
+        Self(integer).checked_add(Self(fraction.parse()?))
+    }
+
+    /// Formats the amount as a decimal tez string without trailing fractional zeros, e.g. `"1.25"`.
+    pub fn to_tez_string(&self) -> String {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let value = self.0.unsigned_abs();
+        let per_tez = Self::MUTEZ_PER_TEZ as u64;
+        let fraction = format!("{:0width$}", value % per_tez, width = Self::TEZ_DECIMALS);
+        let fraction = fraction.trim_end_matches('0');
+        if fraction.is_empty() {
+            format!("{}{}", sign, value / per_tez)
+        } else {
+            format!("{}{}.{}", sign, value / per_tez, fraction)
+        }
+    }
+
+    /// Adds the amounts, returns [Error::MutezOverflow] if the result exceeds [Mutez::MAX].
+    pub fn checked_add(self, other: Mutez) -> Result<Self> {
+        Self::checked(self.0.checked_add(other.0))
+    }
+
+    /// Subtracts the amounts, returns [Error::MutezUnderflow] if the result would be negative.
+    pub fn checked_sub(self, other: Mutez) -> Result<Self> {
+        Self::checked(self.0.checked_sub(other.0))
+    }
+
+    /// Multiplies the amount by a natural factor, returns [Error::MutezOverflow] if the result exceeds [Mutez::MAX].
+    pub fn checked_mul(self, factor: u64) -> Result<Self> {
+        let factor = i64::try_from(factor).map_err(|_| Error::MutezOverflow)?;
+        Self::checked(self.0.checked_mul(factor))
+    }
+
+    /// Divides the amount by a natural divisor rounding down, returns [Error::DivisionByZero] if the divisor is 0.
+    pub fn checked_div(self, divisor: u64) -> Result<Self> {
+        if divisor == 0 {
+            return Err(Error::DivisionByZero);
+        }
+        let divisor = i64::try_from(divisor).unwrap_or(i64::MAX);
+
+        Self::checked(Some(self.0 / divisor))
+    }
+
+    /// Adds the amounts, clamping the result at [Mutez::MAX].
+    pub fn saturating_add(self, other: Mutez) -> Self {
+        self.checked_add(other).unwrap_or(Self::MAX)
+    }
+
+    /// Subtracts the amounts, clamping the result at [Mutez::ZERO].
+    pub fn saturating_sub(self, other: Mutez) -> Self {
+        self.checked_sub(other).unwrap_or(Self::ZERO)
+    }
+
+    /// Multiplies the amount by a natural factor, clamping the result at [Mutez::MAX].
+    pub fn saturating_mul(self, factor: u64) -> Self {
+        self.checked_mul(factor).unwrap_or(Self::MAX)
+    }
+
+    fn checked(value: Option<i64>) -> Result<Self> {
+        match value {
+            Some(value) if value < 0 => Err(Error::MutezUnderflow),
+            Some(value) => Ok(Self(value)),
+            None => Err(Error::MutezOverflow),
+        }
+    }
+
+    fn from_ibig(value: IBig) -> Result<Self> {
+        if value < IBig::from(0u8) {
+            return Err(Error::MutezUnderflow);
+        }
+        Ok(Self(value.try_into().map_err(|_| Error::MutezOverflow)?))
     }
 
     pub(super) fn value(&self) -> u64 {
@@ -163,8 +266,15 @@ impl TryFrom<&Int> for Mutez {
     type Error = Error;
 
     fn try_from(value: &Int) -> Result<Self> {
-        let uint = value.to_u64().ok_or(Error::InvalidIntegerConversion)?;
-        uint.try_into()
+        Self::from_ibig(value.into())
+    }
+}
+
+impl TryFrom<Int> for Mutez {
+    type Error = Error;
+
+    fn try_from(value: Int) -> Result<Self> {
+        Self::from_ibig(value.into())
     }
 }
 
@@ -317,7 +427,7 @@ impl TryFrom<&Nat> for Mutez {
     type Error = Error;
 
     fn try_from(value: &Nat) -> Result<Self> {
-        value.to_string().try_into()
+        value.clone().try_into()
     }
 }
 
@@ -337,6 +447,74 @@ impl TryFrom<&Mutez> for Vec<u8> {
     }
 }
 
+impl Add for Mutez {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        self.checked_add(rhs).expect("Mutez addition overflow")
+    }
+}
+
+impl AddAssign for Mutez {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Mutez {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self.checked_sub(rhs).expect("Mutez subtraction underflow")
+    }
+}
+
+impl SubAssign for Mutez {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<u64> for Mutez {
+    type Output = Self;
+
+    fn mul(self, rhs: u64) -> Self {
+        self.checked_mul(rhs).expect("Mutez multiplication overflow")
+    }
+}
+
+impl MulAssign<u64> for Mutez {
+    fn mul_assign(&mut self, rhs: u64) {
+        *self = *self * rhs;
+    }
+}
+
+impl Div<u64> for Mutez {
+    type Output = Self;
+
+    fn div(self, rhs: u64) -> Self {
+        self.checked_div(rhs).expect("Mutez division by zero")
+    }
+}
+
+impl DivAssign<u64> for Mutez {
+    fn div_assign(&mut self, rhs: u64) {
+        *self = *self / rhs;
+    }
+}
+
+impl Sum for Mutez {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Mutez> for Mutez {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
 impl Default for Mutez {
     fn default() -> Self {
         Self(0)
@@ -367,6 +545,37 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_operators() {
+        let v1: Mutez = 1u8.into();
+        let v2: Mutez = 2u8.into();
+
+        let mut value = v1 + v2;
+        value -= v1;
+        value *= 3;
+        assert_eq!(value, 6u8.into());
+        assert_eq!(value / 4, 1u8.into());
+        assert_eq!([v1, v2, v2].iter().sum::<Mutez>(), 5u8.into());
+    }
+
+    #[test]
+    #[should_panic(expected = "Mutez addition overflow")]
+    fn test_add_overflow() {
+        let _ = Mutez::MAX + 1u8.into();
+    }
+
+    #[test]
+    #[should_panic(expected = "Mutez subtraction underflow")]
+    fn test_sub_underflow() {
+        let _ = Mutez::from(1u8) - 2u8.into();
+    }
+
+    #[test]
+    #[should_panic(expected = "Mutez multiplication overflow")]
+    fn test_mul_overflow() {
+        let _ = Mutez::MAX * 2;
+    }
+
     #[test]
     fn test_cmp() -> Result<()> {
         let v1: Mutez = 1u8.into();
@@ -376,4 +585,121 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn test_checked_arithmetic() -> Result<()> {
+        let v1: Mutez = 1u8.into();
+        let v2: Mutez = 2u8.into();
+
+        assert_eq!(v1.checked_add(v2)?, 3u8.into());
+        assert_eq!(v2.checked_sub(v1)?, 1u8.into());
+        assert_eq!(v2.checked_mul(3)?, 6u8.into());
+        assert_eq!(Mutez::from(7u8).checked_div(2)?, 3u8.into());
+        assert!(matches!(
+            Mutez::MAX.checked_add(v1),
+            Err(Error::MutezOverflow)
+        ));
+        assert!(matches!(v1.checked_sub(v2), Err(Error::MutezUnderflow)));
+        assert!(matches!(
+            Mutez::MAX.checked_mul(2),
+            Err(Error::MutezOverflow)
+        ));
+        assert!(matches!(
+            v1.checked_mul(u64::MAX),
+            Err(Error::MutezOverflow)
+        ));
+        assert!(matches!(v1.checked_div(0), Err(Error::DivisionByZero)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_saturating_arithmetic() {
+        let v1: Mutez = 1u8.into();
+        let v2: Mutez = 2u8.into();
+
+        assert_eq!(v1.saturating_add(v2), 3u8.into());
+        assert_eq!(Mutez::MAX.saturating_add(v1), Mutez::MAX);
+        assert_eq!(v1.saturating_sub(v2), Mutez::ZERO);
+        assert_eq!(Mutez::MAX.saturating_mul(2), Mutez::MAX);
+    }
+
+    #[test]
+    fn test_from_tez() -> Result<()> {
+        let values: Vec<(&str, Mutez)> = vec![
+            ("1.25", 1_250_000u32.into()),
+            ("0.000001 ꜩ", 1u8.into()),
+            ("0.000001ꜩ", 1u8.into()),
+            (" 42 ", 42_000_000u32.into()),
+            ("0", Mutez::ZERO),
+            ("9223372036854.775807", Mutez::MAX),
+        ];
+        for (value, expected) in values {
+            assert_eq!(Mutez::from_tez(value)?, expected);
+        }
+
+        for value in [
+            "",
+            "ꜩ",
+            "1.",
+            ".5",
+            "-1",
+            "+1",
+            "1.0000001",
+            "1,5",
+            "1e6",
+            "1.2.3",
+        ] {
+            assert!(
+                matches!(Mutez::from_tez(value), Err(Error::InvalidTezString)),
+                "{}",
+                value
+            );
+        }
+        assert!(matches!(
+            Mutez::from_tez("9223372036854.775808"),
+            Err(Error::MutezOverflow)
+        ));
+        assert!(matches!(
+            Mutez::from_tez("99999999999999999999"),
+            Err(Error::MutezOverflow)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_tez_string() -> Result<()> {
+        let values: Vec<(Mutez, &str)> = vec![
+            (1_250_000u32.into(), "1.25"),
+            (1u8.into(), "0.000001"),
+            (42_000_000u32.into(), "42"),
+            (Mutez::ZERO, "0"),
+            (Mutez::MAX, "9223372036854.775807"),
+        ];
+        for (value, expected) in values {
+            assert_eq!(value.to_tez_string(), expected);
+            assert_eq!(Mutez::from_tez(expected)?, value);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_number_conversions() -> Result<()> {
+        let nat: Nat = Mutez::MAX.into();
+        assert_eq!(nat.to_string(), "9223372036854775807");
+        assert_eq!(Mutez::try_from(&nat)?, Mutez::MAX);
+        let nat: Nat = "9223372036854775808".try_into()?;
+        assert!(matches!(Mutez::try_from(&nat), Err(Error::MutezOverflow)));
+
+        let int: Int = Int::try_from(&Mutez::MAX)?;
+        assert_eq!(Mutez::try_from(int)?, Mutez::MAX);
+        let int: Int = "9223372036854775808".try_into()?;
+        assert!(matches!(Mutez::try_from(&int), Err(Error::MutezOverflow)));
+        let int: Int = "-1".try_into()?;
+        assert!(matches!(Mutez::try_from(int), Err(Error::MutezUnderflow)));
+
+        Ok(())
+    }
 }
@@ -1,11 +1,10 @@
-use lazy_static::lazy_static;
+use alloc::{string::{String, ToString}, vec::Vec};
 use ibig::{IBig, UBig};
 use num_traits::ToPrimitive;
 use num_integer::Integer;
-use regex::Regex;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize, de};
-use std::{
+use core::{
     fmt::{Debug, Display},
     str::FromStr,
 };
@@ -16,16 +15,15 @@ use derive_more::{
 };
 
 use crate::{
-    internal::coder::{Decoder, Encoder, IntegerBytesCoder},
+    internal::{
+        coder::{Decoder, Encoder, IntegerBytesCoder},
+        utils::is_integer_string,
+    },
     Error, Result,
 };
 
 use super::Nat;
 
-lazy_static! {
-    static ref REGEX: Regex = Regex::new(r"^-?[0-9]+$").unwrap();
-}
-
 /// An integer that can be encoded to a Zarith number
 #[derive(
     Add,
@@ -66,7 +64,7 @@ pub struct Int(IBig);
 
 #[cfg(feature = "serde")]
 impl Serialize for Int {
-    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
         where
             S: serde::Serializer {
         serializer.collect_str(&self.to_string())
@@ -75,7 +73,7 @@ impl Serialize for Int {
 
 #[cfg(feature = "serde")]
 impl<'de> Deserialize<'de> for Int {
-    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
         where
             D: serde::Deserializer<'de> {
         Self::from(String::deserialize(deserializer)?).map_err(de::Error::custom)
@@ -108,7 +106,7 @@ impl Int {
     }
 
     pub fn is_valid(value: &str) -> bool {
-        REGEX.is_match(value)
+        is_integer_string(value)
     }
 
     pub fn to_bytes(&self) -> Result<Vec<u8>> {
@@ -129,7 +127,7 @@ impl FromStr for Int {
 }
 
 impl Display for Int {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}", self.0)
     }
 }
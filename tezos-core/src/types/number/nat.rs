@@ -1,11 +1,10 @@
-use lazy_static::lazy_static;
+use alloc::{string::{String, ToString}, vec::Vec};
 use ibig::{UBig, IBig};
 use num_traits::ToPrimitive;
 use num_integer::Integer;
-use regex::Regex;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize, de};
-use std::{
+use core::{
     fmt::{Debug, Display},
     str::FromStr,
 };
@@ -19,15 +18,12 @@ use crate::{
     internal::{
         coder::{ConsumingDecoder, Decoder, Encoder, NaturalBytesCoder},
         consumable_list::ConsumableList,
+        utils::is_natural_string,
     },
     types::mutez::Mutez,
     Error, Result,
 };
 
-lazy_static! {
-    static ref REGEX: Regex = Regex::new(r"^[0-9]+$").unwrap();
-}
-
 /// An unsigned integer that can be encoded to a Zarith number
 #[derive(
     Add,
@@ -68,7 +64,7 @@ pub struct Nat(UBig);
 
 #[cfg(feature = "serde")]
 impl Serialize for Nat {
-    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
         where
             S: serde::Serializer {
         serializer.collect_str(&self.to_string())
@@ -77,7 +73,7 @@ impl Serialize for Nat {
 
 #[cfg(feature = "serde")]
 impl<'de> Deserialize<'de> for Nat {
-    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
         where
             D: serde::Deserializer<'de> {
         Self::from(String::deserialize(deserializer)?).map_err(de::Error::custom)
@@ -114,7 +110,7 @@ impl Nat {
     }
 
     pub fn is_valid(value: &str) -> bool {
-        REGEX.is_match(value)
+        is_natural_string(value)
     }
 
     pub fn to_bytes(&self) -> Result<Vec<u8>> {
@@ -161,7 +157,7 @@ impl FromStr for Nat {
 }
 
 impl Display for Nat {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}", self.0)
     }
 }
@@ -232,11 +228,17 @@ impl From<Nat> for IBig {
     }
 }
 
+impl From<Mutez> for Nat {
+    fn from(mutez: Mutez) -> Self {
+        (&mutez).into()
+    }
+}
+
 impl TryFrom<Nat> for Mutez {
     type Error = Error;
 
     fn try_from(value: Nat) -> Result<Self> {
-        value.0.try_into()
+        value.0.try_into().map_err(|_| Error::MutezOverflow)
     }
 }
 
@@ -1,14 +1,11 @@
+use alloc::{borrow::ToOwned, format, string::String, vec::Vec};
 use hex;
-use lazy_static::lazy_static;
-use regex::Regex;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::{Error, Result};
+use tezos_core::internal::utils::is_hex_string;
 
-lazy_static! {
-    static ref REGEX: Regex = Regex::new("^(0x)?([0-9a-fA-F]{2})*$").unwrap();
-}
+use crate::{Error, Result};
 
 /// A structure representing bytes.
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -32,7 +29,7 @@ impl Bytes {
 
     /// Returns true if the provided value represents a valid hex string, with or without 0x prefix.
     pub fn is_valid(value: &str) -> bool {
-        REGEX.is_match(value)
+        is_hex_string(value)
     }
 
     pub fn from_string(value: String) -> Result<Self> {
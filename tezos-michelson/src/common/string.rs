@@ -1,27 +1,24 @@
-use std::str::FromStr;
+use alloc::borrow::ToOwned;
+use core::str::FromStr;
 
-use lazy_static::lazy_static;
-use regex::Regex;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use crate::{Error, Result};
 
-lazy_static! {
-    static ref REGEX: Regex = Regex::new("^(\"|\r|\n|\t|\\b|\\\\|[^\"\\\\])*$").unwrap();
-}
-
 /// A valid tezos String.
 #[derive(Debug, PartialEq, Eq, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct String(std::string::String);
+pub struct String(alloc::string::String);
 
 impl String {
-    pub fn is_valid(value: &str) -> bool {
-        REGEX.is_match(value)
+    /// Any character sequence is a valid string, quotes, backslashes and control characters
+    /// can be escaped when the value is printed.
+    pub fn is_valid(_value: &str) -> bool {
+        true
     }
 
-    pub fn from_string(value: std::string::String) -> Result<Self> {
+    pub fn from_string(value: alloc::string::String) -> Result<Self> {
         if Self::is_valid(&value) {
             return Ok(Self(value));
         }
@@ -32,7 +29,7 @@ impl String {
         self.0.as_str()
     }
 
-    pub fn into_string(self) -> std::string::String {
+    pub fn into_string(self) -> alloc::string::String {
         self.0
     }
 }
@@ -45,10 +42,10 @@ impl FromStr for String {
     }
 }
 
-impl TryFrom<std::string::String> for String {
+impl TryFrom<alloc::string::String> for String {
     type Error = Error;
 
-    fn try_from(value: std::string::String) -> Result<Self> {
+    fn try_from(value: alloc::string::String) -> Result<Self> {
         Self::from_string(value)
     }
 }
@@ -61,7 +58,7 @@ impl TryFrom<&str> for String {
     }
 }
 
-impl From<String> for std::string::String {
+impl From<String> for alloc::string::String {
     fn from(value: String) -> Self {
         value.0
     }
@@ -1,9 +1,13 @@
-use std::result;
+use alloc::string::String;
+use core::result;
 
-use derive_more::{Display, Error as DError, From};
+#[cfg(feature = "std")]
+use derive_more::Error as DError;
+use derive_more::{Display, From};
 
 /// Errors returned by this crate.
-#[derive(DError, Display, Debug, From)]
+#[derive(Display, Debug, From)]
+#[cfg_attr(feature = "std", derive(DError))]
 pub enum Error {
     Internal {
         description: String,
@@ -30,10 +34,35 @@ pub enum Error {
     InvalidMicheline {
         description: String,
     },
+    #[from(ignore)]
+    #[display(fmt = "{} at {}:{}", description, line, column)]
+    InvalidMichelineSyntax {
+        description: String,
+        line: usize,
+        column: usize,
+    },
+    #[from(ignore)]
+    InvalidMichelsonMacro {
+        description: String,
+    },
+    #[from(ignore)]
+    #[display(fmt = "{} at {}", description, path)]
+    TypecheckFailed {
+        path: String,
+        description: String,
+    },
     InvalidPrimitiveApplication,
     InvalidMichelineLiteral,
     MichelineValueSchemaMismatch,
     BigIntConversionError,
+    InvalidSignature,
+    #[from(ignore)]
+    InvalidSignInMessage {
+        description: String,
+    },
+    SignInAddressMismatch,
+    SignInMessageExpired,
+    SignInNonceAlreadyUsed,
 }
 
 pub type Result<T> = result::Result<T, Error>;
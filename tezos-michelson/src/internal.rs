@@ -1,3 +1,6 @@
 pub mod coder;
+pub mod expander;
 pub mod normalizer;
 pub mod packer;
+pub mod signer;
+pub mod timestamp;
@@ -1,3 +1,4 @@
+use alloc::{borrow::ToOwned, vec, vec::Vec};
 use tezos_core::internal::{
     coder::{ConsumingDecoder, Decoder, Encoder, IntegerBytesCoder},
     consumable_list::{ConsumableBytes, ConsumableList},
@@ -0,0 +1,814 @@
+use alloc::{
+    boxed::Box,
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+use crate::{
+    micheline::{int, primitive_application::PrimitiveApplication, Micheline},
+    Error, Result,
+};
+
+const NO_ANNOTS: &[&str] = &[];
+const COMPARISONS: [&str; 6] = ["EQ", "NEQ", "LT", "GT", "LE", "GE"];
+
+/// Rewrites the standard Michelson macros into core instructions, the way `octez-client` does
+/// before sending a script to the node.
+pub struct MichelineMacroExpander;
+
+impl MichelineMacroExpander {
+    pub fn expand(value: Micheline) -> Result<Micheline> {
+        match value {
+            Micheline::Literal(_) => Ok(value),
+            Micheline::Sequence(value) => Ok(value
+                .into_values()
+                .into_iter()
+                .map(Self::expand)
+                .collect::<Result<Vec<_>>>()?
+                .into()),
+            Micheline::PrimitiveApplication(value) => {
+                let (prim, args, annots) = value.into();
+                let args = args.unwrap_or_default();
+                let annots = annots.unwrap_or_default();
+                match Self::expand_macro(&prim, &args, &annots)? {
+                    Some(expanded) => Self::expand(expanded),
+                    None => Ok(PrimitiveApplication::new(
+                        prim,
+                        Some(
+                            args.into_iter()
+                                .map(Self::expand)
+                                .collect::<Result<Vec<_>>>()?,
+                        ),
+                        Some(annots),
+                    )
+                    .into()),
+                }
+            }
+        }
+    }
+
+    fn expand_macro(
+        name: &str,
+        args: &[Micheline],
+        annots: &[String],
+    ) -> Result<Option<Micheline>> {
+        let expanders = [
+            Self::expand_car_n,
+            Self::expand_cdr_n,
+            Self::expand_cadr,
+            Self::expand_set_cadr,
+            Self::expand_map_cadr,
+            Self::expand_diip,
+            Self::expand_papair,
+            Self::expand_unpapair,
+            Self::expand_duup,
+            Self::expand_compare,
+            Self::expand_assert,
+            Self::expand_if_some,
+            Self::expand_if_right,
+            Self::expand_fail,
+        ];
+        for expander in expanders {
+            if let Some(expanded) = expander(name, args, annots)? {
+                return Ok(Some(expanded));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// `CAR n` => `GET (2n + 1)`
+    fn expand_car_n(
+        name: &str,
+        args: &[Micheline],
+        annots: &[String],
+    ) -> Result<Option<Micheline>> {
+        match (name, args) {
+            ("CAR", [Micheline::Literal(_)]) => {
+                let n = index_arg(name, &args[0])?;
+                Ok(Some(seq(vec![prim("GET", vec![int(2 * n + 1)], annots)])))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// `CDR n` => `GET 2n`
+    fn expand_cdr_n(
+        name: &str,
+        args: &[Micheline],
+        annots: &[String],
+    ) -> Result<Option<Micheline>> {
+        match (name, args) {
+            ("CDR", [Micheline::Literal(_)]) => {
+                let n = index_arg(name, &args[0])?;
+                Ok(Some(seq(vec![prim("GET", vec![int(2 * n)], annots)])))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// `C[AD]+R` => `{ CAR ; CDR ; ... }`
+    fn expand_cadr(name: &str, args: &[Micheline], annots: &[String]) -> Result<Option<Micheline>> {
+        let path = match name
+            .strip_prefix('C')
+            .and_then(|name| name.strip_suffix('R'))
+        {
+            Some(path) if path.len() > 1 && is_path(path) => path,
+            _ => return Ok(None),
+        };
+        expect_arity(name, args, 0)?;
+        let path_annots: Vec<String> = annots
+            .iter()
+            .filter(|annot| *annot == "@%" || *annot == "@%%")
+            .cloned()
+            .collect();
+
+        Ok(Some(seq(path
+            .chars()
+            .enumerate()
+            .map(|(index, c)| {
+                let annots = if index + 1 == path.len() {
+                    annots
+                } else {
+                    &path_annots
+                };
+                prim(if c == 'A' { "CAR" } else { "CDR" }, vec![], annots)
+            })
+            .collect())))
+    }
+
+    /// `SET_C[AD]+R` => updates the value at the path with the value below the pair on the stack.
+    fn expand_set_cadr(
+        name: &str,
+        args: &[Micheline],
+        annots: &[String],
+    ) -> Result<Option<Micheline>> {
+        let path = match name
+            .strip_prefix("SET_C")
+            .and_then(|name| name.strip_suffix('R'))
+        {
+            Some(path) if !path.is_empty() && is_path(path) => path,
+            _ => return Ok(None),
+        };
+        expect_arity(name, args, 0)?;
+        let (field_annot, annots) = single_field_annot(name, annots)?;
+
+        let (init, rest) = path.split_at(path.len() - 1);
+        let field_or_empty = field_annot.clone().unwrap_or_else(|| "%".into());
+        let mut expanded = if rest == "A" {
+            let mut values = access_check("CAR", &field_annot);
+            values.extend([
+                prim("CDR", vec![], &["@%%"]),
+                prim("SWAP", vec![], NO_ANNOTS),
+                prim("PAIR", vec![], &[field_or_empty, "%@".into()]),
+            ]);
+            values
+        } else {
+            let mut values = access_check("CDR", &field_annot);
+            values.extend([
+                prim("CAR", vec![], &["@%%"]),
+                prim("PAIR", vec![], &["%@".into(), field_or_empty]),
+            ]);
+            values
+        };
+        for c in init.chars().rev() {
+            expanded = wrap_path(c, seq(expanded));
+        }
+        add_annots_to_last_pair(&mut expanded, &annots);
+
+        Ok(Some(seq(expanded)))
+    }
+
+    /// `MAP_C[AD]+R code` => applies `code` to the value at the path.
+    fn expand_map_cadr(
+        name: &str,
+        args: &[Micheline],
+        annots: &[String],
+    ) -> Result<Option<Micheline>> {
+        let path = match name
+            .strip_prefix("MAP_C")
+            .and_then(|name| name.strip_suffix('R'))
+        {
+            Some(path) if !path.is_empty() && is_path(path) => path,
+            _ => return Ok(None),
+        };
+        expect_arity(name, args, 1)?;
+        let code = expect_sequence(name, &args[0])?;
+        let (field_annot, annots) = single_field_annot(name, annots)?;
+        let var_annots: Vec<String> = field_annot
+            .iter()
+            .map(|field| format!("@{}", &field[1..]))
+            .collect();
+
+        let (init, rest) = path.split_at(path.len() - 1);
+        let field_or_empty = field_annot.unwrap_or_else(|| "%".into());
+        let mut expanded = if rest == "A" {
+            vec![
+                prim("DUP", vec![], NO_ANNOTS),
+                prim("CDR", vec![], &["@%%"]),
+                prim(
+                    "DIP",
+                    vec![seq(vec![prim("CAR", vec![], &var_annots), code])],
+                    NO_ANNOTS,
+                ),
+                prim("SWAP", vec![], NO_ANNOTS),
+                prim("PAIR", vec![], &[field_or_empty, "%@".into()]),
+            ]
+        } else {
+            vec![
+                prim("DUP", vec![], NO_ANNOTS),
+                prim("CDR", vec![], &var_annots),
+                code,
+                prim("SWAP", vec![], NO_ANNOTS),
+                prim("CAR", vec![], &["@%%"]),
+                prim("PAIR", vec![], &["%@".into(), field_or_empty]),
+            ]
+        };
+        for c in init.chars().rev() {
+            expanded = wrap_path(c, seq(expanded));
+        }
+        add_annots_to_last_pair(&mut expanded, &annots);
+
+        Ok(Some(seq(expanded)))
+    }
+
+    /// `DI{n}P code` => `DIP n code`
+    fn expand_diip(name: &str, args: &[Micheline], annots: &[String]) -> Result<Option<Micheline>> {
+        let depth = match repeated_letter(name, 'D', 'I', 'P') {
+            Some(depth) => depth,
+            None => return Ok(None),
+        };
+        expect_arity(name, args, 1)?;
+        let code = expect_sequence(name, &args[0])?;
+
+        Ok(Some(seq(vec![prim(
+            "DIP",
+            vec![int(depth as i64), code],
+            annots,
+        )])))
+    }
+
+    /// `DU{n}P` => `DUP n`
+    fn expand_duup(name: &str, args: &[Micheline], annots: &[String]) -> Result<Option<Micheline>> {
+        let depth = match repeated_letter(name, 'D', 'U', 'P') {
+            Some(depth) => depth,
+            None => return Ok(None),
+        };
+        expect_arity(name, args, 0)?;
+
+        Ok(Some(seq(vec![prim(
+            "DUP",
+            vec![int(depth as i64)],
+            annots,
+        )])))
+    }
+
+    /// `P[PAI]+R` => `PAIR`s building a right or left comb, e.g. `PAPPAIIR` => `{ DIP { PAIR ; PAIR } ; PAIR }`
+    fn expand_papair(
+        name: &str,
+        args: &[Micheline],
+        annots: &[String],
+    ) -> Result<Option<Micheline>> {
+        if name.len() <= 4 || !name.starts_with('P') || !name.ends_with('R') {
+            return Ok(None);
+        }
+        let ast = match PairItem::parse(&name[..name.len() - 1]) {
+            Some(ast) => ast,
+            None => return Ok(None),
+        };
+        expect_arity(name, args, 0)?;
+        let (field_annots, annots): (Vec<String>, Vec<String>) = annots
+            .iter()
+            .cloned()
+            .partition(|annot| annot.starts_with('%'));
+        let mut field_annots_positions = BTreeMap::new();
+        ast.field_annots_positions(
+            0,
+            &mut field_annots.into_iter(),
+            &mut field_annots_positions,
+        );
+
+        Ok(Some(seq(ast.expand_pair(&field_annots_positions, &annots))))
+    }
+
+    /// `UNP[PAI]+R` => `UNPAIR`s destructuring a right or left comb, e.g. `UNPAPAIR` => `{ UNPAIR ; DIP { UNPAIR } }`
+    fn expand_unpapair(
+        name: &str,
+        args: &[Micheline],
+        annots: &[String],
+    ) -> Result<Option<Micheline>> {
+        if name == "UNPAIR" || !name.starts_with("UNP") || !name.ends_with('R') {
+            return Ok(None);
+        }
+        let ast = match PairItem::parse(&name[2..name.len() - 1]) {
+            Some(ast) => ast,
+            None => return Ok(None),
+        };
+        expect_no_annots(name, annots)?;
+        expect_arity(name, args, 0)?;
+
+        Ok(Some(seq(ast.expand_unpair())))
+    }
+
+    /// `CMP{op}` => `{ COMPARE ; op }`, `IF{op} bt bf` => `{ op ; IF bt bf }`
+    /// and `IFCMP{op} bt bf` => `{ COMPARE ; op ; IF bt bf }`
+    fn expand_compare(
+        name: &str,
+        args: &[Micheline],
+        annots: &[String],
+    ) -> Result<Option<Micheline>> {
+        let comparison = |prefix: &str| {
+            name.strip_prefix(prefix)
+                .filter(|comparison| COMPARISONS.contains(comparison))
+        };
+        if let Some(comparison) = comparison("CMP") {
+            expect_arity(name, args, 0)?;
+            return Ok(Some(seq(vec![
+                prim("COMPARE", vec![], NO_ANNOTS),
+                prim(comparison, vec![], annots),
+            ])));
+        }
+        if let Some(comparison) = comparison("IFCMP") {
+            let branches = expect_branches(name, args)?;
+            return Ok(Some(seq(vec![
+                prim("COMPARE", vec![], NO_ANNOTS),
+                prim(comparison, vec![], NO_ANNOTS),
+                prim("IF", branches, annots),
+            ])));
+        }
+        if let Some(comparison) = comparison("IF") {
+            let branches = expect_branches(name, args)?;
+            return Ok(Some(seq(vec![
+                prim(comparison, vec![], NO_ANNOTS),
+                prim("IF", branches, annots),
+            ])));
+        }
+
+        Ok(None)
+    }
+
+    /// `ASSERT`, `ASSERT_{op}`, `ASSERT_CMP{op}`, `ASSERT_NONE`, `ASSERT_SOME`, `ASSERT_LEFT` and `ASSERT_RIGHT`
+    fn expand_assert(
+        name: &str,
+        args: &[Micheline],
+        annots: &[String],
+    ) -> Result<Option<Micheline>> {
+        if name != "ASSERT" && !name.starts_with("ASSERT_") {
+            return Ok(None);
+        }
+        let fail = || seq(vec![prim("FAIL", vec![], NO_ANNOTS)]);
+        let rename = || {
+            if annots.is_empty() {
+                seq(vec![])
+            } else {
+                seq(vec![prim("RENAME", vec![], annots)])
+            }
+        };
+        let expanded = match name {
+            "ASSERT_SOME" => prim("IF_NONE", vec![fail(), rename()], NO_ANNOTS),
+            "ASSERT_LEFT" => prim("IF_LEFT", vec![rename(), fail()], NO_ANNOTS),
+            "ASSERT_RIGHT" => prim("IF_LEFT", vec![fail(), rename()], NO_ANNOTS),
+            _ => {
+                expect_no_annots(name, annots)?;
+                match name {
+                    "ASSERT" => prim("IF", vec![seq(vec![]), fail()], NO_ANNOTS),
+                    "ASSERT_NONE" => prim("IF_NONE", vec![seq(vec![]), fail()], NO_ANNOTS),
+                    _ => {
+                        let comparison = &name["ASSERT_".len()..];
+                        let comparison = comparison.strip_prefix("CMP").unwrap_or(comparison);
+                        if !COMPARISONS.contains(&comparison) {
+                            return Ok(None);
+                        }
+                        prim(
+                            &format!("IF{}", &name["ASSERT_".len()..]),
+                            vec![seq(vec![]), fail()],
+                            NO_ANNOTS,
+                        )
+                    }
+                }
+            }
+        };
+        expect_arity(name, args, 0)?;
+
+        Ok(Some(seq(vec![expanded])))
+    }
+
+    /// `IF_SOME bt bf` => `{ IF_NONE bf bt }`
+    fn expand_if_some(
+        name: &str,
+        args: &[Micheline],
+        annots: &[String],
+    ) -> Result<Option<Micheline>> {
+        if name != "IF_SOME" {
+            return Ok(None);
+        }
+        let branches = expect_branches(name, args)?;
+
+        Ok(Some(seq(vec![prim(
+            "IF_NONE",
+            branches.into_iter().rev().collect(),
+            annots,
+        )])))
+    }
+
+    /// `IF_RIGHT bt bf` => `{ IF_LEFT bf bt }`
+    fn expand_if_right(
+        name: &str,
+        args: &[Micheline],
+        annots: &[String],
+    ) -> Result<Option<Micheline>> {
+        if name != "IF_RIGHT" {
+            return Ok(None);
+        }
+        let branches = expect_branches(name, args)?;
+
+        Ok(Some(seq(vec![prim(
+            "IF_LEFT",
+            branches.into_iter().rev().collect(),
+            annots,
+        )])))
+    }
+
+    /// `FAIL` => `{ UNIT ; FAILWITH }`
+    fn expand_fail(name: &str, args: &[Micheline], annots: &[String]) -> Result<Option<Micheline>> {
+        if name != "FAIL" {
+            return Ok(None);
+        }
+        expect_no_annots(name, annots)?;
+        expect_arity(name, args, 0)?;
+
+        Ok(Some(seq(vec![
+            prim("UNIT", vec![], NO_ANNOTS),
+            prim("FAILWITH", vec![], NO_ANNOTS),
+        ])))
+    }
+}
+
+/// The structure of a `PAIR`/`UNPAIR` macro, `A` and `I` being the left and right leaves of a pair
+/// and `P` a nested pair, identified by its position in the macro name.
+enum PairItem {
+    A,
+    I,
+    P(usize, Box<PairItem>, Box<PairItem>),
+}
+
+impl PairItem {
+    /// Parses the letters of the macro, without the final `R`.
+    fn parse(letters: &str) -> Option<Self> {
+        let letters = letters.as_bytes();
+        let (next, ast) = Self::parse_item(letters, 0, None)?;
+        if next == letters.len() {
+            Some(ast)
+        } else {
+            None
+        }
+    }
+
+    fn parse_item(letters: &[u8], index: usize, left: Option<bool>) -> Option<(usize, Self)> {
+        match letters.get(index)? {
+            b'P' => {
+                let (next, l) = Self::parse_item(letters, index + 1, Some(true))?;
+                let (next, r) = Self::parse_item(letters, next, Some(false))?;
+                Some((next, Self::P(index, Box::new(l), Box::new(r))))
+            }
+            b'A' if left == Some(true) => Some((index + 1, Self::A)),
+            b'I' if left != Some(true) => Some((index + 1, Self::I)),
+            _ => None,
+        }
+    }
+
+    /// Assigns the field annotations, in order, to the pairs the leaves belong to.
+    fn field_annots_positions<I: Iterator<Item = String>>(
+        &self,
+        parent: usize,
+        annots: &mut I,
+        positions: &mut BTreeMap<usize, (Option<String>, Option<String>)>,
+    ) {
+        match self {
+            Self::P(index, left, right) => {
+                left.field_annots_positions(*index, annots, positions);
+                right.field_annots_positions(*index, annots, positions);
+            }
+            Self::A => {
+                if let Some(annot) = annots.next() {
+                    positions.entry(parent).or_default().0 = Some(annot);
+                }
+            }
+            Self::I => {
+                if let Some(annot) = annots.next() {
+                    positions.entry(parent).or_default().1 = Some(annot);
+                }
+            }
+        }
+    }
+
+    fn expand_pair(
+        &self,
+        field_annots_positions: &BTreeMap<usize, (Option<String>, Option<String>)>,
+        annots: &[String],
+    ) -> Vec<Micheline> {
+        let (index, left, right) = match self {
+            Self::P(index, left, right) => (*index, left, right),
+            _ => return vec![],
+        };
+        let l = left.expand_pair(field_annots_positions, &[]);
+        let r = right.expand_pair(field_annots_positions, &[]);
+        let mut expanded = match (l.is_empty(), r.is_empty()) {
+            (true, true) => vec![],
+            (false, true) => l,
+            (true, false) => vec![prim("DIP", vec![seq(r)], NO_ANNOTS)],
+            (false, false) => vec![seq(l), prim("DIP", vec![seq(r)], NO_ANNOTS)],
+        };
+        let mut pair_annots = annots.to_vec();
+        match field_annots_positions.get(&index) {
+            Some((Some(car), Some(cdr))) => pair_annots.extend([car.clone(), cdr.clone()]),
+            Some((Some(car), None)) => pair_annots.push(car.clone()),
+            Some((None, Some(cdr))) => pair_annots.extend(["%".to_string(), cdr.clone()]),
+            _ => {}
+        }
+        expanded.push(prim("PAIR", vec![], &pair_annots));
+        expanded
+    }
+
+    fn expand_unpair(&self) -> Vec<Micheline> {
+        let (left, right) = match self {
+            Self::P(_, left, right) => (left, right),
+            _ => return vec![],
+        };
+        let mut expanded = vec![prim("UNPAIR", vec![], NO_ANNOTS)];
+        if let Self::P(..) = right.as_ref() {
+            expanded.push(prim("DIP", vec![seq(right.expand_unpair())], NO_ANNOTS));
+        }
+        expanded.extend(left.expand_unpair());
+        expanded
+    }
+}
+
+fn prim<S: AsRef<str>>(name: &str, args: Vec<Micheline>, annots: &[S]) -> Micheline {
+    PrimitiveApplication::new(
+        name.into(),
+        Some(args),
+        Some(annots.iter().map(|annot| annot.as_ref().into()).collect()),
+    )
+    .into()
+}
+
+fn seq(values: Vec<Micheline>) -> Micheline {
+    values.into()
+}
+
+fn is_path(path: &str) -> bool {
+    path.chars().all(|c| c == 'A' || c == 'D')
+}
+
+/// Returns `n` if the name is `first`, followed by `n > 1` times `repeated`, followed by `last`.
+fn repeated_letter(name: &str, first: char, repeated: char, last: char) -> Option<usize> {
+    let letters = name.strip_prefix(first)?.strip_suffix(last)?;
+    if letters.len() > 1 && letters.chars().all(|c| c == repeated) {
+        Some(letters.len())
+    } else {
+        None
+    }
+}
+
+/// `DUP ; DIP { C[AD]R @%% ; expanded } ; ...` for the outer steps of the `SET_` and `MAP_` macros.
+fn wrap_path(step: char, expanded: Micheline) -> Vec<Micheline> {
+    let (access, other) = if step == 'A' {
+        ("CAR", "CDR")
+    } else {
+        ("CDR", "CAR")
+    };
+    let mut values = vec![
+        prim("DUP", vec![], NO_ANNOTS),
+        prim(
+            "DIP",
+            vec![seq(vec![prim(access, vec![], &["@%%"]), expanded])],
+            NO_ANNOTS,
+        ),
+        prim(other, vec![], &["@%%"]),
+    ];
+    if step == 'A' {
+        values.push(prim("SWAP", vec![], NO_ANNOTS));
+    }
+    values.push(prim("PAIR", vec![], &["%@", "%@"]));
+    values
+}
+
+/// Checks the field of the updated value, e.g. `DUP ; CAR %field ; DROP` for `SET_CAR %field`.
+fn access_check(access: &str, field_annot: &Option<String>) -> Vec<Micheline> {
+    match field_annot {
+        Some(field) => vec![
+            prim("DUP", vec![], NO_ANNOTS),
+            prim(access, vec![], &[field]),
+            prim("DROP", vec![], NO_ANNOTS),
+        ],
+        None => vec![],
+    }
+}
+
+fn add_annots_to_last_pair(expanded: &mut [Micheline], annots: &[String]) {
+    if annots.is_empty() {
+        return;
+    }
+    if let Some(Micheline::PrimitiveApplication(pair)) = expanded.last_mut() {
+        let mut pair_annots = pair.annots().clone().unwrap_or_default();
+        pair_annots.extend(annots.iter().cloned());
+        *pair = pair.clone().with_annots(pair_annots);
+    }
+}
+
+fn single_field_annot(name: &str, annots: &[String]) -> Result<(Option<String>, Vec<String>)> {
+    let (field_annots, annots): (Vec<String>, Vec<String>) = annots
+        .iter()
+        .cloned()
+        .partition(|annot| annot.starts_with('%'));
+    if field_annots.len() > 1 {
+        return Err(invalid_macro(name, "expects at most one field annotation"));
+    }
+
+    Ok((field_annots.into_iter().next(), annots))
+}
+
+fn index_arg(name: &str, arg: &Micheline) -> Result<i64> {
+    let n: Option<i64> = match arg {
+        Micheline::Literal(crate::micheline::literals::Literal::Int(value)) => {
+            value.to_string().parse().ok()
+        }
+        _ => None,
+    };
+    n.filter(|n| *n >= 0 && *n < i64::MAX / 2)
+        .ok_or_else(|| invalid_macro(name, "expects a natural number argument"))
+}
+
+fn expect_arity(name: &str, args: &[Micheline], arity: usize) -> Result<()> {
+    if args.len() != arity {
+        return Err(invalid_macro(
+            name,
+            &format!("expects {} argument(s), got {}", arity, args.len()),
+        ));
+    }
+    Ok(())
+}
+
+fn expect_sequence(name: &str, arg: &Micheline) -> Result<Micheline> {
+    if !arg.is_sequence() {
+        return Err(invalid_macro(name, "expects a sequence argument"));
+    }
+    Ok(arg.clone())
+}
+
+fn expect_branches(name: &str, args: &[Micheline]) -> Result<Vec<Micheline>> {
+    expect_arity(name, args, 2)?;
+    args.iter().map(|arg| expect_sequence(name, arg)).collect()
+}
+
+fn expect_no_annots(name: &str, annots: &[String]) -> Result<()> {
+    if !annots.is_empty() {
+        return Err(invalid_macro(name, "does not accept annotations"));
+    }
+    Ok(())
+}
+
+fn invalid_macro(name: &str, description: &str) -> Error {
+    Error::InvalidMichelsonMacro {
+        description: format!("{} {}", name, description),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn expand(source: &str) -> Result<String> {
+        Ok(MichelineMacroExpander::expand(Micheline::parse(source)?)?.to_compact_string())
+    }
+
+    #[test]
+    fn test_expand() -> Result<()> {
+        let values = [
+            ("FAIL", "{ UNIT ; FAILWITH }"),
+            ("CMPLT @lt", "{ COMPARE ; LT @lt }"),
+            ("IFEQ { DROP } {}", "{ EQ ; IF { DROP } {} }"),
+            ("IFCMPNEQ {} { FAIL }", "{ COMPARE ; NEQ ; IF {} { { UNIT ; FAILWITH } } }"),
+            ("ASSERT", "{ IF {} { { UNIT ; FAILWITH } } }"),
+            ("ASSERT_NONE", "{ IF_NONE {} { { UNIT ; FAILWITH } } }"),
+            ("ASSERT_SOME", "{ IF_NONE { { UNIT ; FAILWITH } } {} }"),
+            ("ASSERT_SOME @x", "{ IF_NONE { { UNIT ; FAILWITH } } { RENAME @x } }"),
+            ("ASSERT_LEFT", "{ IF_LEFT {} { { UNIT ; FAILWITH } } }"),
+            ("ASSERT_RIGHT", "{ IF_LEFT { { UNIT ; FAILWITH } } {} }"),
+            ("ASSERT_GT", "{ { GT ; IF {} { { UNIT ; FAILWITH } } } }"),
+            (
+                "ASSERT_CMPGE",
+                "{ { COMPARE ; GE ; IF {} { { UNIT ; FAILWITH } } } }",
+            ),
+            ("IF_SOME { DROP } { UNIT }", "{ IF_NONE { UNIT } { DROP } }"),
+            ("IF_RIGHT { DROP } { UNIT }", "{ IF_LEFT { UNIT } { DROP } }"),
+            ("DUUP", "{ DUP 2 }"),
+            ("DUUUP @x", "{ DUP @x 3 }"),
+            ("DIIP { DROP }", "{ DIP 2 { DROP } }"),
+            ("DIIIP { FAIL }", "{ DIP 3 { { UNIT ; FAILWITH } } }"),
+            ("CADR", "{ CAR ; CDR }"),
+            ("CDDAR @x", "{ CDR ; CDR ; CAR @x }"),
+            ("CAR 2", "{ GET 5 }"),
+            ("CDR 2", "{ GET 4 }"),
+            ("PAPAIR", "{ DIP { PAIR } ; PAIR }"),
+            ("PPAIIR", "{ PAIR ; PAIR }"),
+            ("PAPPAIIR", "{ DIP { PAIR ; PAIR } ; PAIR }"),
+            ("PPAIPAIR", "{ { PAIR } ; DIP { PAIR } ; PAIR }"),
+            ("PAPAIR @p %a %b %c", "{ DIP { PAIR %b %c } ; PAIR @p %a }"),
+            ("UNPAPAIR", "{ UNPAIR ; DIP { UNPAIR } }"),
+            ("UNPPAIIR", "{ UNPAIR ; UNPAIR }"),
+            ("UNPPAIPAIR", "{ UNPAIR ; DIP { UNPAIR } ; UNPAIR }"),
+            ("SET_CAR", "{ CDR @%% ; SWAP ; PAIR % %@ }"),
+            ("SET_CDR %f", "{ DUP ; CDR %f ; DROP ; CAR @%% ; PAIR %@ %f }"),
+            (
+                "SET_CADR",
+                "{ DUP ; DIP { CAR @%% ; { CAR @%% ; PAIR %@ % } } ; CDR @%% ; SWAP ; PAIR %@ %@ }",
+            ),
+            (
+                "MAP_CAR { DROP }",
+                "{ DUP ; CDR @%% ; DIP { CAR ; { DROP } } ; SWAP ; PAIR % %@ }",
+            ),
+            (
+                "MAP_CDR %f { DROP }",
+                "{ DUP ; CDR @f ; { DROP } ; SWAP ; CAR @%% ; PAIR %@ %f }",
+            ),
+            (
+                "MAP_CDAR { DROP }",
+                "{ DUP ; DIP { CDR @%% ; { DUP ; CDR @%% ; DIP { CAR ; { DROP } } ; SWAP ; PAIR % %@ } } ; CAR @%% ; PAIR %@ %@ }",
+            ),
+        ];
+        for (source, expected) in values {
+            assert_eq!(expand(source)?, expected, "{}", source);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_expand_nested() -> Result<()> {
+        assert_eq!(
+            expand("{ DUP ; CDR ; IF_SOME { CMPEQ ; ASSERT } { FAIL } ; UNPAIR ; PAIR ; DIP { CADR } }")?,
+            "{ DUP ; CDR ; { IF_NONE { { UNIT ; FAILWITH } } { { COMPARE ; EQ } ; { IF {} { { UNIT ; FAILWITH } } } } } ; UNPAIR ; PAIR ; DIP { { CAR ; CDR } } }"
+        );
+        assert_eq!(
+            expand("pair (nat %a) (option string)")?,
+            "pair (nat %a) (option string)"
+        );
+
+        let code = MichelineMacroExpander::expand(Micheline::parse(
+            "{ UNPAPAIR ; DIIP { DROP } ; IFCMPEQ { SET_CADR } { FAIL } ; DUUP ; CAR 1 }",
+        )?)?;
+        assert!(crate::michelson::Michelson::try_from(code).is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn test_not_macros() -> Result<()> {
+        for source in [
+            "CAR",
+            "CDR",
+            "DIP { DROP }",
+            "DUP",
+            "PAIR",
+            "UNPAIR",
+            "CAR @x",
+            "IF_NONE {} {}",
+            "ASSERTX",
+            "CMPX",
+            "PAIRR",
+            "PAAIR",
+            "DIIPX",
+        ] {
+            assert_eq!(expand(source)?, source);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_macros() {
+        for source in [
+            "FAIL @x",
+            "FAIL 1",
+            "ASSERT_EQ @x",
+            "CADR 1",
+            "DIIP",
+            "DIIP DROP",
+            "IFEQ {}",
+            "IF_SOME {} DROP",
+            "PAPAIR {}",
+            "UNPAPAIR @x",
+            "SET_CAR %a %b",
+            "MAP_CAR",
+            "CAR (-1)",
+        ] {
+            assert!(
+                matches!(expand(source), Err(Error::InvalidMichelsonMacro { .. })),
+                "{}",
+                source
+            );
+        }
+    }
+}
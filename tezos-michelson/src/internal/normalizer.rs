@@ -1,3 +1,4 @@
+use alloc::{string::String, vec, vec::Vec};
 use tezos_core::internal::normalizer::Normalizer;
 
 use crate::{
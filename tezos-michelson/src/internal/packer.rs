@@ -1,4 +1,4 @@
-use chrono::{DateTime, NaiveDateTime, SecondsFormat, Utc};
+use alloc::{format, vec, vec::Vec};
 use num_traits::ToPrimitive;
 use tezos_core::{
     internal::types::BytesTag,
@@ -6,6 +6,7 @@ use tezos_core::{
 };
 
 use crate::{
+    internal::timestamp,
     micheline::{
         literals::{Bytes, Literal},
         primitive_application,
@@ -22,8 +23,8 @@ use crate::{
 pub trait Packer<T> {
     type Error;
 
-    fn pack(value: T, schema: Option<&Micheline>) -> std::result::Result<Vec<u8>, Error>;
-    fn unpack(bytes: &[u8], schema: Option<&Micheline>) -> std::result::Result<T, Error>;
+    fn pack(value: T, schema: Option<&Micheline>) -> core::result::Result<Vec<u8>, Error>;
+    fn unpack(bytes: &[u8], schema: Option<&Micheline>) -> core::result::Result<T, Error>;
 }
 
 pub struct MichelinePacker;
@@ -738,9 +739,9 @@ impl MichelinePacker {
         match value {
             Literal::Int(_) => Ok(value.into()),
             Literal::String(value) => {
-                let date_time = DateTime::parse_from_rfc3339(value.to_str())
-                    .map_err(|_error| Error::MichelineValueSchemaMismatch)?;
-                Ok(Literal::Int(date_time.timestamp_millis().into()).into())
+                let timestamp = timestamp::parse_rfc3339(value.to_str())
+                    .ok_or(Error::MichelineValueSchemaMismatch)?;
+                Ok(Literal::Int(timestamp.into()).into())
             }
             _ => Err(Error::MichelineValueSchemaMismatch),
         }
@@ -750,17 +751,9 @@ impl MichelinePacker {
         match value {
             Literal::Int(value) => {
                 let value = value.to_i64().ok_or(Error::MichelineValueSchemaMismatch)?;
-                let ts_secs = value / 1000;
-                let ts_ns = (value % 1000) * 1_000_000;
-                let dt = DateTime::<Utc>::from_utc(
-                    NaiveDateTime::from_timestamp_opt(ts_secs, ts_ns as u32)
-                        .expect("out-of-range number of seconds and/or invalid nanosecond"),
-                    Utc,
-                );
-                Ok(
-                    Literal::String(dt.to_rfc3339_opts(SecondsFormat::Millis, true).try_into()?)
-                        .into(),
-                )
+                let date_time =
+                    timestamp::format_rfc3339(value).ok_or(Error::MichelineValueSchemaMismatch)?;
+                Ok(Literal::String(date_time.try_into()?).into())
             }
             Literal::String(_) => Ok(value.into()),
             _ => Err(Error::MichelineValueSchemaMismatch),
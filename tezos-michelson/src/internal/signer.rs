@@ -0,0 +1,146 @@
+use alloc::vec::Vec;
+use tezos_core::{
+    internal::crypto::Crypto,
+    types::encoded::{Encoded, PublicKey, SecretKey, Signature},
+};
+use zeroize::Zeroizing;
+
+use crate::Result;
+
+/// Signs and verifies packed Micheline expressions. The packed bytes already start with the `0x05` watermark.
+pub struct ExpressionSigner {
+    crypto: Crypto,
+}
+
+impl ExpressionSigner {
+    const MESSAGE_HASH_SIZE: usize = 32;
+
+    pub fn new(crypto: Crypto) -> Self {
+        Self { crypto }
+    }
+
+    pub fn sign(&self, packed: &[u8], key: &SecretKey) -> Result<Signature> {
+        let secret = Zeroizing::new(key.to_bytes()?);
+        let signature = match key {
+            SecretKey::Ed25519(_) => self.crypto.sign_ed25519(&self.hash(packed)?, &secret)?,
+            SecretKey::Secp256K1(_) => self.crypto.sign_secp256_k1(&self.hash(packed)?, &secret)?,
+            SecretKey::P256(_) => self.crypto.sign_p256(&self.hash(packed)?, &secret)?,
+            SecretKey::Bls12381(_) => self.crypto.sign_bls12_381(packed, &secret)?,
+        };
+
+        Ok(match key {
+            SecretKey::Ed25519(_) => Signature::Ed25519((&signature).try_into()?),
+            SecretKey::Secp256K1(_) => Signature::Secp256K1((&signature).try_into()?),
+            SecretKey::P256(_) => Signature::P256((&signature).try_into()?),
+            SecretKey::Bls12381(_) => Signature::Bls12381((&signature).try_into()?),
+        })
+    }
+
+    pub fn verify(&self, packed: &[u8], signature: &Signature, key: &PublicKey) -> Result<bool> {
+        let signature = signature.to_bytes()?;
+
+        Ok(match key {
+            PublicKey::Ed25519(key) => {
+                self.crypto
+                    .verify_ed25519(&self.hash(packed)?, &signature, &key.to_bytes()?)?
+            }
+            PublicKey::Secp256K1(key) => {
+                self.crypto
+                    .verify_secp256_k1(&self.hash(packed)?, &signature, &key.to_bytes()?)?
+            }
+            PublicKey::P256(key) => {
+                self.crypto
+                    .verify_p256(&self.hash(packed)?, &signature, &key.to_bytes()?)?
+            }
+            PublicKey::Bls12381(key) => {
+                self.crypto
+                    .verify_bls12_381(packed, &signature, &key.to_bytes()?)?
+            }
+        })
+    }
+
+    /// Ed25519, secp256k1 and p256 keys sign the blake2b digest of the packed expression,
+    /// BLS keys sign the packed expression directly.
+    fn hash(&self, packed: &[u8]) -> Result<Vec<u8>> {
+        Ok(self.crypto.blake2b(packed, Self::MESSAGE_HASH_SIZE)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::micheline::{primitive_application, try_string, Micheline};
+
+    #[cfg(feature = "ed25519")]
+    fn message() -> Micheline {
+        try_string("Tezos Signed Message: example.xyz 2021-01-01T00:00:00Z Hello").unwrap()
+    }
+
+    #[cfg(feature = "ed25519")]
+    #[test]
+    fn test_ed25519_sign() -> Result<()> {
+        let key: SecretKey = "edskRv7VyXGVZb8EsrR7D9XKUbbAQNQGtALP6QeB16ZCD7SmmJpzyeneJVg3Mq56YLbxRA1kSdAXiswwPiaVfR3NHGMCXCziuZ".try_into()?;
+        let signature = message().sign(None, &key)?;
+
+        assert_eq!(
+            signature.value(),
+            "edsigtmo6q8BrJSBhFeTcqwq4h47URASkVkbYKv28fJwcUYV4uoNdMhCnxmx3qViXHyTxjLVYRWGvM6wd8quyPSSGRGDdBMvsLn"
+        );
+
+        Ok(())
+    }
+
+    #[cfg(feature = "ed25519")]
+    #[test]
+    fn test_ed25519_verify() -> Result<()> {
+        let key: PublicKey = "edpkttZKC51wemRqL2QxwpMnEKxWnbd35pq47Y6xsCHp5M1f7LN8NP".try_into()?;
+        let signature: Signature = "edsigtmo6q8BrJSBhFeTcqwq4h47URASkVkbYKv28fJwcUYV4uoNdMhCnxmx3qViXHyTxjLVYRWGvM6wd8quyPSSGRGDdBMvsLn".try_into()?;
+
+        assert!(message().verify(None, &signature, &key)?);
+        let other: Micheline =
+            try_string("Tezos Signed Message: example.xyz 2021-01-01T00:00:00Z Bye")?;
+        assert!(!other.verify(None, &signature, &key)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sign_and_verify_with_schema() -> Result<()> {
+        let value: Micheline = try_string("tz1RFxSHjxxyaaXyAvJG3uRQeJUCCm4jk2LX")?;
+        let schema: Micheline = primitive_application("address").into();
+
+        for (secret_key, public_key) in key_pairs() {
+            let signature = value.sign(Some(&schema), &secret_key)?;
+
+            assert!(value.verify(Some(&schema), &signature, &public_key)?);
+            assert!(!value.verify(None, &signature, &public_key)?);
+        }
+
+        Ok(())
+    }
+
+    fn key_pairs() -> Vec<(SecretKey, PublicKey)> {
+        vec![
+            #[cfg(feature = "ed25519")]
+            (
+                "edskRv7VyXGVZb8EsrR7D9XKUbbAQNQGtALP6QeB16ZCD7SmmJpzyeneJVg3Mq56YLbxRA1kSdAXiswwPiaVfR3NHGMCXCziuZ".try_into().unwrap(),
+                "edpkttZKC51wemRqL2QxwpMnEKxWnbd35pq47Y6xsCHp5M1f7LN8NP".try_into().unwrap(),
+            ),
+            #[cfg(feature = "secp256_k1")]
+            (
+                "spsk1SsrWCpufeXkNruaG9L3Mf9dRyd4D8HsM8ftqseN1fne3x9LNk".try_into().unwrap(),
+                "sppk7ZpH5qAjTDZn1o1TW7z2QbQZUcMHRn2wtV4rRfz15eLQrvPkt6k".try_into().unwrap(),
+            ),
+            #[cfg(feature = "p256")]
+            (
+                "p2sk2rVhhi5EfEdhJ3wQGsdc4ZEN3i7Z8f73Bn1xp1JKjETNyJ85oW".try_into().unwrap(),
+                "p2pk67fo5oy6byruqDtzVixbM7L3cVBDRMcFhA33XD5w2HF4fRXDJhw".try_into().unwrap(),
+            ),
+            #[cfg(feature = "bls")]
+            (
+                "BLsk1WMaoyRDXHuLDViHoExYpeCE52AH9y3n2YZUrF1yYPqgkMxLQB".try_into().unwrap(),
+                "BLpk1zKCZWUm6fXHxeLZY8jyv8m4cNKvYtM1yVC6uE9fViEGwU5CMRF8kL1DUKtdaqYmX71FJPCe".try_into().unwrap(),
+            ),
+        ]
+    }
+}
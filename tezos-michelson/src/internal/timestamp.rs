@@ -0,0 +1,210 @@
+//! RFC 3339 conversions of the Michelson `timestamp` values, which are represented as milliseconds since
+//! the Unix epoch when packed.
+
+use alloc::{format, string::String};
+
+const MILLIS_PER_SECOND: i64 = 1000;
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Parses an RFC 3339 date-time, e.g. `2022-01-20T10:43:57.103Z` or `2022-01-20T11:43:57+01:00`,
+/// into milliseconds since the Unix epoch. Fractional seconds are truncated to milliseconds.
+pub fn parse_rfc3339(value: &str) -> Option<i64> {
+    let bytes = value.as_bytes();
+    if bytes.len() < 20
+        || bytes[4] != b'-'
+        || bytes[7] != b'-'
+        || !matches!(bytes[10], b'T' | b't' | b' ')
+        || bytes[13] != b':'
+        || bytes[16] != b':'
+    {
+        return None;
+    }
+
+    let year = digits(bytes, 0, 4)?;
+    let month = digits(bytes, 5, 2)?;
+    let day = digits(bytes, 8, 2)?;
+    let hour = digits(bytes, 11, 2)?;
+    let minute = digits(bytes, 14, 2)?;
+    let second = digits(bytes, 17, 2)?;
+    if !(1..=12).contains(&month)
+        || day < 1
+        || day > days_in_month(year, month)
+        || hour > 23
+        || minute > 59
+        || second > 59
+    {
+        return None;
+    }
+
+    let mut index = 19;
+    let mut millis = 0;
+    if bytes[index] == b'.' {
+        let start = index + 1;
+        index = start;
+        while index < bytes.len() && bytes[index].is_ascii_digit() {
+            index += 1;
+        }
+        if index == start {
+            return None;
+        }
+        let fraction = &value[start..index.min(start + 3)];
+        millis = fraction.parse::<i64>().ok()? * 10_i64.pow(3 - fraction.len() as u32);
+    }
+
+    let offset = match &bytes[index..] {
+        [b'Z' | b'z'] => 0,
+        [sign @ (b'+' | b'-'), _, _, b':', _, _] => {
+            let offset_hours = digits(bytes, index + 1, 2)?;
+            let offset_minutes = digits(bytes, index + 4, 2)?;
+            if offset_hours > 23 || offset_minutes > 59 {
+                return None;
+            }
+            let offset = (offset_hours * 60 + offset_minutes) * 60;
+            if *sign == b'-' {
+                -offset
+            } else {
+                offset
+            }
+        }
+        _ => return None,
+    };
+
+    let seconds =
+        days_from_civil(year, month, day) * SECONDS_PER_DAY + hour * 3600 + minute * 60 + second
+            - offset;
+
+    Some(seconds * MILLIS_PER_SECOND + millis)
+}
+
+/// Formats milliseconds since the Unix epoch as an RFC 3339 UTC date-time with millisecond precision,
+/// e.g. `2022-01-20T10:43:57.103Z`. Returns `None` if the year does not fit in 4 digits.
+pub fn format_rfc3339(value: i64) -> Option<String> {
+    let millis = value.rem_euclid(MILLIS_PER_SECOND);
+    let seconds = value.div_euclid(MILLIS_PER_SECOND);
+    let days = seconds.div_euclid(SECONDS_PER_DAY);
+    let seconds_of_day = seconds.rem_euclid(SECONDS_PER_DAY);
+    let (year, month, day) = civil_from_days(days);
+    if !(0..=9999).contains(&year) {
+        return None;
+    }
+
+    Some(format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        seconds_of_day / 3600,
+        seconds_of_day % 3600 / 60,
+        seconds_of_day % 60,
+        millis
+    ))
+}
+
+fn digits(bytes: &[u8], start: usize, length: usize) -> Option<i64> {
+    bytes
+        .get(start..start + length)?
+        .iter()
+        .try_fold(0, |value, byte| {
+            byte.is_ascii_digit()
+                .then(|| value * 10 + (byte - b'0') as i64)
+        })
+}
+
+fn is_leap_year(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Number of days since 1970-01-01 of the given proleptic Gregorian date
+/// (see [chrono-Compatible Low-Level Date Algorithms](http://howardhinnant.github.io/date_algorithms.html)).
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Reverts [days_from_civil].
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400;
+
+    (if month <= 2 { year + 1 } else { year }, month, day)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_rfc3339() {
+        let values = [
+            ("1970-01-01T00:00:00Z", 0),
+            ("2022-01-20T10:43:57.103Z", 1642675437103),
+            ("2022-01-20T10:43:57.1034567Z", 1642675437103),
+            ("2022-01-20T10:43:57.1Z", 1642675437100),
+            ("2022-01-20T11:43:57.103+01:00", 1642675437103),
+            ("2022-01-20t09:13:57.103-01:30", 1642675437103),
+            ("2000-02-29T00:00:00Z", 951782400000),
+            ("1969-12-31T23:59:59.999Z", -1),
+        ];
+        for (value, expected) in values {
+            assert_eq!(parse_rfc3339(value), Some(expected), "{}", value);
+        }
+
+        let invalid = [
+            "",
+            "2022-01-20",
+            "2022-01-20T10:43:57",
+            "2022-01-20T10:43:57.Z",
+            "2022-13-20T10:43:57Z",
+            "2022-02-29T10:43:57Z",
+            "2022-01-20T24:43:57Z",
+            "2022-01-20T10:43:57+1:00",
+            "2022-01-20T10:43:57Zgarbage",
+            "20a2-01-20T10:43:57Z",
+        ];
+        for value in invalid {
+            assert_eq!(parse_rfc3339(value), None, "{}", value);
+        }
+    }
+
+    #[test]
+    fn test_format_rfc3339() {
+        let values = [
+            (0, "1970-01-01T00:00:00.000Z"),
+            (1642675437103, "2022-01-20T10:43:57.103Z"),
+            (951782400000, "2000-02-29T00:00:00.000Z"),
+            (-1, "1969-12-31T23:59:59.999Z"),
+        ];
+        for (value, expected) in values {
+            assert_eq!(format_rfc3339(value).as_deref(), Some(expected));
+            assert_eq!(parse_rfc3339(expected), Some(value));
+        }
+
+        assert_eq!(format_rfc3339(i64::MAX), None);
+    }
+}
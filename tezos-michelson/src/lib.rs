@@ -273,6 +273,29 @@
 //! let unpacked_bytes = Micheline::from_bytes(&packed_bytes).expect("valid conversion to Micheline"); // = { "string": "tz1ZBuF2dQ7E1b32bK3g1Qsah4pvWqpM4b4A" }
 //! ```
 //!
+//! ## Sign and Verify
+//!
+//! Packed Micheline expressions can be signed, e.g. to authenticate a user or to produce a permit checked on-chain with `CHECK_SIGNATURE`.
+//! The signed message is the packed expression, including its `0x05` watermark (requires one of the crypto features):
+//!
+//! ```rust
+//! use tezos_michelson::micheline::{Micheline, try_string};
+//! use tezos_core::types::encoded::{PublicKey, SecretKey};
+//!
+//! let message: Micheline = try_string("Tezos Signed Message: example.xyz 2021-01-01T00:00:00Z Hello").expect("valid conversion to Micheline");
+//! let secret_key: SecretKey = "edskRv7VyXGVZb8EsrR7D9XKUbbAQNQGtALP6QeB16ZCD7SmmJpzyeneJVg3Mq56YLbxRA1kSdAXiswwPiaVfR3NHGMCXCziuZ".try_into().unwrap();
+//! let public_key: PublicKey = "edpkttZKC51wemRqL2QxwpMnEKxWnbd35pq47Y6xsCHp5M1f7LN8NP".try_into().unwrap();
+//!
+//! # #[cfg(feature = "ed25519")]
+//! let signature = message.sign(None, &secret_key).expect("valid signature"); // = edsigtmo6q8BrJSBhFeTcqwq4h47URASkVkbYKv28fJwcUYV4uoNdMhCnxmx3qViXHyTxjLVYRWGvM6wd8quyPSSGRGDdBMvsLn
+//! # #[cfg(feature = "ed25519")]
+//! let is_valid = message.verify(None, &signature, &public_key).expect("valid verification"); // = true
+//! ```
+//!
+//! The [siwt](crate::siwt) module builds on it to implement the Sign-In-With-Tezos flow: a [SignInMessage](crate::siwt::SignInMessage)
+//! binds an address to a domain, a one-time nonce and a validity window, and its signature is verified server-side against
+//! the public key of the address.
+//!
 //! # `Michelson` (type)
 //!
 //! The [Michelson](crate::michelson::Michelson) type is the representation of the Smart Contract language. It provides a much
@@ -505,11 +528,18 @@
 //! let michelson: Michelson = micheline.try_into().expect("valid conversion to Michelson");
 //! ```
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
 mod common;
 mod error;
 mod internal;
 pub mod micheline;
 pub mod michelson;
+#[cfg(feature = "std")]
+pub mod siwt;
+pub mod typechecker;
 
 pub use error::{Error, Result};
 pub use internal::packer::MichelinePacker;
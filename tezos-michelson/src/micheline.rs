@@ -1,12 +1,22 @@
 pub mod literals;
+mod parser;
+mod printer;
 pub mod primitive_application;
 pub mod sequence;
+#[cfg(feature = "timelock")]
+mod timelock;
 mod utils;
+use alloc::vec::Vec;
+use core::{fmt, str::FromStr};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
-use tezos_core::internal::{
-    coder::{Decoder, Encoder},
-    normalizer::Normalizer,
+use tezos_core::{
+    internal::{
+        coder::{Decoder, Encoder},
+        normalizer::Normalizer,
+    },
+    types::encoded::{PublicKey, SecretKey, Signature},
+    Tezos,
 };
 
 pub use self::utils::{
@@ -16,8 +26,10 @@ use self::{literals::Literal, primitive_application::PrimitiveApplication, seque
 use crate::{
     internal::{
         coder::micheline_bytes_coder::MichelineBytesCoder,
+        expander::MichelineMacroExpander,
         normalizer::MichelineNormalizer,
         packer::{MichelinePacker, Packer},
+        signer::ExpressionSigner,
     },
     michelson::Michelson,
     Error, Result,
@@ -73,6 +85,68 @@ impl Micheline {
         MichelinePacker::unpack(bytes, schema)
     }
 
+    /// Packs the [Micheline] value using the provided schema and signs it with the given key, using the crypto
+    /// providers configured in the given [Tezos] instance.
+    ///
+    /// The signed message is the packed value, prefixed with the `0x05` watermark, which is what wallets
+    /// and smart contracts (`CHECK_SIGNATURE`) expect when working with signed Micheline payloads.
+    pub fn sign_with(
+        &self,
+        schema: Option<&Micheline>,
+        key: &SecretKey,
+        tezos: &Tezos,
+    ) -> Result<Signature> {
+        let packed = self.clone().pack(schema)?;
+
+        ExpressionSigner::new(tezos.get_crypto()).sign(&packed, key)
+    }
+
+    /// Packs the [Micheline] value using the provided schema and signs it with the given key.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tezos_michelson::micheline::{Micheline, try_string};
+    /// use tezos_core::types::encoded::SecretKey;
+    ///
+    /// let value: Micheline = try_string("Tezos Signed Message: example.xyz 2021-01-01T00:00:00Z Hello").unwrap();
+    /// let key: SecretKey = "edskRv7VyXGVZb8EsrR7D9XKUbbAQNQGtALP6QeB16ZCD7SmmJpzyeneJVg3Mq56YLbxRA1kSdAXiswwPiaVfR3NHGMCXCziuZ".try_into().unwrap();
+    /// # #[cfg(feature = "ed25519")]
+    /// let signature = value.sign(None, &key).expect("valid signature");
+    /// ```
+    pub fn sign(&self, schema: Option<&Micheline>, key: &SecretKey) -> Result<Signature> {
+        let tezos: Tezos = Default::default();
+
+        self.sign_with(schema, key, &tezos)
+    }
+
+    /// Verifies that the signature of the [Micheline] value packed using the provided schema was produced by
+    /// the given key, using the crypto providers configured in the given [Tezos] instance.
+    pub fn verify_with(
+        &self,
+        schema: Option<&Micheline>,
+        signature: &Signature,
+        key: &PublicKey,
+        tezos: &Tezos,
+    ) -> Result<bool> {
+        let packed = self.clone().pack(schema)?;
+
+        ExpressionSigner::new(tezos.get_crypto()).verify(&packed, signature, key)
+    }
+
+    /// Verifies that the signature of the [Micheline] value packed using the provided schema was produced by
+    /// the given key.
+    pub fn verify(
+        &self,
+        schema: Option<&Micheline>,
+        signature: &Signature,
+        key: &PublicKey,
+    ) -> Result<bool> {
+        let tezos: Tezos = Default::default();
+
+        self.verify_with(schema, signature, key, &tezos)
+    }
+
     /// Encodes the [Micheline] value to bytes.
     pub fn to_bytes(&self) -> Result<Vec<u8>> {
         MichelineBytesCoder::encode(self)
@@ -136,6 +210,100 @@ impl Micheline {
     pub fn normalized(self) -> Self {
         MichelineNormalizer::normalize(self)
     }
+
+    /// Expands the standard Michelson macros, e.g. `CADR`, `DIIP`, `PAPAIR`, `IFCMPEQ` or `ASSERT_SOME`,
+    /// into core instructions the way `octez-client` does, so the result can be converted into
+    /// [Michelson] and sent to a node.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tezos_michelson::micheline::Micheline;
+    ///
+    /// let code = Micheline::parse("{ CADR ; DUUP ; IFCMPEQ {} { FAIL } }").unwrap();
+    /// assert_eq!(
+    ///     code.expand_macros().unwrap().to_compact_string(),
+    ///     "{ { CAR ; CDR } ; { DUP 2 } ; { COMPARE ; EQ ; IF {} { { UNIT ; FAILWITH } } } }"
+    /// );
+    /// ```
+    pub fn expand_macros(self) -> Result<Self> {
+        MichelineMacroExpander::expand(self)
+    }
+
+    /// Parses a [Micheline] expression written in the Michelson concrete syntax.
+    ///
+    /// Errors report the line and column (both starting at 1) of the invalid input.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tezos_michelson::micheline::Micheline;
+    ///
+    /// let value = Micheline::parse(r#"Pair 1 (Some "foo")"#).unwrap();
+    /// let code = Micheline::parse("{ CAR ; NIL operation ; PAIR }").unwrap();
+    /// ```
+    pub fn parse(source: &str) -> Result<Self> {
+        parser::parse_expression(source)
+    }
+
+    /// Parses a script written in the Michelson concrete syntax, e.g. the content of a `.tz` file.
+    ///
+    /// The toplevel sections are separated by semicolons and may or may not be enclosed in braces,
+    /// the result is always a [Sequence] of the sections.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tezos_michelson::micheline::Micheline;
+    ///
+    /// let script = Micheline::parse_script(
+    ///     "parameter unit; storage unit; code { CDR ; NIL operation ; PAIR }",
+    /// )
+    /// .unwrap();
+    /// assert_eq!(script.into_sequence().unwrap().values().len(), 3);
+    /// ```
+    pub fn parse_script(source: &str) -> Result<Self> {
+        parser::parse_script(source)
+    }
+
+    /// Renders the [Micheline] value in the Michelson concrete syntax the way `octez-client` does,
+    /// breaking the lines longer than `width` characters.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tezos_michelson::micheline::Micheline;
+    ///
+    /// let code = Micheline::parse("{ UNPAIR ; ADD ; NIL operation ; PAIR }").unwrap();
+    /// assert_eq!(
+    ///     code.to_pretty_string(20),
+    ///     "{ UNPAIR ;\n  ADD ;\n  NIL operation ;\n  PAIR }"
+    /// );
+    /// ```
+    pub fn to_pretty_string(&self, width: usize) -> alloc::string::String {
+        printer::print_pretty(self, width)
+    }
+
+    /// Renders the [Micheline] value in the Michelson concrete syntax on a single line, e.g. for logs.
+    pub fn to_compact_string(&self) -> alloc::string::String {
+        printer::print_compact(self)
+    }
+}
+
+/// Formats the value with [Micheline::to_pretty_string], the line width defaults to 80 characters
+/// and can be set with the formatter width, e.g. `format!("{:40}", micheline)`.
+impl fmt::Display for Micheline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_pretty_string(f.width().unwrap_or(printer::DEFAULT_WIDTH)))
+    }
+}
+
+impl FromStr for Micheline {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
 }
 
 impl From<Literal> for Micheline {
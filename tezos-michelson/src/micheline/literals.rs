@@ -1,3 +1,4 @@
+use alloc::format;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use tezos_core::types::number::Nat;
@@ -166,10 +167,10 @@ impl From<&String> for Micheline {
     }
 }
 
-impl TryFrom<std::string::String> for Literal {
+impl TryFrom<alloc::string::String> for Literal {
     type Error = Error;
 
-    fn try_from(value: std::string::String) -> Result<Self> {
+    fn try_from(value: alloc::string::String) -> Result<Self> {
         let string: String = value.try_into()?;
 
         Ok(Literal::String(string))
@@ -0,0 +1,597 @@
+//! Parser of the Micheline concrete syntax, the textual form used by `.tz` files and `octez-client`.
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{iter::Peekable, str::CharIndices};
+
+use super::{
+    literals::{self, Bytes, Int},
+    primitive_application::PrimitiveApplication,
+    Micheline,
+};
+use crate::{Error, Result};
+
+/// Parses a single expression, e.g. `Pair 1 (Some "foo")` or `{ DROP ; NIL operation ; PAIR }`.
+pub fn parse_expression(source: &str) -> Result<Micheline> {
+    let mut parser = Parser::new(source)?;
+    let expression = parser.node(true)?;
+    parser.expect_end()?;
+
+    Ok(expression)
+}
+
+/// Parses a script, the content of a `.tz` file: a sequence of sections (e.g. `parameter`, `storage`
+/// and `code`) separated by semicolons, the enclosing braces being optional.
+pub fn parse_script(source: &str) -> Result<Micheline> {
+    let mut parser = Parser::new(source)?;
+    if matches!(parser.peek_kind(), Some(TokenKind::LeftBrace)) {
+        let script = parser.node(true)?;
+        parser.expect_end()?;
+        return Ok(script);
+    }
+
+    let mut sections = Vec::new();
+    while parser.peek().is_some() {
+        sections.push(parser.node(true)?);
+        match parser.peek_kind() {
+            Some(TokenKind::Semicolon) => parser.index += 1,
+            None => break,
+            Some(_) => return Err(parser.unexpected()),
+        }
+    }
+
+    Ok(sections.into())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TokenKind {
+    Int(String),
+    String(String),
+    Bytes(String),
+    Primitive(String),
+    Annotation(String),
+    LeftBrace,
+    RightBrace,
+    LeftParenthesis,
+    RightParenthesis,
+    Semicolon,
+}
+
+impl TokenKind {
+    fn describe(&self) -> String {
+        match self {
+            Self::Int(value) | Self::Primitive(value) | Self::Annotation(value) => {
+                format!("`{}`", value)
+            }
+            Self::String(_) => "string".into(),
+            Self::Bytes(_) => "bytes".into(),
+            Self::LeftBrace => "`{`".into(),
+            Self::RightBrace => "`}`".into(),
+            Self::LeftParenthesis => "`(`".into(),
+            Self::RightParenthesis => "`)`".into(),
+            Self::Semicolon => "`;`".into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Location {
+    line: usize,
+    column: usize,
+}
+
+impl Location {
+    fn error<T: ToString>(&self, description: T) -> Error {
+        Error::InvalidMichelineSyntax {
+            description: description.to_string(),
+            line: self.line,
+            column: self.column,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    location: Location,
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    index: usize,
+    end: Location,
+}
+
+impl Parser {
+    fn new(source: &str) -> Result<Self> {
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.tokens()?;
+
+        Ok(Self {
+            tokens,
+            index: 0,
+            end: lexer.location,
+        })
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.index)
+    }
+
+    fn peek_kind(&self) -> Option<&TokenKind> {
+        self.peek().map(|token| &token.kind)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.index).cloned();
+        self.index += 1;
+        token
+    }
+
+    fn unexpected(&self) -> Error {
+        match self.peek() {
+            Some(token) => token
+                .location
+                .error(format!("unexpected {}", token.kind.describe())),
+            None => self.end.error("unexpected end of input"),
+        }
+    }
+
+    fn expect_end(&self) -> Result<()> {
+        match self.peek() {
+            Some(_) => Err(self.unexpected()),
+            None => Ok(()),
+        }
+    }
+
+    /// Parses a node, primitives take annotations and arguments only if `application` is set,
+    /// as in the arguments of another primitive they have to be wrapped in parentheses.
+    fn node(&mut self, application: bool) -> Result<Micheline> {
+        let token = match self.peek() {
+            Some(token) => token.clone(),
+            None => return Err(self.unexpected()),
+        };
+        match token.kind {
+            TokenKind::Int(value) => {
+                self.index += 1;
+                let int: Int = value
+                    .as_str()
+                    .try_into()
+                    .map_err(|_| token.location.error("invalid int"))?;
+                Ok(int.into())
+            }
+            TokenKind::String(value) => {
+                self.index += 1;
+                let string: literals::String = value.try_into()?;
+                Ok(string.into())
+            }
+            TokenKind::Bytes(value) => {
+                self.index += 1;
+                let bytes: Bytes = value
+                    .try_into()
+                    .map_err(|_| token.location.error("invalid bytes"))?;
+                Ok(bytes.into())
+            }
+            TokenKind::LeftBrace => self.sequence(),
+            TokenKind::LeftParenthesis => {
+                self.index += 1;
+                let node = self.node(true)?;
+                match self.next() {
+                    Some(Token {
+                        kind: TokenKind::RightParenthesis,
+                        ..
+                    }) => Ok(node),
+                    _ => {
+                        self.index -= 1;
+                        Err(self.unexpected())
+                    }
+                }
+            }
+            TokenKind::Primitive(prim) => {
+                self.index += 1;
+                if !application {
+                    if let Some(TokenKind::Annotation(_)) = self.peek_kind() {
+                        return Err(self.peek().unwrap().location.error(
+                            "annotations of an argument have to be wrapped in parentheses with the argument",
+                        ));
+                    }
+                    return Ok(PrimitiveApplication::new(prim, None, None).into());
+                }
+                let mut annots = Vec::new();
+                while let Some(TokenKind::Annotation(annot)) = self.peek_kind() {
+                    annots.push(annot.clone());
+                    self.index += 1;
+                }
+                let mut args = Vec::new();
+                while let Some(kind) = self.peek_kind() {
+                    match kind {
+                        TokenKind::RightBrace
+                        | TokenKind::RightParenthesis
+                        | TokenKind::Semicolon => break,
+                        TokenKind::Annotation(_) => {
+                            return Err(self
+                                .peek()
+                                .unwrap()
+                                .location
+                                .error("annotations have to follow the primitive"))
+                        }
+                        _ => args.push(self.node(false)?),
+                    }
+                }
+                Ok(PrimitiveApplication::new(prim, Some(args), Some(annots)).into())
+            }
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    fn sequence(&mut self) -> Result<Micheline> {
+        self.index += 1;
+        let mut values = Vec::new();
+        loop {
+            if let Some(TokenKind::RightBrace) = self.peek_kind() {
+                self.index += 1;
+                return Ok(values.into());
+            }
+            values.push(self.node(true)?);
+            match self.peek_kind() {
+                Some(TokenKind::Semicolon) => self.index += 1,
+                Some(TokenKind::RightBrace) => {}
+                _ => return Err(self.unexpected()),
+            }
+        }
+    }
+}
+
+struct Lexer<'a> {
+    chars: Peekable<CharIndices<'a>>,
+    location: Location,
+}
+
+impl<'a> Lexer<'a> {
+    fn new(source: &'a str) -> Self {
+        Self {
+            chars: source.char_indices().peekable(),
+            location: Location { line: 1, column: 1 },
+        }
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().map(|(_, c)| *c)
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let (_, c) = self.chars.next()?;
+        if c == '\n' {
+            self.location.line += 1;
+            self.location.column = 1;
+        } else {
+            self.location.column += 1;
+        }
+        Some(c)
+    }
+
+    fn take_while<F: Fn(char) -> bool>(&mut self, value: &mut String, predicate: F) {
+        while let Some(c) = self.peek() {
+            if !predicate(c) {
+                break;
+            }
+            value.push(c);
+            self.bump();
+        }
+    }
+
+    fn tokens(&mut self) -> Result<Vec<Token>> {
+        let mut tokens = Vec::new();
+        while let Some(c) = self.peek() {
+            let location = self.location;
+            let kind = match c {
+                ' ' | '\t' | '\n' | '\r' => {
+                    self.bump();
+                    continue;
+                }
+                '#' => {
+                    while !matches!(self.bump(), None | Some('\n')) {}
+                    continue;
+                }
+                '/' => {
+                    self.block_comment(location)?;
+                    continue;
+                }
+                '{' => self.punctuation(TokenKind::LeftBrace),
+                '}' => self.punctuation(TokenKind::RightBrace),
+                '(' => self.punctuation(TokenKind::LeftParenthesis),
+                ')' => self.punctuation(TokenKind::RightParenthesis),
+                ';' => self.punctuation(TokenKind::Semicolon),
+                '"' => self.string(location)?,
+                '-' | '0'..='9' => self.number(location)?,
+                '@' | '%' | ':' => {
+                    let mut annot = String::new();
+                    annot.push(c);
+                    self.bump();
+                    self.take_while(&mut annot, |c| {
+                        c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '%' | '@')
+                    });
+                    TokenKind::Annotation(annot)
+                }
+                c if c.is_ascii_alphabetic() || c == '_' => {
+                    let mut prim = String::new();
+                    self.take_while(&mut prim, |c| c.is_ascii_alphanumeric() || c == '_');
+                    TokenKind::Primitive(prim)
+                }
+                c => return Err(location.error(format!("unexpected character `{}`", c))),
+            };
+            tokens.push(Token { kind, location });
+        }
+
+        Ok(tokens)
+    }
+
+    fn punctuation(&mut self, kind: TokenKind) -> TokenKind {
+        self.bump();
+        kind
+    }
+
+    fn block_comment(&mut self, location: Location) -> Result<()> {
+        self.bump();
+        if self.bump() != Some('*') {
+            return Err(location.error("unexpected character `/`"));
+        }
+        loop {
+            match self.bump() {
+                Some('*') if self.peek() == Some('/') => {
+                    self.bump();
+                    return Ok(());
+                }
+                Some(_) => {}
+                None => return Err(location.error("unterminated comment")),
+            }
+        }
+    }
+
+    fn string(&mut self, location: Location) -> Result<TokenKind> {
+        self.bump();
+        let mut value = String::new();
+        loop {
+            let escape_location = self.location;
+            match self.bump() {
+                Some('"') => return Ok(TokenKind::String(value)),
+                Some('\\') => {
+                    let escaped = match self.bump() {
+                        Some('n') => '\n',
+                        Some('t') => '\t',
+                        Some('b') => '\u{8}',
+                        Some('r') => '\r',
+                        Some('\\') => '\\',
+                        Some('"') => '"',
+                        _ => return Err(escape_location.error("invalid escape sequence")),
+                    };
+                    value.push(escaped);
+                }
+                Some('\n') | None => return Err(location.error("unterminated string")),
+                Some(c) => value.push(c),
+            }
+        }
+    }
+
+    fn number(&mut self, location: Location) -> Result<TokenKind> {
+        let mut value = String::new();
+        if self.peek() == Some('-') {
+            value.push('-');
+            self.bump();
+        }
+        self.take_while(&mut value, |c| c.is_ascii_digit());
+        if value == "0" && self.peek() == Some('x') {
+            value.push('x');
+            self.bump();
+            self.take_while(&mut value, |c| c.is_ascii_alphanumeric());
+            if !value.len().is_multiple_of(2) || !value[2..].chars().all(|c| c.is_ascii_hexdigit())
+            {
+                return Err(location.error("invalid bytes"));
+            }
+            return Ok(TokenKind::Bytes(value));
+        }
+        if value.is_empty() || value == "-" {
+            return Err(location.error("invalid int"));
+        }
+        if let Some(c) = self
+            .peek()
+            .filter(|c| c.is_ascii_alphanumeric() || *c == '_')
+        {
+            return Err(self.location.error(format!("unexpected character `{}`", c)));
+        }
+
+        Ok(TokenKind::Int(value))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::micheline::{int, primitive_application, try_bytes, try_string};
+    use alloc::vec;
+
+    fn prim(name: &str, args: Vec<Micheline>, annots: Vec<&str>) -> Micheline {
+        primitive_application(name)
+            .with_args(args)
+            .with_annots(annots.into_iter().map(String::from).collect())
+            .into()
+    }
+
+    #[test]
+    fn test_parse_expression() -> Result<()> {
+        let values: Vec<(&str, Micheline)> = vec![
+            ("1", int(1)),
+            ("-42", int(-42)),
+            ("\"foo\"", try_string("foo")?),
+            (r#""a\"b\\c\nd""#, try_string("a\"b\\c\nd")?),
+            ("0x", try_bytes("0x")?),
+            ("0x0aFF", try_bytes("0x0aFF")?),
+            ("Unit", prim("Unit", vec![], vec![])),
+            ("(Unit)", prim("Unit", vec![], vec![])),
+            (
+                "Pair 1 (Some \"foo\")",
+                prim(
+                    "Pair",
+                    vec![int(1), prim("Some", vec![try_string("foo")?], vec![])],
+                    vec![],
+                ),
+            ),
+            (
+                "pair (nat %amount) (option :o %memo string) unit",
+                prim(
+                    "pair",
+                    vec![
+                        prim("nat", vec![], vec!["%amount"]),
+                        prim(
+                            "option",
+                            vec![prim("string", vec![], vec![])],
+                            vec![":o", "%memo"],
+                        ),
+                        prim("unit", vec![], vec![]),
+                    ],
+                    vec![],
+                ),
+            ),
+            ("{}", vec![].into()),
+            (
+                "{ Elt 1 \"one\" ; Elt 2 \"two\" ; }",
+                vec![
+                    prim("Elt", vec![int(1), try_string("one")?], vec![]),
+                    prim("Elt", vec![int(2), try_string("two")?], vec![]),
+                ]
+                .into(),
+            ),
+            (
+                "{ CAR @storage ; NIL operation ; PAIR ; { } }",
+                vec![
+                    prim("CAR", vec![], vec!["@storage"]),
+                    prim("NIL", vec![prim("operation", vec![], vec![])], vec![]),
+                    prim("PAIR", vec![], vec![]),
+                    vec![].into(),
+                ]
+                .into(),
+            ),
+            (
+                "IF_LEFT { DROP } { PUSH nat 1 ; DROP } # comment\n",
+                prim(
+                    "IF_LEFT",
+                    vec![
+                        vec![prim("DROP", vec![], vec![])].into(),
+                        vec![
+                            prim("PUSH", vec![prim("nat", vec![], vec![]), int(1)], vec![]),
+                            prim("DROP", vec![], vec![]),
+                        ]
+                        .into(),
+                    ],
+                    vec![],
+                ),
+            ),
+            (
+                "/* multi\nline */ Left /* inline */ Unit",
+                prim("Left", vec![prim("Unit", vec![], vec![])], vec![]),
+            ),
+        ];
+        for (source, expected) in values {
+            assert_eq!(parse_expression(source)?, expected, "{}", source);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_script() -> Result<()> {
+        let source = "# counter\nparameter (or (int %increment) (unit %reset));\nstorage int;\ncode { UNPAIR ;\n       IF_LEFT { ADD } { DROP 2 ; PUSH int 0 } ;\n       NIL operation ; PAIR }\n";
+        let expected: Micheline = vec![
+            prim(
+                "parameter",
+                vec![prim(
+                    "or",
+                    vec![
+                        prim("int", vec![], vec!["%increment"]),
+                        prim("unit", vec![], vec!["%reset"]),
+                    ],
+                    vec![],
+                )],
+                vec![],
+            ),
+            prim("storage", vec![prim("int", vec![], vec![])], vec![]),
+            prim(
+                "code",
+                vec![vec![
+                    prim("UNPAIR", vec![], vec![]),
+                    prim(
+                        "IF_LEFT",
+                        vec![
+                            vec![prim("ADD", vec![], vec![])].into(),
+                            vec![
+                                prim("DROP", vec![int(2)], vec![]),
+                                prim("PUSH", vec![prim("int", vec![], vec![]), int(0)], vec![]),
+                            ]
+                            .into(),
+                        ],
+                        vec![],
+                    ),
+                    prim("NIL", vec![prim("operation", vec![], vec![])], vec![]),
+                    prim("PAIR", vec![], vec![]),
+                ]
+                .into()],
+                vec![],
+            ),
+        ]
+        .into();
+
+        assert_eq!(parse_script(source)?, expected);
+        assert_eq!(parse_script(&format!("{{ {} }}", source))?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_str() -> Result<()> {
+        use crate::michelson::{
+            types::{nat, pair, string, Type},
+            Michelson,
+        };
+
+        let ty: Type = "pair nat string".parse()?;
+        assert_eq!(ty, pair(vec![nat(), string()]));
+        let michelson: Michelson = "{ DROP ; UNIT }".parse()?;
+        assert!(matches!(michelson, Michelson::Data(_)));
+        assert!("pair nat (".parse::<Type>().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_errors() {
+        let values = [
+            ("Pair 1", None),
+            ("\"unterminated", Some((1, 1))),
+            ("\"bad \\q escape\"", Some((1, 6))),
+            ("0x0", Some((1, 1))),
+            ("0xzz", Some((1, 1))),
+            ("12ab", Some((1, 3))),
+            ("{ UNIT ; \n  UNIT UNIT ) }", Some((2, 13))),
+            ("{ UNIT", Some((1, 7))),
+            ("Pair 1 2 %a", Some((1, 10))),
+            ("pair nat %a nat", Some((1, 10))),
+            ("/* unterminated", Some((1, 1))),
+            ("Unit Unit\n  $", Some((2, 3))),
+            ("Unit ; Unit", Some((1, 6))),
+        ];
+        for (source, location) in values {
+            let result = parse_expression(source);
+            match location {
+                None => assert!(result.is_ok(), "{}", source),
+                Some((line, column)) => match result {
+                    Err(Error::InvalidMichelineSyntax {
+                        line: error_line,
+                        column: error_column,
+                        ..
+                    }) => assert_eq!((error_line, error_column), (line, column), "{}", source),
+                    other => panic!("unexpected result {:?} for {}", other, source),
+                },
+            }
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use alloc::{format, string::String, vec::Vec};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use tezos_core::internal::normalizer::Normalizer;
@@ -85,9 +86,12 @@ impl PrimitiveApplication {
         self
     }
 
-    pub fn try_with_mutated_args<F, Error>(mut self, mutator: F) -> std::result::Result<Self, Error>
+    pub fn try_with_mutated_args<F, Error>(
+        mut self,
+        mutator: F,
+    ) -> core::result::Result<Self, Error>
     where
-        F: FnOnce(Vec<Micheline>) -> std::result::Result<Vec<Micheline>, Error>,
+        F: FnOnce(Vec<Micheline>) -> core::result::Result<Vec<Micheline>, Error>,
     {
         if let Some(args) = self.args {
             self.args = Some(mutator(args)?)
@@ -99,9 +103,9 @@ impl PrimitiveApplication {
         mut self,
         index: usize,
         replacer: F,
-    ) -> std::result::Result<Self, Error>
+    ) -> core::result::Result<Self, Error>
     where
-        F: FnOnce(Micheline) -> std::result::Result<Micheline, Error>,
+        F: FnOnce(Micheline) -> core::result::Result<Micheline, Error>,
     {
         if let Some(args) = self.args.as_mut() {
             let element = args.remove(index);
@@ -0,0 +1,294 @@
+//! Printer of the Micheline concrete syntax, following the layout of `octez-client`.
+
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+
+use super::{literals::Literal, primitive_application::PrimitiveApplication, Micheline};
+
+/// The default line width of [Micheline::to_pretty_string].
+pub const DEFAULT_WIDTH: usize = 80;
+
+/// Renders the expression on a single line.
+pub fn print_compact(micheline: &Micheline) -> String {
+    let mut out = String::new();
+    write_compact(&mut out, micheline, false);
+    out
+}
+
+/// Renders the expression, breaking the lines that would exceed `width` characters.
+///
+/// Sequences that do not fit on their line put one element per line, aligned after their opening
+/// brace. Primitive applications that do not fit move the overflowing arguments to the next line,
+/// indented by two spaces from the primitive, and so do the arguments following an argument
+/// spanning several lines. If some of the arguments are sequences, e.g. of `IF`,
+/// each argument gets its own line, unless it's the only one, e.g. of `code` or `ITER`, in which
+/// case it stays on the line of the primitive.
+pub fn print_pretty(micheline: &Micheline, width: usize) -> String {
+    let mut printer = Printer {
+        out: String::new(),
+        column: 0,
+        line: 0,
+        width,
+    };
+    printer.write(micheline, false, 0);
+    printer.out
+}
+
+struct Printer {
+    out: String,
+    column: usize,
+    line: usize,
+    width: usize,
+}
+
+impl Printer {
+    fn push(&mut self, value: &str) {
+        self.out.push_str(value);
+        self.column += value.chars().count();
+    }
+
+    fn new_line(&mut self, indent: usize) {
+        self.out.push('\n');
+        self.out.extend(core::iter::repeat_n(' ', indent));
+        self.column = indent;
+        self.line += 1;
+    }
+
+    fn fits(&self, length: usize, reserve: usize) -> bool {
+        self.column + length + reserve <= self.width
+    }
+
+    /// Writes the node, keeping `reserve` characters free for what has to follow it on the same line.
+    fn write(&mut self, micheline: &Micheline, argument: bool, reserve: usize) {
+        let compact = print_compact_node(micheline, argument);
+        if self.fits(compact.chars().count(), reserve) {
+            self.push(&compact);
+            return;
+        }
+
+        match micheline {
+            Micheline::Literal(_) => self.push(&compact),
+            Micheline::Sequence(sequence) => {
+                let values = sequence.values();
+                self.push("{ ");
+                let indent = self.column;
+                for (index, value) in values.iter().enumerate() {
+                    let is_last = index + 1 == values.len();
+                    if index > 0 {
+                        self.new_line(indent);
+                    }
+                    self.write(value, false, if is_last { reserve + 2 } else { 2 });
+                    if !is_last {
+                        self.push(" ;");
+                    }
+                }
+                self.push(" }");
+            }
+            Micheline::PrimitiveApplication(primitive_application) => {
+                let parenthesized = argument && needs_parentheses(primitive_application);
+                let reserve = if parenthesized { reserve + 1 } else { reserve };
+                let indent = self.column + 2;
+                if parenthesized {
+                    self.push("(");
+                }
+                self.push(&print_head(primitive_application));
+                let args = primitive_application
+                    .args()
+                    .as_ref()
+                    .map(|args| args.as_slice())
+                    .unwrap_or_default();
+                let has_sequence = args.iter().any(|arg| arg.is_sequence());
+                let mut previous_broken = false;
+                for (index, arg) in args.iter().enumerate() {
+                    let arg_reserve = if index + 1 == args.len() { reserve } else { 0 };
+                    let compact = print_compact_node(arg, true);
+                    let inline = if has_sequence {
+                        args.len() == 1
+                    } else {
+                        !previous_broken && self.fits(compact.chars().count() + 1, arg_reserve)
+                    };
+                    if inline {
+                        self.push(" ");
+                    } else {
+                        self.new_line(indent);
+                    }
+                    let line = self.line;
+                    self.write(arg, true, arg_reserve);
+                    previous_broken = self.line > line;
+                }
+                if parenthesized {
+                    self.push(")");
+                }
+            }
+        }
+    }
+}
+
+fn print_compact_node(micheline: &Micheline, argument: bool) -> String {
+    let mut out = String::new();
+    write_compact(&mut out, micheline, argument);
+    out
+}
+
+fn write_compact(out: &mut String, micheline: &Micheline, argument: bool) {
+    match micheline {
+        Micheline::Literal(literal) => write_literal(out, literal),
+        Micheline::Sequence(sequence) => {
+            if sequence.values().is_empty() {
+                out.push_str("{}");
+                return;
+            }
+            out.push_str("{ ");
+            for (index, value) in sequence.values().iter().enumerate() {
+                if index > 0 {
+                    out.push_str(" ; ");
+                }
+                write_compact(out, value, false);
+            }
+            out.push_str(" }");
+        }
+        Micheline::PrimitiveApplication(primitive_application) => {
+            let parenthesized = argument && needs_parentheses(primitive_application);
+            if parenthesized {
+                out.push('(');
+            }
+            out.push_str(&print_head(primitive_application));
+            if let Some(args) = primitive_application.args() {
+                for arg in args {
+                    out.push(' ');
+                    write_compact(out, arg, true);
+                }
+            }
+            if parenthesized {
+                out.push(')');
+            }
+        }
+    }
+}
+
+fn needs_parentheses(primitive_application: &PrimitiveApplication) -> bool {
+    primitive_application.args_count() > 0
+        || primitive_application
+            .annots()
+            .as_ref()
+            .is_some_and(|annots| !annots.is_empty())
+}
+
+fn print_head(primitive_application: &PrimitiveApplication) -> String {
+    let mut head = primitive_application.prim().to_string();
+    if let Some(annots) = primitive_application.annots() {
+        for annot in annots {
+            head.push(' ');
+            head.push_str(annot);
+        }
+    }
+    head
+}
+
+fn write_literal(out: &mut String, literal: &Literal) {
+    match literal {
+        Literal::Int(value) => out.push_str(&value.to_string()),
+        Literal::Bytes(value) => out.push_str(&format!("0x{}", &value.value()[2..]).to_lowercase()),
+        Literal::String(value) => {
+            out.push('"');
+            for c in value.to_str().chars() {
+                match c {
+                    '"' => out.push_str("\\\""),
+                    '\\' => out.push_str("\\\\"),
+                    '\n' => out.push_str("\\n"),
+                    '\r' => out.push_str("\\r"),
+                    '\t' => out.push_str("\\t"),
+                    '\u{8}' => out.push_str("\\b"),
+                    c => out.push(c),
+                }
+            }
+            out.push('"');
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{micheline::try_string, Result};
+
+    #[test]
+    fn test_print_compact() -> Result<()> {
+        let values = [
+            "1",
+            "-42",
+            "\"foo\"",
+            r#""a\"b\\c\nd""#,
+            "0x",
+            "0x0aff",
+            "Unit",
+            "{}",
+            r#"Pair 1 (Some "foo")"#,
+            "pair (nat %amount) (option :o %memo string) unit",
+            "{ Elt 1 \"one\" ; Elt 2 \"two\" }",
+            "{ CAR @storage ; NIL operation ; PAIR ; {} }",
+            "IF_LEFT { DROP } { PUSH nat 1 ; DROP }",
+            "{ parameter unit ; storage (list nat) ; code { CDR ; NIL operation ; PAIR } }",
+        ];
+        for value in values {
+            assert_eq!(print_compact(&Micheline::parse(value)?), value);
+        }
+        assert_eq!(
+            print_compact(&Micheline::parse("{ DROP ;\n  UNIT ; }")?),
+            "{ DROP ; UNIT }"
+        );
+        assert_eq!(print_compact(&try_string("tab\there")?), "\"tab\\there\"");
+        Ok(())
+    }
+
+    #[test]
+    fn test_print_pretty() -> Result<()> {
+        let script = Micheline::parse_script(
+            "parameter (or (int %increment) (unit %reset)); storage int; code { UNPAIR ; IF_LEFT { ADD } { DROP 2 ; PUSH int 0 } ; NIL operation ; PAIR }",
+        )?;
+        assert_eq!(
+            print_pretty(&script, 40),
+            "{ parameter\n    (or (int %increment)\n      (unit %reset)) ;\n  storage int ;\n  code { UNPAIR ;\n         IF_LEFT\n           { ADD }\n           { DROP 2 ; PUSH int 0 } ;\n         NIL operation ;\n         PAIR } }"
+        );
+        assert_eq!(
+            print_pretty(&script, DEFAULT_WIDTH),
+            "{ parameter (or (int %increment) (unit %reset)) ;\n  storage int ;\n  code { UNPAIR ;\n         IF_LEFT { ADD } { DROP 2 ; PUSH int 0 } ;\n         NIL operation ;\n         PAIR } }"
+        );
+
+        let value = Micheline::parse(
+            r#"Pair (Pair "tz1VSUr8wwNhLAzempoch5d6hLRiTh8Cjcjb" 1000000) (Some (Left "a long memo"))"#,
+        )?;
+        assert_eq!(
+            print_pretty(&value, 50),
+            "Pair\n  (Pair \"tz1VSUr8wwNhLAzempoch5d6hLRiTh8Cjcjb\"\n    1000000)\n  (Some (Left \"a long memo\"))"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_display() -> Result<()> {
+        use crate::michelson::{data, types, Michelson};
+        use alloc::vec;
+
+        let ty: types::Type = types::pair(vec![types::nat(), types::option(types::string())]);
+        assert_eq!(format!("{}", ty), "pair nat (option string)");
+        assert_eq!(format!("{:12}", ty), "pair nat\n  (option\n    string)");
+        let value: Michelson = data::some(data::pair(vec![data::int(1), data::try_string("a")?]));
+        assert_eq!(format!("{}", value), "Some (Pair 1 \"a\")");
+        Ok(())
+    }
+
+    #[test]
+    fn test_print_pretty_round_trip() -> Result<()> {
+        let source = "{ parameter (pair (list %transfers (pair address nat)) (option %memo string)) ; storage (big_map address nat) ; code { UNPAIR ; CAR ; ITER { UNPAIR ; DIG 2 ; SWAP ; DUP 3 ; GET ; IF_NONE { PUSH nat 0 } {} ; DIG 2 ; ADD ; SOME ; SWAP ; UPDATE } ; NIL operation ; PAIR } }";
+        let micheline = Micheline::parse(source)?;
+        for width in [10, 20, 40, 80, 200] {
+            let pretty = print_pretty(&micheline, width);
+            assert_eq!(Micheline::parse(&pretty)?, micheline, "{}", pretty);
+        }
+        assert_eq!(print_pretty(&micheline, 1000), source);
+        Ok(())
+    }
+}
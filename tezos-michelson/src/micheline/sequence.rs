@@ -1,3 +1,4 @@
+use alloc::{format, vec::Vec};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use tezos_core::internal::{coder::Encoder, normalizer::Normalizer};
@@ -0,0 +1,83 @@
+use alloc::vec::Vec;
+use tezos_core::timelock::{Chest, ChestKey};
+
+use super::{literals::Literal, Micheline};
+use crate::{common::bytes::Bytes, Error, Result};
+
+fn into_bytes(value: &Micheline) -> Result<Vec<u8>> {
+    match value {
+        Micheline::Literal(Literal::Bytes(bytes)) => Ok(bytes.into()),
+        _ => Err(Error::InvalidMichelineLiteral),
+    }
+}
+
+impl TryFrom<&Chest> for Micheline {
+    type Error = Error;
+
+    fn try_from(value: &Chest) -> Result<Self> {
+        Ok(Bytes::from(value.to_bytes()?).into())
+    }
+}
+
+impl TryFrom<&Micheline> for Chest {
+    type Error = Error;
+
+    fn try_from(value: &Micheline) -> Result<Self> {
+        Ok(Chest::from_bytes(&into_bytes(value)?)?)
+    }
+}
+
+impl TryFrom<&ChestKey> for Micheline {
+    type Error = Error;
+
+    fn try_from(value: &ChestKey) -> Result<Self> {
+        Ok(Bytes::from(value.to_bytes()?).into())
+    }
+}
+
+impl TryFrom<&Micheline> for ChestKey {
+    type Error = Error;
+
+    fn try_from(value: &Micheline) -> Result<Self> {
+        Ok(ChestKey::from_bytes(&into_bytes(value)?)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+    use tezos_core::timelock::Opening;
+
+    use super::*;
+    use crate::micheline::int;
+
+    #[test]
+    fn test_chest_micheline() -> Result<()> {
+        let (chest, chest_key) =
+            Chest::create(b"timelocked", 10, &mut ChaCha20Rng::seed_from_u64(0))?;
+
+        let chest_micheline: Micheline = (&chest).try_into()?;
+        let chest_key_micheline: Micheline = (&chest_key).try_into()?;
+        assert!(matches!(
+            chest_micheline,
+            Micheline::Literal(Literal::Bytes(_))
+        ));
+
+        let decoded_chest: Chest = (&chest_micheline).try_into()?;
+        let decoded_chest_key: ChestKey = (&chest_key_micheline).try_into()?;
+        assert_eq!(
+            decoded_chest.open(&decoded_chest_key, 10),
+            Opening::Correct(b"timelocked".to_vec())
+        );
+        assert!(matches!(
+            Chest::try_from(&chest_key_micheline),
+            Err(Error::Core { .. })
+        ));
+        assert!(matches!(
+            ChestKey::try_from(&int::<_, Micheline>(1)),
+            Err(Error::InvalidMichelineLiteral)
+        ));
+
+        Ok(())
+    }
+}
@@ -3,8 +3,9 @@ pub mod data;
 pub mod metadata;
 pub mod types;
 
+use alloc::{format, string::String, vec, vec::Vec};
 use annotations::Annotation;
-use std::str::FromStr;
+use core::{fmt, str::FromStr};
 use tezos_core::internal::normalizer::Normalizer;
 
 pub use self::{
@@ -129,6 +130,21 @@ impl TryFrom<Micheline> for Michelson {
     }
 }
 
+impl FromStr for Michelson {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Micheline::parse(s)?.try_into()
+    }
+}
+
+/// Formats the structure in the Michelson concrete syntax, see [Micheline::to_pretty_string].
+impl fmt::Display for Michelson {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Micheline::from(self).fmt(f)
+    }
+}
+
 impl From<Literal> for Michelson {
     fn from(value: Literal) -> Self {
         match value {
@@ -1,4 +1,5 @@
 use crate::{Error, Result};
+use alloc::{format, string::String};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Kind {
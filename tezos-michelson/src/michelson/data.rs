@@ -3,9 +3,14 @@ mod macros;
 mod map;
 mod sequence;
 
+use alloc::vec::Vec;
+use core::fmt;
+
 use self::macros::{make_all_data, make_data};
 pub use crate::common::{bytes::Bytes, string::String};
-use crate::internal::normalizer::MichelsonNormalizer;
+use crate::{
+    internal::normalizer::MichelsonNormalizer, michelson::types::Type, typechecker::typecheck_data,
+};
 pub use map::{map, Map};
 use num_traits::ToPrimitive;
 pub use sequence::{sequence, Sequence};
@@ -45,6 +50,18 @@ impl Data {
     pub fn normalized(self) -> Self {
         MichelsonNormalizer::normalize(self)
     }
+
+    /// Checks that the value is a valid value of the type, see [typecheck_data](crate::typechecker::typecheck_data).
+    pub fn typecheck(&self, r#type: &Type) -> crate::Result<()> {
+        let value = Micheline::from(Michelson::from(self.clone()));
+        typecheck_data(&value, &r#type.into())
+    }
+}
+
+impl fmt::Display for Data {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Micheline::from(Michelson::from(self.clone())).fmt(f)
+    }
 }
 
 impl From<Literal> for Data {
@@ -70,10 +87,10 @@ impl From<i8> for Data {
     }
 }
 
-impl TryFrom<std::string::String> for Data {
+impl TryFrom<alloc::string::String> for Data {
     type Error = Error;
 
-    fn try_from(value: std::string::String) -> Result<Self> {
+    fn try_from(value: alloc::string::String) -> Result<Self> {
         let value: String = value.try_into()?;
         Ok(value.into())
     }
@@ -290,16 +307,16 @@ impl From<Primitive> for crate::michelson::Primitive {
 
 pub fn int<T, Output>(value: T) -> Output
 where
-    T: std::convert::Into<Int>,
+    T: core::convert::Into<Int>,
     Output: From<Int>,
 {
     let value: Int = value.into();
     value.into()
 }
 
-pub fn try_int<T, Output, Error>(value: T) -> std::result::Result<Output, Error>
+pub fn try_int<T, Output, Error>(value: T) -> core::result::Result<Output, Error>
 where
-    T: std::convert::TryInto<Int, Error = Error>,
+    T: core::convert::TryInto<Int, Error = Error>,
     Output: From<Int>,
 {
     let value: Int = value.try_into()?;
@@ -308,16 +325,16 @@ where
 
 pub fn nat<T, Output>(value: T) -> Output
 where
-    T: std::convert::Into<Nat>,
+    T: core::convert::Into<Nat>,
     Output: From<Nat>,
 {
     let value: Nat = value.into();
     value.into()
 }
 
-pub fn try_nat<T, Output, Error>(value: T) -> std::result::Result<Output, Error>
+pub fn try_nat<T, Output, Error>(value: T) -> core::result::Result<Output, Error>
 where
-    T: std::convert::TryInto<Nat, Error = Error>,
+    T: core::convert::TryInto<Nat, Error = Error>,
     Output: From<Nat>,
 {
     let value: Nat = value.try_into()?;
@@ -326,16 +343,16 @@ where
 
 pub fn string<T, Output>(value: T) -> Output
 where
-    T: std::convert::Into<String>,
+    T: core::convert::Into<String>,
     Output: From<String>,
 {
     let value: String = value.into();
     value.into()
 }
 
-pub fn try_string<T, Output, Error>(value: T) -> std::result::Result<Output, Error>
+pub fn try_string<T, Output, Error>(value: T) -> core::result::Result<Output, Error>
 where
-    T: std::convert::TryInto<String, Error = Error>,
+    T: core::convert::TryInto<String, Error = Error>,
     Output: From<String>,
 {
     let value: String = value.try_into()?;
@@ -344,16 +361,16 @@ where
 
 pub fn bytes<T, Output>(value: T) -> Output
 where
-    T: std::convert::Into<Bytes>,
+    T: core::convert::Into<Bytes>,
     Output: From<Bytes>,
 {
     let value: Bytes = value.into();
     value.into()
 }
 
-pub fn try_bytes<T, Output, Error>(value: T) -> std::result::Result<Output, Error>
+pub fn try_bytes<T, Output, Error>(value: T) -> core::result::Result<Output, Error>
 where
-    T: std::convert::TryInto<Bytes, Error = Error>,
+    T: core::convert::TryInto<Bytes, Error = Error>,
     Output: From<Bytes>,
 {
     let value: Bytes = value.try_into()?;
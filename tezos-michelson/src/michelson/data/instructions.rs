@@ -626,6 +626,21 @@ make_instructions!(
     (OpenChest, OPEN_CHEST, open_chest, 143),
 );
 
+impl Instruction {
+    /// Typechecks the instruction against the types of the input stack, given from its top,
+    /// see [typecheck_code](crate::typechecker::typecheck_code).
+    pub fn typecheck(
+        &self,
+        stack: &[crate::michelson::types::Type],
+    ) -> Result<crate::typechecker::StackType> {
+        let stack = stack
+            .iter()
+            .map(Micheline::from)
+            .collect::<alloc::vec::Vec<_>>();
+        crate::typechecker::typecheck_code(&self.into(), &stack)
+    }
+}
+
 impl From<Primitive> for crate::michelson::Primitive {
     fn from(value: Primitive) -> Self {
         Self::Instruction(value)
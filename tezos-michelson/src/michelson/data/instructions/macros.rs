@@ -123,7 +123,7 @@ macro_rules! make_instruction {
                     pub $field_name: $field_type,
                 )*
                 $(
-                    pub $boxed_field_name: Box<$boxed_field_type>,
+                    pub $boxed_field_name: alloc::boxed::Box<$boxed_field_type>,
                 )*
                 $(
                     pub(crate) metadata: $metadata_type,
@@ -136,7 +136,7 @@ macro_rules! make_instruction {
                         &self.metadata
                     }
 
-                    pub fn annotations(&self) -> Vec<&crate::michelson::Annotation> {
+                    pub fn annotations(&self) -> alloc::vec::Vec<&crate::michelson::Annotation> {
                         self.metadata.annotations()
                     }
                 )?
@@ -145,7 +145,7 @@ macro_rules! make_instruction {
                     Self {
                         $($opt_field_name,)*
                         $($field_name,)*
-                        $($boxed_field_name: Box::new($boxed_field_name),)*
+                        $($boxed_field_name: alloc::boxed::Box::new($boxed_field_name),)*
                         $(
                             metadata: metadata as $metadata_type
                         )?
@@ -205,7 +205,7 @@ macro_rules! make_instruction {
             impl From<$name> for Micheline {
                 #[allow(unused)]
                 fn from(value: $name) -> Self {
-                    let mut args: Vec<Micheline> = vec![];
+                    let mut args: alloc::vec::Vec<Micheline> = alloc::vec![];
                     $(
                         if let Some(value) = value.$opt_field_name {
                             args.push(value.into());
@@ -217,7 +217,7 @@ macro_rules! make_instruction {
                     $(
                         args.push((*value.$boxed_field_name).into());
                     )*
-                    let mut annots: Vec<String> = vec![];
+                    let mut annots: alloc::vec::Vec<alloc::string::String> = alloc::vec![];
                     $(
                         let metadata: $metadata_type = value.metadata;
                         annots = metadata.annotations().into_iter().map(|annot| annot.value().into()).collect();
@@ -231,7 +231,7 @@ macro_rules! make_instruction {
             impl From<&$name> for Micheline {
                 #[allow(unused)]
                 fn from(value: &$name) -> Self {
-                    let mut args: Vec<Micheline> = vec![];
+                    let mut args: alloc::vec::Vec<Micheline> = alloc::vec![];
                     $(
                         if let Some(value) = &value.$opt_field_name {
                             args.push(value.into());
@@ -243,7 +243,7 @@ macro_rules! make_instruction {
                     $(
                         args.push((&*value.$boxed_field_name).into());
                     )*
-                    let mut annots: Vec<String> = vec![];
+                    let mut annots: alloc::vec::Vec<alloc::string::String> = alloc::vec![];
                     $(
                         let metadata: &$metadata_type = &value.metadata;
                         annots = metadata.annotations().into_iter().map(|annot| annot.value().into()).collect();
@@ -265,7 +265,7 @@ macro_rules! make_instruction {
                     $(
                         let metadata: $metadata_type = (&value).try_into()?;
                     )?
-                    let mut args = value.into_args().unwrap_or(vec![]);
+                    let mut args = value.into_args().unwrap_or(alloc::vec![]);
                     Ok(Self {
                         $(
                             $opt_field_name: match args.get(0) {
@@ -278,7 +278,7 @@ macro_rules! make_instruction {
                             $field_name: if !args.is_empty() { args.remove(0).try_into()? } else { Err(Error::InvalidPrimitiveApplication)? },
                         )*
                         $(
-                            $boxed_field_name: if !args.is_empty() { Box::new(args.remove(0).try_into()?) } else { Err(Error::InvalidPrimitiveApplication)? },
+                            $boxed_field_name: if !args.is_empty() { alloc::boxed::Box::new(args.remove(0).try_into()?) } else { Err(Error::InvalidPrimitiveApplication)? },
                         )*
                         $(
                             metadata: metadata as $metadata_type,
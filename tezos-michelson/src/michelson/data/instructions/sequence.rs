@@ -4,6 +4,7 @@ use crate::{
     michelson::{data::Data, Michelson},
     Error, Result,
 };
+use alloc::vec::Vec;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Sequence(Vec<Instruction>);
@@ -20,6 +21,15 @@ impl Sequence {
     pub fn form(values: Vec<Instruction>) -> Self {
         Self(values)
     }
+
+    /// Typechecks the instructions against the types of the input stack, given from its top,
+    /// see [typecheck_code](crate::typechecker::typecheck_code).
+    pub fn typecheck(
+        &self,
+        stack: &[crate::michelson::types::Type],
+    ) -> Result<crate::typechecker::StackType> {
+        Instruction::Sequence(self.clone()).typecheck(stack)
+    }
 }
 
 impl From<Vec<Instruction>> for Sequence {
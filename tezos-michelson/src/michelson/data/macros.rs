@@ -227,7 +227,7 @@ macro_rules! make_data {
                     if value.prim() != Self::prim_value().name() {
                         return Err(Error::InvalidPrimitiveApplication);
                     }
-                    let mut args = value.into_args().unwrap_or(vec![]);
+                    let mut args = value.into_args().unwrap_or(alloc::vec![]);
                     Ok($name)
                 }
             }
@@ -270,19 +270,19 @@ macro_rules! make_data {
                     pub $opt_field_name: Option<$opt_field_type>,
                 )*
                 $(
-                    pub $boxed_field_name: Box<$boxed_field_type>,
+                    pub $boxed_field_name: alloc::boxed::Box<$boxed_field_type>,
                 )*
                 $(
-                    pub $vec_field_name: Vec<$vec_field_type>,
+                    pub $vec_field_name: alloc::vec::Vec<$vec_field_type>,
                 )*
             }
 
             impl $name {
-                pub fn new($($field_name: $field_type,)* $($opt_field_name: Option<$opt_field_type>,)* $($boxed_field_name: $boxed_field_type,)* $($vec_field_name: Vec<$vec_field_type>,)*) -> Self {
+                pub fn new($($field_name: $field_type,)* $($opt_field_name: Option<$opt_field_type>,)* $($boxed_field_name: $boxed_field_type,)* $($vec_field_name: alloc::vec::Vec<$vec_field_type>,)*) -> Self {
                     Self {
                         $($field_name,)*
                         $($opt_field_name,)*
-                        $($boxed_field_name: Box::new($boxed_field_name),)*
+                        $($boxed_field_name: alloc::boxed::Box::new($boxed_field_name),)*
                         $($vec_field_name,)*
                     }
                 }
@@ -342,7 +342,7 @@ macro_rules! make_data {
             impl From<$name> for Micheline {
 
                 fn from(value: $name) -> Self {
-                    let mut args: Vec<Micheline> = vec![];
+                    let mut args: alloc::vec::Vec<Micheline> = alloc::vec![];
                     $(
                         args.push(value.$field_name.into());
                     )*
@@ -355,7 +355,7 @@ macro_rules! make_data {
                         args.push((*value.$boxed_field_name).into());
                     )*
                     $(
-                        let mut values = value.$vec_field_name.into_iter().map(|value| value.into()).collect::<Vec<Micheline>>();
+                        let mut values = value.$vec_field_name.into_iter().map(|value| value.into()).collect::<alloc::vec::Vec<Micheline>>();
                         args.append(&mut values);
                     )*
 
@@ -368,7 +368,7 @@ macro_rules! make_data {
             impl From<&$name> for Micheline {
 
                 fn from(value: &$name) -> Self {
-                    let mut args: Vec<Micheline> = vec![];
+                    let mut args: alloc::vec::Vec<Micheline> = alloc::vec![];
                     $(
                         args.push(value.$field_name.into());
                     )*
@@ -381,7 +381,7 @@ macro_rules! make_data {
                         args.push((&*value.$boxed_field_name).into());
                     )*
                     $(
-                        let mut values = value.$vec_field_name.iter().map(|value| value.into()).collect::<Vec<Micheline>>();
+                        let mut values = value.$vec_field_name.iter().map(|value| value.into()).collect::<alloc::vec::Vec<Micheline>>();
                         args.append(&mut values);
                     )*
 
@@ -399,7 +399,7 @@ macro_rules! make_data {
                     if value.prim() != Self::prim_value().name() {
                         return Err(Error::InvalidPrimitiveApplication);
                     }
-                    let mut args = value.into_args().unwrap_or(vec![]);
+                    let mut args = value.into_args().unwrap_or(alloc::vec![]);
                     Ok(Self {
                         $(
                             $field_name: if !args.is_empty() { args.remove(0).try_into()? } else { Err(Error::InvalidPrimitiveApplication)? },
@@ -408,16 +408,16 @@ macro_rules! make_data {
                             $opt_field_name: if !args.is_empty() { Some(args.remove(0).try_into()?) } else { None },
                         )*
                         $(
-                            $boxed_field_name: if !args.is_empty() { Box::new(args.remove(0).try_into()?) } else { Err(Error::InvalidPrimitiveApplication)? },
+                            $boxed_field_name: if !args.is_empty() { alloc::boxed::Box::new(args.remove(0).try_into()?) } else { Err(Error::InvalidPrimitiveApplication)? },
                         )*
                         $(
-                            $vec_field_name: if !args.is_empty() { args.into_iter().map(|value| value.try_into()).collect::<Result<Vec<_>>>()? } else { Err(Error::InvalidPrimitiveApplication)? },
+                            $vec_field_name: if !args.is_empty() { args.into_iter().map(|value| value.try_into()).collect::<Result<alloc::vec::Vec<_>>>()? } else { Err(Error::InvalidPrimitiveApplication)? },
                         )*
                     })
                 }
             }
 
-            pub fn $mod_name<Output>($($field_name: $field_type,)* $($opt_field_name: Option<$opt_field_type>,)* $($boxed_field_name: $boxed_field_type,)* $($vec_field_name: Vec<$vec_field_type>,)*) -> Output where Output: From<$name> {
+            pub fn $mod_name<Output>($($field_name: $field_type,)* $($opt_field_name: Option<$opt_field_type>,)* $($boxed_field_name: $boxed_field_type,)* $($vec_field_name: alloc::vec::Vec<$vec_field_type>,)*) -> Output where Output: From<$name> {
                 $name::new(
                     $($field_name, )*
                     $($opt_field_name, )*
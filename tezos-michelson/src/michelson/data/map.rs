@@ -1,4 +1,5 @@
 use crate::{micheline::Micheline, michelson::Michelson, Error, Result};
+use alloc::vec::Vec;
 
 use super::{Data, Elt};
 
@@ -1,3 +1,4 @@
+use alloc::{string::String, vec, vec::Vec};
 use super::annotations::{Annotation, Kind};
 use crate::{micheline::primitive_application::PrimitiveApplication, Error, Result};
 
@@ -1,6 +1,7 @@
 mod comparables;
 mod macros;
 
+use core::{fmt, str::FromStr};
 use macros::{make_type, make_types};
 use tezos_core::internal::normalizer::Normalizer;
 
@@ -107,6 +108,20 @@ impl TryFrom<Michelson> for Type {
     }
 }
 
+impl FromStr for Type {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Micheline::parse(s)?.try_into()
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Micheline::from(Michelson::from(self.clone())).fmt(f)
+    }
+}
+
 impl From<Primitive> for crate::michelson::Primitive {
     fn from(value: Primitive) -> Self {
         Self::Type(value)
@@ -142,10 +142,10 @@ macro_rules! make_type {
                     pub $field_name: $field_type,
                 )*
                 $(
-                    pub $boxed_field_name: Box<$boxed_field_type>,
+                    pub $boxed_field_name: alloc::boxed::Box<$boxed_field_type>,
                 )*
                 $(
-                    pub $vec_field_name: Vec<$vec_field_type>,
+                    pub $vec_field_name: alloc::vec::Vec<$vec_field_type>,
                 )*
                 pub(crate) metadata: TypeFieldMetadata,
             }
@@ -155,26 +155,26 @@ macro_rules! make_type {
                     &self.metadata
                 }
 
-                pub fn annotations(&self) -> Vec<&Annotation> {
+                pub fn annotations(&self) -> alloc::vec::Vec<&Annotation> {
                     self.metadata.annotations()
                 }
 
-                pub fn new($($field_name: $field_type,)* $($boxed_field_name: $boxed_field_type,)* $($vec_field_name: Vec<$vec_field_type>,)* metadata: core::option::Option<TypeFieldMetadata>) -> Self {
+                pub fn new($($field_name: $field_type,)* $($boxed_field_name: $boxed_field_type,)* $($vec_field_name: alloc::vec::Vec<$vec_field_type>,)* metadata: core::option::Option<TypeFieldMetadata>) -> Self {
                     Self {
                         $($field_name,)*
-                        $($boxed_field_name: Box::new($boxed_field_name),)*
+                        $($boxed_field_name: alloc::boxed::Box::new($boxed_field_name),)*
                         $($vec_field_name,)*
                         metadata: metadata.unwrap_or_default()
                     }
                 }
 
-                pub fn with_type_annotation<Output>(mut self, annotation: std::string::String) -> Output where Output: From<$name> {
+                pub fn with_type_annotation<Output>(mut self, annotation: alloc::string::String) -> Output where Output: From<$name> {
                     self.metadata = self.metadata.with_type_name(annotation);
 
                     self.into()
                 }
 
-                pub fn with_field_annotation<Output>(mut self, annotation: std::string::String) -> Output where Output: From<$name> {
+                pub fn with_field_annotation<Output>(mut self, annotation: alloc::string::String) -> Output where Output: From<$name> {
                     self.metadata = self.metadata.with_field_name(annotation);
 
                     self.into()
@@ -248,16 +248,16 @@ macro_rules! make_type {
                         return Err(Error::InvalidPrimitiveApplication);
                     }
                     let metadata: TypeFieldMetadata = (&value).try_into()?;
-                    let mut args = value.into_args().unwrap_or(vec![]);
+                    let mut args = value.into_args().unwrap_or(alloc::vec![]);
                     Ok(Self {
                         $(
                             $field_name: if !args.is_empty() { args.remove(0).try_into()? } else { Err(Error::InvalidPrimitiveApplication)? },
                         )*
                         $(
-                            $boxed_field_name: if !args.is_empty() { Box::new(args.remove(0).try_into()?) } else { Err(Error::InvalidPrimitiveApplication)? },
+                            $boxed_field_name: if !args.is_empty() { alloc::boxed::Box::new(args.remove(0).try_into()?) } else { Err(Error::InvalidPrimitiveApplication)? },
                         )*
                         $(
-                            $vec_field_name: if !args.is_empty() { args.into_iter().map(|value| value.try_into()).collect::<Result<Vec<_>>>()? } else { Err(Error::InvalidPrimitiveApplication)? },
+                            $vec_field_name: if !args.is_empty() { args.into_iter().map(|value| value.try_into()).collect::<Result<alloc::vec::Vec<_>>>()? } else { Err(Error::InvalidPrimitiveApplication)? },
                         )*
                         metadata,
                     })
@@ -267,8 +267,8 @@ macro_rules! make_type {
             impl From<$name> for Micheline {
                 #[allow(unused)]
                 fn from(value: $name) -> Self {
-                    let mut args: Vec<Micheline> = vec![];
-                    let annots: Vec<std::string::String> = value.annotations().into_iter().map(|annot| annot.value().into()).collect();
+                    let mut args: alloc::vec::Vec<Micheline> = alloc::vec![];
+                    let annots: alloc::vec::Vec<alloc::string::String> = value.annotations().into_iter().map(|annot| annot.value().into()).collect();
                     $(
                         args.push(value.$field_name.into());
                     )*
@@ -276,7 +276,7 @@ macro_rules! make_type {
                         args.push((*value.$boxed_field_name).into());
                     )*
                     $(
-                        let mut values = value.$vec_field_name.into_iter().map(|value| value.into()).collect::<Vec<Micheline>>();
+                        let mut values = value.$vec_field_name.into_iter().map(|value| value.into()).collect::<alloc::vec::Vec<Micheline>>();
                         args.append(&mut values);
                     )*
                     let primitive_application = PrimitiveApplication::new($name::prim_value().name().into(), Some(args), Some(annots));
@@ -288,8 +288,8 @@ macro_rules! make_type {
             impl From<&$name> for Micheline {
                 #[allow(unused)]
                 fn from(value: &$name) -> Self {
-                    let mut args: Vec<Micheline> = vec![];
-                    let annots: Vec<std::string::String> = value.annotations().into_iter().map(|annot| annot.value().into()).collect();
+                    let mut args: alloc::vec::Vec<Micheline> = alloc::vec![];
+                    let annots: alloc::vec::Vec<alloc::string::String> = value.annotations().into_iter().map(|annot| annot.value().into()).collect();
                     $(
                         args.push((&value.$field_name).into());
                     )*
@@ -297,7 +297,7 @@ macro_rules! make_type {
                         args.push((&*value.$boxed_field_name).into());
                     )*
                     $(
-                        let mut values = value.$vec_field_name.iter().map(|value| value.into()).collect::<Vec<Micheline>>();
+                        let mut values = value.$vec_field_name.iter().map(|value| value.into()).collect::<alloc::vec::Vec<Micheline>>();
                         args.append(&mut values);
                     )*
                     let primitive_application = PrimitiveApplication::new($name::prim_value().name().into(), Some(args), Some(annots));
@@ -306,7 +306,7 @@ macro_rules! make_type {
                 }
             }
 
-            pub fn $code<Output>($($field_name: $field_type,)* $($boxed_field_name: $boxed_field_type,)* $($vec_field_name: Vec<$vec_field_type>,)*) -> Output where Output: From<$name> {
+            pub fn $code<Output>($($field_name: $field_type,)* $($boxed_field_name: $boxed_field_type,)* $($vec_field_name: alloc::vec::Vec<$vec_field_type>,)*) -> Output where Output: From<$name> {
                 $name::new(
                     $($field_name,)*
                     $($boxed_field_name,)*
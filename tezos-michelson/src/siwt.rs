@@ -0,0 +1,627 @@
+//! Sign-In-With-Tezos (SIWT) messages.
+//!
+//! A [SignInMessage] is a human readable `Tezos Signed Message` that binds a wallet address to a domain, a one-time nonce
+//! and a validity window. Wallets sign the message as a packed Micheline string, so the signature can be verified with
+//! the same rules as any other signed Micheline expression. Some applications have the raw bytes of the message
+//! signed instead, see [SignInPayloadFormat].
+
+use std::{collections::HashMap, fmt, str::FromStr};
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use tezos_core::{
+    types::encoded::{Encoded, ImplicitAddress, PublicKey, Signature},
+    Tezos,
+};
+
+use crate::{
+    internal::signer::ExpressionSigner,
+    micheline::{bytes, literals::Literal, try_string, Micheline},
+    Error, Result,
+};
+
+/// Storage keeping track of the nonces that have already been used to sign in.
+///
+/// Implementations may also reject nonces that have never been issued by the application.
+pub trait NonceStore {
+    /// Marks the nonce as used. Returns `false` if the nonce cannot be used, e.g. because it has already been consumed.
+    ///
+    /// The expiration time of the message is provided so that the nonce can be forgotten once the message has expired.
+    fn consume(&mut self, nonce: &str, expiration_time: &DateTime<Utc>) -> bool;
+}
+
+/// A [NonceStore] keeping the used nonces in memory.
+#[derive(Debug, Default, Clone)]
+pub struct InMemoryNonceStore {
+    used: HashMap<String, DateTime<Utc>>,
+}
+
+impl InMemoryNonceStore {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Forgets the nonces of messages that have expired at the given time. Such messages are rejected anyway.
+    pub fn prune(&mut self, now: &DateTime<Utc>) {
+        self.used
+            .retain(|_, expiration_time| *expiration_time > *now);
+    }
+}
+
+impl NonceStore for InMemoryNonceStore {
+    fn consume(&mut self, nonce: &str, expiration_time: &DateTime<Utc>) -> bool {
+        if self.used.contains_key(nonce) {
+            return false;
+        }
+        self.used.insert(nonce.into(), *expiration_time);
+        true
+    }
+}
+
+/// The form of the message signed by the wallet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SignInPayloadFormat {
+    /// The packed Micheline string `0x05 0x01 <length> "Tezos Signed Message: ..."`, e.g. the `MICHELINE` signing type of Beacon.
+    #[default]
+    Micheline,
+    /// The UTF-8 bytes of the message, e.g. the `RAW` signing type of Beacon.
+    Raw,
+}
+
+/// A Sign-In-With-Tezos message.
+///
+/// # Example
+///
+/// ```rust
+/// use chrono::{TimeZone, Utc};
+/// use tezos_michelson::siwt::SignInMessage;
+///
+/// let message = SignInMessage::new(
+///     "example.xyz",
+///     "tz1RFxSHjxxyaaXyAvJG3uRQeJUCCm4jk2LX".try_into().unwrap(),
+///     "k8Xq3nNf0a9Z",
+///     Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2021, 1, 1, 0, 5, 0).unwrap(),
+/// )
+/// .expect("valid message");
+///
+/// let payload = message.to_payload().expect("valid payload"); // the bytes to be signed by the wallet
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignInMessage {
+    domain: String,
+    address: ImplicitAddress,
+    nonce: String,
+    issued_at: DateTime<Utc>,
+    expiration_time: DateTime<Utc>,
+}
+
+impl SignInMessage {
+    const PREFIX: &'static str = "Tezos Signed Message:";
+    const ADDRESS_LABEL: &'static str = "Address:";
+    const NONCE_LABEL: &'static str = "Nonce:";
+    const EXPIRATION_TIME_LABEL: &'static str = "Expiration Time:";
+    const MIN_NONCE_LENGTH: usize = 8;
+
+    /// Creates a new [SignInMessage].
+    ///
+    /// The domain must not be empty nor contain whitespaces, the nonce must be at least 8 alphanumeric characters long
+    /// and the message must expire after it has been issued.
+    pub fn new(
+        domain: &str,
+        address: ImplicitAddress,
+        nonce: &str,
+        issued_at: DateTime<Utc>,
+        expiration_time: DateTime<Utc>,
+    ) -> Result<Self> {
+        if domain.is_empty() || domain.contains(char::is_whitespace) {
+            return Err(Error::InvalidSignInMessage {
+                description: format!("invalid domain `{}`", domain),
+            });
+        }
+        if nonce.len() < Self::MIN_NONCE_LENGTH || !nonce.chars().all(|c| c.is_ascii_alphanumeric())
+        {
+            return Err(Error::InvalidSignInMessage {
+                description: format!("invalid nonce `{}`", nonce),
+            });
+        }
+        if expiration_time <= issued_at {
+            return Err(Error::InvalidSignInMessage {
+                description: "expiration time must be later than issued at".into(),
+            });
+        }
+
+        Ok(Self {
+            domain: domain.into(),
+            address,
+            nonce: nonce.into(),
+            issued_at,
+            expiration_time,
+        })
+    }
+
+    pub fn domain(&self) -> &str {
+        &self.domain
+    }
+
+    pub fn address(&self) -> &ImplicitAddress {
+        &self.address
+    }
+
+    pub fn nonce(&self) -> &str {
+        &self.nonce
+    }
+
+    pub fn issued_at(&self) -> &DateTime<Utc> {
+        &self.issued_at
+    }
+
+    pub fn expiration_time(&self) -> &DateTime<Utc> {
+        &self.expiration_time
+    }
+
+    /// Checks if the message is valid at the given time.
+    pub fn is_valid_at(&self, time: &DateTime<Utc>) -> bool {
+        &self.issued_at <= time && time < &self.expiration_time
+    }
+
+    /// Returns the message as a Micheline string.
+    pub fn to_micheline(&self) -> Result<Micheline> {
+        try_string(self.to_string())
+    }
+
+    /// Returns the packed Micheline string (starting with the `0x05` watermark) that wallets sign.
+    pub fn to_payload(&self) -> Result<Vec<u8>> {
+        self.to_payload_as(SignInPayloadFormat::Micheline)
+    }
+
+    /// Returns the bytes that wallets sign in the given format.
+    pub fn to_payload_as(&self, format: SignInPayloadFormat) -> Result<Vec<u8>> {
+        match format {
+            SignInPayloadFormat::Micheline => self.to_micheline()?.pack(None),
+            SignInPayloadFormat::Raw => Ok(self.to_string().into_bytes()),
+        }
+    }
+
+    /// Returns the payload in the given format as a Micheline bytes literal, the form in which applications
+    /// usually send the payload to the wallets.
+    ///
+    /// The message can be read back from the bytes with [SignInMessage::try_from].
+    pub fn to_payload_micheline(&self, format: SignInPayloadFormat) -> Result<Micheline> {
+        Ok(bytes(self.to_payload_as(format)?))
+    }
+
+    /// Verifies the signature of the message using the crypto providers configured in the given [Tezos] instance.
+    ///
+    /// The message is accepted only if the public key matches its address, it is valid at the given time, the signature
+    /// is valid and its nonce is consumed successfully from the [NonceStore].
+    pub fn verify_with<S: NonceStore>(
+        &self,
+        signature: &Signature,
+        public_key: &PublicKey,
+        now: &DateTime<Utc>,
+        nonces: &mut S,
+        tezos: &Tezos,
+    ) -> Result<()> {
+        self.verify_payload_with(
+            SignInPayloadFormat::Micheline,
+            signature,
+            public_key,
+            now,
+            nonces,
+            tezos,
+        )
+    }
+
+    /// Verifies the signature of the payload of the message in the given format, using the crypto providers configured
+    /// in the given [Tezos] instance.
+    ///
+    /// The message is accepted with the same rules as [SignInMessage::verify_with].
+    pub fn verify_payload_with<S: NonceStore>(
+        &self,
+        format: SignInPayloadFormat,
+        signature: &Signature,
+        public_key: &PublicKey,
+        now: &DateTime<Utc>,
+        nonces: &mut S,
+        tezos: &Tezos,
+    ) -> Result<()> {
+        if public_key.public_key_hash()? != self.address {
+            return Err(Error::SignInAddressMismatch);
+        }
+        if !self.is_valid_at(now) {
+            return Err(Error::SignInMessageExpired);
+        }
+        if !ExpressionSigner::new(tezos.get_crypto()).verify(
+            &self.to_payload_as(format)?,
+            signature,
+            public_key,
+        )? {
+            return Err(Error::InvalidSignature);
+        }
+        if !nonces.consume(&self.nonce, &self.expiration_time) {
+            return Err(Error::SignInNonceAlreadyUsed);
+        }
+
+        Ok(())
+    }
+
+    /// Verifies the signature of the message.
+    ///
+    /// The message is accepted only if the public key matches its address, it is valid at the given time, the signature
+    /// is valid and its nonce is consumed successfully from the [NonceStore].
+    pub fn verify<S: NonceStore>(
+        &self,
+        signature: &Signature,
+        public_key: &PublicKey,
+        now: &DateTime<Utc>,
+        nonces: &mut S,
+    ) -> Result<()> {
+        let tezos: Tezos = Default::default();
+
+        self.verify_with(signature, public_key, now, nonces, &tezos)
+    }
+
+    /// Verifies the signature of the payload of the message in the given format.
+    ///
+    /// The message is accepted with the same rules as [SignInMessage::verify].
+    pub fn verify_payload<S: NonceStore>(
+        &self,
+        format: SignInPayloadFormat,
+        signature: &Signature,
+        public_key: &PublicKey,
+        now: &DateTime<Utc>,
+        nonces: &mut S,
+    ) -> Result<()> {
+        let tezos: Tezos = Default::default();
+
+        self.verify_payload_with(format, signature, public_key, now, nonces, &tezos)
+    }
+
+    fn format_time(time: &DateTime<Utc>) -> String {
+        time.to_rfc3339_opts(SecondsFormat::Secs, true)
+    }
+
+    fn parse_time(value: &str) -> Result<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(value)
+            .map(|time| time.with_timezone(&Utc))
+            .map_err(|_| Error::InvalidSignInMessage {
+                description: format!("invalid time `{}`", value),
+            })
+    }
+}
+
+impl fmt::Display for SignInMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {} {} {} {} {} {}",
+            Self::PREFIX,
+            self.domain,
+            Self::format_time(&self.issued_at),
+            Self::ADDRESS_LABEL,
+            self.address.value(),
+            Self::NONCE_LABEL,
+            self.nonce,
+            Self::EXPIRATION_TIME_LABEL,
+            Self::format_time(&self.expiration_time),
+        )
+    }
+}
+
+impl FromStr for SignInMessage {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || Error::InvalidSignInMessage {
+            description: s.into(),
+        };
+        let rest = s.strip_prefix(Self::PREFIX).ok_or_else(invalid)?;
+        let (domain, rest) = rest.trim_start().split_once(' ').ok_or_else(invalid)?;
+        let (issued_at, rest) = rest.split_once(' ').ok_or_else(invalid)?;
+        let rest = rest.strip_prefix(Self::ADDRESS_LABEL).ok_or_else(invalid)?;
+        let (address, rest) = rest.trim_start().split_once(' ').ok_or_else(invalid)?;
+        let rest = rest.strip_prefix(Self::NONCE_LABEL).ok_or_else(invalid)?;
+        let (nonce, rest) = rest.trim_start().split_once(' ').ok_or_else(invalid)?;
+        let expiration_time = rest
+            .strip_prefix(Self::EXPIRATION_TIME_LABEL)
+            .ok_or_else(invalid)?
+            .trim_start();
+
+        Self::new(
+            domain,
+            address.try_into()?,
+            nonce,
+            Self::parse_time(issued_at)?,
+            Self::parse_time(expiration_time)?,
+        )
+    }
+}
+
+impl TryFrom<&str> for SignInMessage {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self> {
+        value.parse()
+    }
+}
+
+impl TryFrom<&[u8]> for SignInMessage {
+    type Error = Error;
+
+    /// Reads the message from a payload in any [SignInPayloadFormat].
+    fn try_from(value: &[u8]) -> Result<Self> {
+        if value.starts_with(&[5]) {
+            return match Micheline::unpack(value, None)? {
+                Micheline::Literal(Literal::String(value)) => value.to_str().parse(),
+                _ => Err(Error::InvalidSignInMessage {
+                    description: "expected a packed Micheline string".into(),
+                }),
+            };
+        }
+        core::str::from_utf8(value)
+            .map_err(|_| Error::InvalidSignInMessage {
+                description: "expected a UTF-8 message".into(),
+            })?
+            .parse()
+    }
+}
+
+impl TryFrom<&Micheline> for SignInMessage {
+    type Error = Error;
+
+    /// Reads the message from a Micheline string, or from Micheline bytes holding a payload in any
+    /// [SignInPayloadFormat].
+    fn try_from(value: &Micheline) -> Result<Self> {
+        match value {
+            Micheline::Literal(Literal::String(value)) => value.to_str().parse(),
+            Micheline::Literal(Literal::Bytes(value)) => {
+                let payload: Vec<u8> = value.into();
+                payload.as_slice().try_into()
+            }
+            _ => Err(Error::InvalidSignInMessage {
+                description: "expected a Micheline string or bytes".into(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn message() -> SignInMessage {
+        SignInMessage::new(
+            "example.xyz",
+            "tz1RFxSHjxxyaaXyAvJG3uRQeJUCCm4jk2LX".try_into().unwrap(),
+            "k8Xq3nNf0a9Z",
+            Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2021, 1, 1, 0, 5, 0).unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_to_string() -> Result<()> {
+        let value = "Tezos Signed Message: example.xyz 2021-01-01T00:00:00Z Address: tz1RFxSHjxxyaaXyAvJG3uRQeJUCCm4jk2LX Nonce: k8Xq3nNf0a9Z Expiration Time: 2021-01-01T00:05:00Z";
+
+        assert_eq!(message().to_string(), value);
+        assert_eq!(SignInMessage::try_from(value)?, message());
+        assert_eq!(
+            SignInMessage::try_from(&message().to_micheline()?)?,
+            message()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_payload() -> Result<()> {
+        let payload = message().to_payload()?;
+
+        assert_eq!(&payload[..2], &[5, 1]);
+        assert_eq!(
+            u32::from_be_bytes(payload[2..6].try_into().unwrap()) as usize,
+            message().to_string().len()
+        );
+        assert_eq!(&payload[6..], message().to_string().as_bytes());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_micheline_bytes_payload() -> Result<()> {
+        let payload = message().to_payload_micheline(SignInPayloadFormat::Micheline)?;
+        let mut expected = vec![5u8, 1];
+        expected.extend((message().to_string().len() as u32).to_be_bytes());
+        expected.extend(message().to_string().as_bytes());
+
+        assert_eq!(payload, bytes::<_, Micheline>(expected.clone()));
+        assert_eq!(SignInMessage::try_from(&payload)?, message());
+        assert_eq!(SignInMessage::try_from(expected.as_slice())?, message());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_raw_bytes_payload() -> Result<()> {
+        let payload = message().to_payload_micheline(SignInPayloadFormat::Raw)?;
+        let expected = message().to_string().into_bytes();
+
+        assert_eq!(payload, bytes::<_, Micheline>(expected.clone()));
+        assert_eq!(SignInMessage::try_from(&payload)?, message());
+        assert_eq!(SignInMessage::try_from(expected.as_slice())?, message());
+        assert!(SignInMessage::try_from([0xffu8, 0xfe].as_slice()).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_message() {
+        let address: ImplicitAddress = "tz1RFxSHjxxyaaXyAvJG3uRQeJUCCm4jk2LX".try_into().unwrap();
+        let issued_at = Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap();
+        let expiration_time = Utc.with_ymd_and_hms(2021, 1, 1, 0, 5, 0).unwrap();
+
+        assert!(SignInMessage::new(
+            "example xyz",
+            address.clone(),
+            "k8Xq3nNf0a9Z",
+            issued_at,
+            expiration_time
+        )
+        .is_err());
+        assert!(SignInMessage::new(
+            "example.xyz",
+            address.clone(),
+            "k8Xq",
+            issued_at,
+            expiration_time
+        )
+        .is_err());
+        assert!(SignInMessage::new(
+            "example.xyz",
+            address.clone(),
+            "k8Xq-3nNf0a9Z",
+            issued_at,
+            expiration_time
+        )
+        .is_err());
+        assert!(SignInMessage::new(
+            "example.xyz",
+            address,
+            "k8Xq3nNf0a9Z",
+            expiration_time,
+            issued_at
+        )
+        .is_err());
+        assert!(SignInMessage::try_from(
+            "Tezos Signed Message: example.xyz 2021-01-01T00:00:00Z Hello"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_in_memory_nonce_store() {
+        let mut nonces = InMemoryNonceStore::new();
+        let expiration_time = Utc.with_ymd_and_hms(2021, 1, 1, 0, 5, 0).unwrap();
+
+        assert!(nonces.consume("k8Xq3nNf0a9Z", &expiration_time));
+        assert!(!nonces.consume("k8Xq3nNf0a9Z", &expiration_time));
+
+        nonces.prune(&Utc.with_ymd_and_hms(2021, 1, 1, 0, 4, 0).unwrap());
+        assert!(!nonces.consume("k8Xq3nNf0a9Z", &expiration_time));
+
+        nonces.prune(&expiration_time);
+        assert!(nonces.consume("k8Xq3nNf0a9Z", &expiration_time));
+    }
+
+    #[cfg(feature = "ed25519")]
+    #[test]
+    fn test_verify() -> Result<()> {
+        use tezos_core::types::encoded::SecretKey;
+
+        let secret_key: SecretKey = "edskRv7VyXGVZb8EsrR7D9XKUbbAQNQGtALP6QeB16ZCD7SmmJpzyeneJVg3Mq56YLbxRA1kSdAXiswwPiaVfR3NHGMCXCziuZ".try_into()?;
+        let public_key: PublicKey =
+            "edpkttZKC51wemRqL2QxwpMnEKxWnbd35pq47Y6xsCHp5M1f7LN8NP".try_into()?;
+        let other_public_key: PublicKey =
+            "edpkvGfYw3LyB1UcCahKQk4rF2tvbMUk8GFiTuMjL75uGXrpvKXhjn".try_into()?;
+        let now = Utc.with_ymd_and_hms(2021, 1, 1, 0, 1, 0).unwrap();
+        let message = message();
+        let signature = message.to_micheline()?.sign(None, &secret_key)?;
+        let mut nonces = InMemoryNonceStore::new();
+
+        assert!(matches!(
+            message.verify(&signature, &other_public_key, &now, &mut nonces),
+            Err(Error::SignInAddressMismatch)
+        ));
+        assert!(matches!(
+            message.verify(
+                &signature,
+                &public_key,
+                &Utc.with_ymd_and_hms(2021, 1, 1, 0, 5, 0).unwrap(),
+                &mut nonces
+            ),
+            Err(Error::SignInMessageExpired)
+        ));
+        assert!(matches!(
+            message.verify(
+                &"edsigtmo6q8BrJSBhFeTcqwq4h47URASkVkbYKv28fJwcUYV4uoNdMhCnxmx3qViXHyTxjLVYRWGvM6wd8quyPSSGRGDdBMvsLn".try_into()?,
+                &public_key,
+                &now,
+                &mut nonces
+            ),
+            Err(Error::InvalidSignature)
+        ));
+        message.verify(&signature, &public_key, &now, &mut nonces)?;
+        assert!(matches!(
+            message.verify(&signature, &public_key, &now, &mut nonces),
+            Err(Error::SignInNonceAlreadyUsed)
+        ));
+
+        Ok(())
+    }
+
+    #[cfg(feature = "ed25519")]
+    #[test]
+    fn test_verify_payload() -> Result<()> {
+        use tezos_core::types::encoded::SecretKey;
+
+        let secret_key: SecretKey = "edskRv7VyXGVZb8EsrR7D9XKUbbAQNQGtALP6QeB16ZCD7SmmJpzyeneJVg3Mq56YLbxRA1kSdAXiswwPiaVfR3NHGMCXCziuZ".try_into()?;
+        let public_key: PublicKey =
+            "edpkttZKC51wemRqL2QxwpMnEKxWnbd35pq47Y6xsCHp5M1f7LN8NP".try_into()?;
+        let now = Utc.with_ymd_and_hms(2021, 1, 1, 0, 1, 0).unwrap();
+        let message = message();
+        let signer = ExpressionSigner::new(Tezos::default().get_crypto());
+
+        let micheline_signature = signer.sign(
+            &message.to_payload_as(SignInPayloadFormat::Micheline)?,
+            &secret_key,
+        )?;
+        assert_eq!(
+            micheline_signature,
+            message.to_micheline()?.sign(None, &secret_key)?
+        );
+        let raw_signature = signer.sign(
+            &message.to_payload_as(SignInPayloadFormat::Raw)?,
+            &secret_key,
+        )?;
+
+        assert!(matches!(
+            message.verify_payload(
+                SignInPayloadFormat::Micheline,
+                &raw_signature,
+                &public_key,
+                &now,
+                &mut InMemoryNonceStore::new()
+            ),
+            Err(Error::InvalidSignature)
+        ));
+        assert!(matches!(
+            message.verify_payload(
+                SignInPayloadFormat::Raw,
+                &micheline_signature,
+                &public_key,
+                &now,
+                &mut InMemoryNonceStore::new()
+            ),
+            Err(Error::InvalidSignature)
+        ));
+        message.verify_payload(
+            SignInPayloadFormat::Micheline,
+            &micheline_signature,
+            &public_key,
+            &now,
+            &mut InMemoryNonceStore::new(),
+        )?;
+        message.verify_payload(
+            SignInPayloadFormat::Raw,
+            &raw_signature,
+            &public_key,
+            &now,
+            &mut InMemoryNonceStore::new(),
+        )?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,1196 @@
+//! Typechecking of Michelson values against their types, and of Michelson code against its input stack.
+//!
+//! The errors point at the offending part of the value or of the code with a path starting at `$`,
+//! e.g. `$.1.left[2].key` or `$.code[3].1[0]`:
+//! - `.0`, `.1`, ... select an element of a right comb of pairs, counted from its leftmost element,
+//!   or an argument of an instruction, e.g. `.1` is the `else` branch of `IF`,
+//! - `.some`, `.left` and `.right` select the argument of `Some`, `Left` and `Right`,
+//! - `[i]` selects an element of a list, a set, a map or a sequence of instructions, and `.key` and `.value`
+//!   select the parts of a map entry,
+//! - `.parameter`, `.storage`, `.code` and `.view.<name>` select the sections of a script.
+
+mod code;
+mod data;
+mod types;
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+
+use self::{
+    code::{Context, Stack},
+    types::Ty,
+};
+use crate::{micheline::Micheline, Error, Result};
+
+/// The type of the stack resulting from a sequence of instructions.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StackType {
+    /// The types of the stack elements, starting from the top of the stack.
+    Typed(Vec<Micheline>),
+    /// The instructions always fail, e.g. end with `FAILWITH`, so the stack can be given any type.
+    Failed,
+}
+
+/// Checks that `value` is a valid value of `type`.
+///
+/// Besides the shape of the value, it checks that:
+/// - the `nat`, `mutez`, `string` and `timestamp` literals are in range,
+/// - the `address`, `contract`, `key_hash`, `key`, `signature` and `chain_id` literals decode,
+/// - the entrypoints of the `address` and `contract` literals are valid and not `%default`, which is implicit,
+/// - there are no `ticket` values, which can only be created by `TICKET`,
+/// - the elements of sets and the keys of maps are sorted in strictly increasing order,
+/// - the `set`, `map`, `big_map` and `ticket` types are applied to comparable types,
+/// - the memo sizes of the `sapling_state` and `sapling_transaction` types fit in 16 bits.
+///
+/// ```rust
+/// use tezos_michelson::{micheline::Micheline, typechecker::typecheck_data};
+///
+/// let r#type = Micheline::parse("map string (pair address nat)").unwrap();
+///
+/// let value = Micheline::parse(r#"{ Elt "a" (Pair "tz1fJGtrdmckD3VkiDxqUEci5h4gGcvocw6e" 1) }"#).unwrap();
+/// assert!(typecheck_data(&value, &r#type).is_ok());
+///
+/// let value = Micheline::parse(r#"{ Elt "b" (Pair "tz1fJGtrdmckD3VkiDxqUEci5h4gGcvocw6e" 1) ; Elt "a" (Pair "tz1fJGtrdmckD3VkiDxqUEci5h4gGcvocw6e" -1) }"#).unwrap();
+/// let error = typecheck_data(&value, &r#type).unwrap_err();
+/// assert_eq!(error.to_string(), "map keys are not in strictly increasing order at $[1]");
+/// ```
+pub fn typecheck_data(value: &Micheline, r#type: &Micheline) -> Result<()> {
+    let path = Path::root();
+    let ty = Ty::parse(r#type, &path)?;
+    data::check(value, &ty, &path).map(|_| ())
+}
+
+/// Typechecks `code`, either a sequence of instructions or a single instruction, against the types of the input
+/// `stack`, given from its top, and returns the type of the resulting stack.
+///
+/// The code is checked the way the node does it, except for the instructions which need the context of a contract:
+/// `SELF` is rejected, as in lambdas, use [typecheck_script] to check the code of a contract. Macros are expanded
+/// first (see [Micheline::expand_macros]), so the error paths point into the expanded code.
+///
+/// ```rust
+/// use tezos_michelson::{micheline::Micheline, typechecker::{typecheck_code, StackType}};
+///
+/// let code = Micheline::parse("{ UNPAIR ; ADD ; NIL operation ; PAIR }").unwrap();
+/// let stack = vec![Micheline::parse("pair nat nat").unwrap()];
+/// assert_eq!(
+///     typecheck_code(&code, &stack).unwrap(),
+///     StackType::Typed(vec![Micheline::parse("pair (list operation) nat").unwrap()]),
+/// );
+///
+/// let code = Micheline::parse("{ UNPAIR ; ADD ; PUSH string \"a\" ; ADD }").unwrap();
+/// let error = typecheck_code(&code, &stack).unwrap_err();
+/// assert_eq!(error.to_string(), "ADD is not defined for string : nat at $[3]");
+/// ```
+pub fn typecheck_code(code: &Micheline, stack: &[Micheline]) -> Result<StackType> {
+    let path = Path::root();
+    let stack = stack
+        .iter()
+        .rev()
+        .map(|ty| Ty::parse(ty, &path))
+        .collect::<Result<Stack>>()?;
+    let code = code.clone().expand_macros()?;
+    let stack = code::check_code(&code, stack, Context::lambda(), &path)?;
+    Ok(match stack {
+        Some(stack) => StackType::Typed(stack.iter().rev().map(Micheline::from).collect()),
+        None => StackType::Failed,
+    })
+}
+
+/// Typechecks a script, i.e. a sequence of the `parameter`, `storage` and `code` sections, followed by optional
+/// `view` sections, as [typecheck_code] does it for the code, and checks that:
+/// - the parameter type doesn't contain operations and its entrypoints are unique,
+/// - the storage type doesn't contain operations or contracts,
+/// - the code turns `pair parameter storage` into `pair (list operation) storage`,
+/// - the code of each view turns `pair argument storage` into its result, without emitting operations.
+///
+/// ```rust
+/// use tezos_michelson::{micheline::Micheline, typechecker::typecheck_script};
+///
+/// let script = Micheline::parse_script(
+///     "parameter (or (int %increment) (unit %reset)) ; storage int ; code { UNPAIR ; IF_LEFT { ADD } { DROP 2 ; PUSH int 0 } ; NIL operation ; PAIR }",
+/// ).unwrap();
+/// assert!(typecheck_script(&script).is_ok());
+/// ```
+pub fn typecheck_script(script: &Micheline) -> Result<()> {
+    let script = script.clone().expand_macros()?;
+    code::check_script(&script, &Path::root()).map(|_| ())
+}
+
+/// A location in a value or in code, see the [module documentation](self).
+#[derive(Debug, Clone)]
+pub(crate) struct Path(String);
+
+impl Path {
+    fn root() -> Self {
+        Self("$".into())
+    }
+
+    fn field<T: fmt::Display>(&self, name: T) -> Self {
+        Self(format!("{}.{}", self.0, name))
+    }
+
+    fn index(&self, index: usize) -> Self {
+        Self(format!("{}[{}]", self.0, index))
+    }
+}
+
+impl fmt::Display for Path {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+fn error<S: ToString>(path: &Path, description: S) -> Error {
+    Error::TypecheckFailed {
+        path: path.to_string(),
+        description: description.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn typecheck(value: &str, r#type: &str) -> Result<()> {
+        typecheck_data(&Micheline::parse(value)?, &Micheline::parse(r#type)?)
+    }
+
+    fn typecheck_error(value: &str, r#type: &str) -> (String, String) {
+        match typecheck(value, r#type) {
+            Err(Error::TypecheckFailed { path, description }) => (path, description),
+            result => panic!("expected a typecheck error for {}, got {:?}", value, result),
+        }
+    }
+
+    #[test]
+    fn test_typecheck_valid_data() -> Result<()> {
+        let values = [
+            ("Unit", "unit"),
+            ("True", "bool"),
+            ("-3", "int"),
+            ("3", "nat"),
+            ("9223372036854775807", "mutez"),
+            (r#""Hello\nWorld""#, "string"),
+            ("0x00ff", "bytes"),
+            ("1640995200", "timestamp"),
+            (r#""2022-01-01T00:00:00Z""#, "timestamp"),
+            (r#""tz1fJGtrdmckD3VkiDxqUEci5h4gGcvocw6e""#, "address"),
+            (
+                r#""KT1BEqzn5Wx8uJrZNvuS9DVHmLvG9td3fDLi%transfer""#,
+                "address",
+            ),
+            ("0x0000e7670f32038107a59a2b9cfefae36ea21f5aa63c", "address"),
+            (r#""sr1Ghq66tYK9y3r8CC1Tf8i8m5nxh8nTvZEf""#, "address"),
+            (
+                r#""sr1Ghq66tYK9y3r8CC1Tf8i8m5nxh8nTvZEf%deposit""#,
+                "address",
+            ),
+            ("0x0374f8952e7a287d78e8dceec67547bd00a278abbf00", "address"),
+            (
+                "0x01bf5e1f0aa0fdd4e82e4ae4fa2f5ac22a8fd4ec9200",
+                "contract unit",
+            ),
+            (r#""tz1fJGtrdmckD3VkiDxqUEci5h4gGcvocw6e""#, "key_hash"),
+            (
+                r#""edpkttZKC51wemRqL2QxwpMnEKxWnbd35pq47Y6xsCHp5M1f7LN8NP""#,
+                "key",
+            ),
+            (
+                r#""edsigtmo6q8BrJSBhFeTcqwq4h47URASkVkbYKv28fJwcUYV4uoNdMhCnxmx3qViXHyTxjLVYRWGvM6wd8quyPSSGRGDdBMvsLn""#,
+                "signature",
+            ),
+            (r#""NetXdQprcVkpaWU""#, "chain_id"),
+            ("0x7a06a770", "chain_id"),
+            ("Some (Left 1)", "option (or nat string)"),
+            ("None", "option never"),
+            ("Pair 1 2 3", "pair nat nat nat"),
+            ("Pair 1 (Pair 2 3)", "pair nat nat nat"),
+            ("{ 1 ; 2 ; 3 }", "pair nat (pair nat nat)"),
+            ("{ 3 ; 1 ; 2 }", "list nat"),
+            ("{ 1 ; 2 ; 3 }", "set nat"),
+            ("{ None ; Some 0 ; Some 1 }", "set (option nat)"),
+            ("{ Left 5 ; Right 1 }", "set (or nat nat)"),
+            ("{ Pair 1 2 ; Pair 1 3 ; Pair 2 0 }", "set (pair nat nat)"),
+            (r#"{ Elt "a" 1 ; Elt "b" 2 }"#, "map string nat"),
+            ("{}", "map string nat"),
+            ("42", "big_map string nat"),
+            (r#"{ Elt "a" 1 }"#, "big_map string nat"),
+            ("{}", "lambda unit unit"),
+            ("{ DROP ; UNIT ; { } }", "lambda nat unit"),
+            ("Lambda_rec { DIP { DROP } }", "lambda nat nat"),
+            ("{}", "sapling_state 8"),
+            ("0x01", "bls12_381_fr"),
+            ("1", "bls12_381_fr"),
+        ];
+        for (value, r#type) in values {
+            assert!(
+                typecheck(value, r#type).is_ok(),
+                "{} : {} -> {:?}",
+                value,
+                r#type,
+                typecheck(value, r#type)
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_typecheck_invalid_data() {
+        let values = [
+            ("1", "unit", "$", "expected unit, got 1"),
+            ("-1", "nat", "$", "invalid nat -1"),
+            (
+                "9223372036854775808",
+                "mutez",
+                "$",
+                "invalid mutez 9223372036854775808",
+            ),
+            (
+                r#""a\tb""#,
+                "string",
+                "$",
+                "invalid character '\\t' in string",
+            ),
+            (
+                r#""2022-13-01T00:00:00Z""#,
+                "timestamp",
+                "$",
+                r#"invalid timestamp "2022-13-01T00:00:00Z""#,
+            ),
+            (r#""tz1foo""#, "address", "$", r#"invalid address "tz1foo""#),
+            ("0x00", "address", "$", "invalid address 0x00"),
+            (
+                "0x0374f8952e7a287d78e8dceec67547bd00a278abbf01",
+                "address",
+                "$",
+                "invalid address 0x0374f8952e7a287d78e8dceec67547bd00a278abbf01",
+            ),
+            (
+                r#""KT1BEqzn5Wx8uJrZNvuS9DVHmLvG9td3fDLi%a-b""#,
+                "address",
+                "$",
+                "invalid entrypoint a-b",
+            ),
+            (
+                r#""KT1BEqzn5Wx8uJrZNvuS9DVHmLvG9td3fDLi%""#,
+                "address",
+                "$",
+                "empty entrypoint",
+            ),
+            (
+                r#""KT1BEqzn5Wx8uJrZNvuS9DVHmLvG9td3fDLi%default""#,
+                "address",
+                "$",
+                "entrypoint %default must be omitted in an address",
+            ),
+            (
+                "0x01bf5e1f0aa0fdd4e82e4ae4fa2f5ac22a8fd4ec920064656661756c74",
+                "address",
+                "$",
+                "entrypoint %default must be omitted in an address",
+            ),
+            (
+                r#""KT1BEqzn5Wx8uJrZNvuS9DVHmLvG9td3fDLi""#,
+                "key_hash",
+                "$",
+                r#"invalid key_hash "KT1BEqzn5Wx8uJrZNvuS9DVHmLvG9td3fDLi""#,
+            ),
+            (r#""edpk""#, "key", "$", r#"invalid key "edpk""#),
+            ("0x00", "key", "$", "invalid key 0x00"),
+            ("0x00", "key_hash", "$", "invalid key_hash 0x00"),
+            ("0x00", "signature", "$", "invalid signature 0x00"),
+            ("0x00", "chain_id", "$", "invalid chain_id 0x00"),
+            (
+                "Some (Right 1)",
+                "option (or nat string)",
+                "$.some.right",
+                "expected string, got 1",
+            ),
+            (
+                "Pair 1 2",
+                "pair nat nat nat",
+                "$.1",
+                "expected (pair nat nat), got 2",
+            ),
+            ("Pair 1 2 -3", "pair nat nat nat", "$.2", "invalid nat -3"),
+            (
+                "Pair 1 (Pair 2 -3)",
+                "pair nat (pair nat nat)",
+                "$.2",
+                "invalid nat -3",
+            ),
+            ("{ 1 ; -2 }", "list nat", "$[1]", "invalid nat -2"),
+            (
+                "{ 2 ; 1 }",
+                "set nat",
+                "$[1]",
+                "set elements are not in strictly increasing order",
+            ),
+            ("{ 1 ; 1 }", "set nat", "$[1]", "duplicate set elements"),
+            (
+                "{ Some 0 ; None }",
+                "set (option nat)",
+                "$[1]",
+                "set elements are not in strictly increasing order",
+            ),
+            (
+                r#"{ Elt "b" 1 ; Elt "a" 2 }"#,
+                "map string nat",
+                "$[1]",
+                "map keys are not in strictly increasing order",
+            ),
+            (
+                r#"{ Elt "a" 1 ; 2 }"#,
+                "map string nat",
+                "$[1]",
+                "expected Elt, got 2",
+            ),
+            (
+                r#"{ Elt "a" -1 }"#,
+                "big_map string nat",
+                "$[0].value",
+                "invalid nat -1",
+            ),
+            (
+                r#"Pair 1 { Elt "a" (Left 1) }"#,
+                "pair nat (map string (or unit nat))",
+                "$.1[0].value.left",
+                "expected unit, got 1",
+            ),
+            (
+                "{ Unit }",
+                "lambda unit unit",
+                "$[0]",
+                "unknown instruction Unit",
+            ),
+            (
+                "{ DROP ; PUSH nat 1 }",
+                "lambda unit unit",
+                "$",
+                "lambda is expected to return [unit], got [nat]",
+            ),
+            (
+                r#"Pair "KT1BEqzn5Wx8uJrZNvuS9DVHmLvG9td3fDLi" "ticket" 10"#,
+                "ticket string",
+                "$",
+                "type ticket has no literal values",
+            ),
+            (
+                r#"Pair 1 (Pair "KT1BEqzn5Wx8uJrZNvuS9DVHmLvG9td3fDLi" "ticket" 10)"#,
+                "pair nat (ticket string)",
+                "$.1",
+                "type ticket has no literal values",
+            ),
+            ("0x00", "bls12_381_g1", "$", "invalid bls12_381_g1 0x00"),
+            (
+                "Unit",
+                "operation",
+                "$",
+                "type operation has no literal values",
+            ),
+            (
+                "{}",
+                "set (list nat)",
+                "$",
+                "type set expects a comparable type, got (list nat)",
+            ),
+            (
+                "{}",
+                "big_map (big_map nat nat) nat",
+                "$",
+                "type big_map expects a comparable type, got (big_map nat nat)",
+            ),
+            (
+                "Unit",
+                "pair unit",
+                "$",
+                "type pair expects at least 2 arguments, got 1",
+            ),
+            ("Unit", "foo", "$", "unknown type foo"),
+            (
+                "{}",
+                "sapling_state 65536",
+                "$",
+                "invalid memo size 65536, expected 0 to 65535",
+            ),
+            (
+                "{}",
+                "sapling_transaction -1",
+                "$",
+                "invalid memo size -1, expected 0 to 65535",
+            ),
+        ];
+        for (value, r#type, path, description) in values {
+            assert_eq!(
+                typecheck_error(value, r#type),
+                (path.into(), description.into()),
+                "{} : {}",
+                value,
+                r#type
+            );
+        }
+    }
+
+    #[test]
+    fn test_typecheck_michelson() -> Result<()> {
+        use crate::michelson::{
+            data::{self, instructions::Sequence, Instruction},
+            types,
+        };
+
+        let r#type = types::pair(vec![types::nat(), types::option(types::address())]);
+        let value: data::Data = data::pair(vec![
+            data::nat(1u8),
+            data::some(data::try_string("tz1fJGtrdmckD3VkiDxqUEci5h4gGcvocw6e")?),
+        ]);
+        value.typecheck(&r#type)?;
+
+        let value: data::Data = data::pair(vec![data::nat(1u8), data::some(data::int(1))]);
+        let error = value.typecheck(&r#type).unwrap_err();
+        assert_eq!(error.to_string(), "expected address, got 1 at $.1.some");
+
+        let code = Sequence::try_from(Instruction::try_from(Micheline::parse(
+            "{ UNPAIR ; ADD ; NIL operation ; PAIR }",
+        )?)?)?;
+        let stack = [types::pair(vec![types::nat(), types::nat()])];
+        assert_eq!(
+            code.typecheck(&stack)?,
+            typed(&["pair (list operation) nat"])
+        );
+        let error = code.typecheck(&[types::nat()]).unwrap_err();
+        assert_eq!(error.to_string(), "UNPAIR is not defined for nat at $[0]");
+        Ok(())
+    }
+
+    fn typecheck_stack(code: &str, stack: &[&str]) -> Result<StackType> {
+        let stack = stack
+            .iter()
+            .map(|ty| Micheline::parse(ty))
+            .collect::<Result<Vec<_>>>()?;
+        typecheck_code(&Micheline::parse(code)?, &stack)
+    }
+
+    fn typed(stack: &[&str]) -> StackType {
+        StackType::Typed(
+            stack
+                .iter()
+                .map(|ty| Micheline::parse(ty).unwrap())
+                .collect(),
+        )
+    }
+
+    fn error_of<T>(result: Result<T>) -> Option<(String, String)> {
+        match result {
+            Err(Error::TypecheckFailed { path, description }) => Some((path, description)),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_typecheck_code() -> Result<()> {
+        let values: &[(&str, &[&str], &[&str])] = &[
+            ("{}", &["nat"], &["nat"]),
+            ("{ DROP 2 }", &["nat", "int", "unit"], &["unit"]),
+            (
+                "{ DUP 3 }",
+                &["nat", "int", "unit"],
+                &["unit", "nat", "int", "unit"],
+            ),
+            ("{ SWAP }", &["nat", "int"], &["int", "nat"]),
+            (
+                "{ DIG 2 }",
+                &["nat", "int", "unit"],
+                &["unit", "nat", "int"],
+            ),
+            (
+                "{ DUG 2 }",
+                &["nat", "int", "unit"],
+                &["int", "unit", "nat"],
+            ),
+            (
+                "{ DIP 2 { DROP } }",
+                &["nat", "int", "unit"],
+                &["nat", "int"],
+            ),
+            (
+                "{ DIP { PUSH int 1 ; ADD } }",
+                &["nat", "nat"],
+                &["nat", "int"],
+            ),
+            (
+                "{ PAIR 3 ; UNPAIR 3 }",
+                &["nat", "int", "unit"],
+                &["nat", "int", "unit"],
+            ),
+            ("{ PAIR 3 ; GET 3 }", &["nat", "int", "unit"], &["int"]),
+            (
+                "{ PAIR 3 ; PUSH string \"a\" ; UPDATE 4 }",
+                &["nat", "int", "unit"],
+                &["pair nat (pair int string)"],
+            ),
+            ("{ CAR }", &["pair nat int"], &["nat"]),
+            ("{ IF_NONE { PUSH nat 0 } {} }", &["option nat"], &["nat"]),
+            ("{ IF_LEFT { INT } {} }", &["or nat int"], &["int"]),
+            ("{ IF_LEFT { FAILWITH } {} }", &["or string int"], &["int"]),
+            (
+                "{ IF_CONS { DIP { DROP } } { PUSH nat 0 } }",
+                &["list nat"],
+                &["nat"],
+            ),
+            (
+                "{ IF { PUSH nat 1 } { PUSH string \"a\" ; FAILWITH } }",
+                &["bool"],
+                &["nat"],
+            ),
+            ("{ MAP { INT } }", &["list nat"], &["list int"]),
+            (
+                "{ MAP { CDR ; SOME } }",
+                &["map string nat"],
+                &["map string (option nat)"],
+            ),
+            ("{ ITER { ADD } }", &["set nat", "nat"], &["nat"]),
+            (
+                "{ ITER { CDR ; ADD } }",
+                &["map string nat", "nat"],
+                &["nat"],
+            ),
+            (
+                "{ LOOP { PUSH nat 1 ; ADD ; DUP ; PUSH nat 10 ; COMPARE ; GT } }",
+                &["bool", "nat"],
+                &["nat"],
+            ),
+            (
+                "{ LOOP_LEFT { DROP ; PUSH string \"done\" ; RIGHT nat } }",
+                &["or nat string"],
+                &["string"],
+            ),
+            (
+                "{ GET }",
+                &["string", "big_map string nat"],
+                &["option nat"],
+            ),
+            ("{ MEM }", &["nat", "set nat"], &["bool"]),
+            ("{ UPDATE }", &["nat", "bool", "set nat"], &["set nat"]),
+            (
+                "{ GET_AND_UPDATE }",
+                &["string", "option nat", "map string nat"],
+                &["option nat", "map string nat"],
+            ),
+            (
+                "{ LAMBDA nat nat { PUSH nat 1 ; ADD } ; SWAP ; EXEC }",
+                &["nat"],
+                &["nat"],
+            ),
+            (
+                "{ LAMBDA (pair nat int) int { UNPAIR ; ADD } ; SWAP ; APPLY }",
+                &["nat"],
+                &["lambda int int"],
+            ),
+            (
+                "{ EDIV }",
+                &["mutez", "nat"],
+                &["option (pair mutez mutez)"],
+            ),
+            ("{ SUB }", &["timestamp", "timestamp"], &["int"]),
+            ("{ MUL }", &["nat", "mutez"], &["mutez"]),
+            ("{ SUB_MUTEZ }", &["mutez", "mutez"], &["option mutez"]),
+            ("{ CONCAT }", &["list string"], &["string"]),
+            ("{ SLICE }", &["nat", "nat", "bytes"], &["option bytes"]),
+            (
+                "{ PACK ; UNPACK (list nat) }",
+                &["list nat"],
+                &["option (list nat)"],
+            ),
+            (
+                "{ CONTRACT %transfer nat }",
+                &["address"],
+                &["option (contract nat)"],
+            ),
+            (
+                "{ TRANSFER_TOKENS }",
+                &["nat", "mutez", "contract nat"],
+                &["operation"],
+            ),
+            ("{ NONE key_hash ; SET_DELEGATE }", &[], &["operation"]),
+            ("{ IMPLICIT_ACCOUNT }", &["key_hash"], &["contract unit"]),
+            (
+                "{ CHECK_SIGNATURE }",
+                &["key", "signature", "bytes"],
+                &["bool"],
+            ),
+            ("{ TICKET }", &["string", "nat"], &["option (ticket string)"]),
+            ("{ TICKET ; ASSERT_SOME }", &["nat", "nat"], &["ticket nat"]),
+            (
+                "{ TICKET ; ASSERT_SOME ; READ_TICKET }",
+                &["string", "nat"],
+                &["pair address (pair string nat)", "ticket string"],
+            ),
+            (
+                "{ TICKET_DEPRECATED ; READ_TICKET }",
+                &["string", "nat"],
+                &["pair address (pair string nat)", "ticket string"],
+            ),
+            (
+                "{ VIEW \"balance\" nat }",
+                &["unit", "address"],
+                &["option nat"],
+            ),
+            (
+                "{ NOW ; AMOUNT ; SENDER ; CHAIN_ID }",
+                &[],
+                &["chain_id", "address", "mutez", "timestamp"],
+            ),
+            ("{ CAR @x %a }", &["pair nat int"], &["nat"]),
+            ("UNIT", &[], &["unit"]),
+            ("{ CADR }", &["pair (pair nat int) unit"], &["int"]),
+            (
+                "{ IFCMPEQ { UNIT } { UNIT } }",
+                &["nat", "nat"],
+                &["unit"],
+            ),
+            ("{ RENAME }", &["nat"], &["nat"]),
+            ("{ CAST nat }", &["nat"], &["nat"]),
+            ("{ SOME }", &["nat"], &["option nat"]),
+            ("{ NONE nat }", &[], &["option nat"]),
+            ("{ LEFT string }", &["nat"], &["or nat string"]),
+            ("{ RIGHT string }", &["nat"], &["or string nat"]),
+            ("{ CDR }", &["pair nat int"], &["int"]),
+            ("{ NIL nat ; SWAP ; CONS }", &["nat"], &["list nat"]),
+            ("{ SIZE }", &["map nat nat"], &["nat"]),
+            ("{ EMPTY_SET nat }", &[], &["set nat"]),
+            ("{ EMPTY_MAP nat string }", &[], &["map nat string"]),
+            (
+                "{ EMPTY_BIG_MAP nat string }",
+                &[],
+                &["big_map nat string"],
+            ),
+            ("{ MAP { DROP ; UNIT } }", &["option nat"], &["option unit"]),
+            ("{ ABS }", &["int"], &["nat"]),
+            ("{ ISNAT }", &["int"], &["option nat"]),
+            ("{ INT }", &["bytes"], &["int"]),
+            ("{ NAT }", &["bytes"], &["nat"]),
+            ("{ BYTES }", &["int"], &["bytes"]),
+            ("{ NEG }", &["nat"], &["int"]),
+            ("{ NEG }", &["bls12_381_g1"], &["bls12_381_g1"]),
+            ("{ NOT }", &["nat"], &["int"]),
+            ("{ NOT }", &["bytes"], &["bytes"]),
+            ("{ COMPARE ; EQ }", &["string", "string"], &["bool"]),
+            ("{ LE }", &["int"], &["bool"]),
+            ("{ ADD }", &["int", "timestamp"], &["timestamp"]),
+            ("{ ADD }", &["nat", "int"], &["int"]),
+            ("{ ADD }", &["bls12_381_fr", "bls12_381_fr"], &["bls12_381_fr"]),
+            ("{ MUL }", &["bls12_381_g2", "bls12_381_fr"], &["bls12_381_g2"]),
+            ("{ MUL }", &["nat", "bls12_381_fr"], &["bls12_381_fr"]),
+            ("{ EDIV }", &["int", "nat"], &["option (pair int nat)"]),
+            ("{ LSL }", &["nat", "nat"], &["nat"]),
+            ("{ LSR }", &["bytes", "nat"], &["bytes"]),
+            ("{ AND }", &["int", "nat"], &["nat"]),
+            ("{ AND }", &["bytes", "bytes"], &["bytes"]),
+            ("{ OR }", &["bool", "bool"], &["bool"]),
+            ("{ XOR }", &["nat", "nat"], &["nat"]),
+            ("{ CONCAT }", &["bytes", "bytes"], &["bytes"]),
+            ("{ SHA256 ; BLAKE2B ; KECCAK }", &["bytes"], &["bytes"]),
+            ("{ HASH_KEY }", &["key"], &["key_hash"]),
+            ("{ ADDRESS }", &["contract nat"], &["address"]),
+            ("{ VOTING_POWER }", &["key_hash"], &["nat"]),
+            (
+                "{ LEVEL ; TOTAL_VOTING_POWER ; MIN_BLOCK_TIME ; BALANCE ; SOURCE ; SELF_ADDRESS }",
+                &[],
+                &["address", "address", "mutez", "nat", "nat", "nat"],
+            ),
+            ("{ EMIT %transfer nat }", &["nat"], &["operation"]),
+            ("{ EMIT }", &["pair nat string"], &["operation"]),
+            (
+                "{ LAMBDA_REC nat nat { DUP ; INT ; EQ ; IF { DIP { DROP } } { DUP ; PUSH nat 1 ; SWAP ; SUB ; ABS ; DIG 2 ; SWAP ; EXEC ; ADD } } }",
+                &[],
+                &["lambda nat nat"],
+            ),
+            (
+                "{ PAIRING_CHECK }",
+                &["list (pair bls12_381_g1 bls12_381_g2)"],
+                &["bool"],
+            ),
+            ("{ SAPLING_EMPTY_STATE 8 }", &[], &["sapling_state 8"]),
+            ("{ SAPLING_EMPTY_STATE 65535 }", &[], &["sapling_state 65535"]),
+            (
+                "{ SAPLING_VERIFY_UPDATE }",
+                &["sapling_transaction 8", "sapling_state 8"],
+                &["option (pair bytes (pair int (sapling_state 8)))"],
+            ),
+            (
+                "{ SPLIT_TICKET }",
+                &["ticket nat", "pair nat nat"],
+                &["option (pair (ticket nat) (ticket nat))"],
+            ),
+            (
+                "{ PAIR ; JOIN_TICKETS }",
+                &["ticket nat", "ticket nat"],
+                &["option (ticket nat)"],
+            ),
+            (
+                "{ OPEN_CHEST }",
+                &["chest_key", "chest", "nat"],
+                &["or bytes bool"],
+            ),
+        ];
+        for (code, stack, expected) in values {
+            assert_eq!(
+                typecheck_stack(code, stack)?,
+                typed(expected),
+                "{} : {:?}",
+                code,
+                stack
+            );
+        }
+        assert_eq!(
+            typecheck_stack("{ DROP ; PUSH string \"a\" ; FAILWITH }", &["nat"])?,
+            StackType::Failed
+        );
+        assert_eq!(typecheck_stack("{ NEVER }", &["never"])?, StackType::Failed);
+        Ok(())
+    }
+
+    #[test]
+    fn test_typecheck_invalid_code() -> Result<()> {
+        let values: &[(&str, &[&str], &str, &str)] = &[
+            (
+                "{ DROP }",
+                &[],
+                "$[0]",
+                "DROP expects at least 1 element(s) on the stack, got []",
+            ),
+            ("{ DUP 0 }", &["nat"], "$[0]", "DUP 0 is not allowed"),
+            (
+                "{ DIG 2 }",
+                &["nat", "int"],
+                "$[0]",
+                "DIG expects at least 3 element(s) on the stack, got [nat : int]",
+            ),
+            ("{ DROP 1024 }", &["nat"], "$[0].0", "invalid depth 1024"),
+            (
+                "{ SAPLING_EMPTY_STATE 100000000000000000000 }",
+                &[],
+                "$[0].0",
+                "invalid memo size 100000000000000000000, expected 0 to 65535",
+            ),
+            (
+                "{ EMPTY_MAP nat (sapling_state 65536) }",
+                &[],
+                "$[0].1",
+                "invalid memo size 65536, expected 0 to 65535",
+            ),
+            (
+                "{ ADD }",
+                &["nat", "string"],
+                "$[0]",
+                "ADD is not defined for nat : string",
+            ),
+            (
+                "{ SUB }",
+                &["mutez", "mutez"],
+                "$[0]",
+                "SUB is not defined for mutez : mutez",
+            ),
+            (
+                "{ IF_NONE { PUSH nat 0 } { INT } }",
+                &["option nat"],
+                "$[0]",
+                "branches of IF_NONE have different stack types: [nat] and [int]",
+            ),
+            (
+                "{ IF { DROP } { DROP } }",
+                &["bool"],
+                "$[0].0[0]",
+                "DROP expects at least 1 element(s) on the stack, got []",
+            ),
+            (
+                "{ IF {} { UNIT } }",
+                &["bool"],
+                "$[0]",
+                "branches of IF have different stack types: [] and [unit]",
+            ),
+            (
+                "{ IF_LEFT DROP {} }",
+                &["or unit unit"],
+                "$[0].0",
+                "expected a sequence of instructions, got DROP",
+            ),
+            ("{ PUSH nat -1 }", &[], "$[0].1", "invalid nat -1"),
+            (
+                "{ PUSH (lambda nat nat) { INT } }",
+                &[],
+                "$[0].1",
+                "lambda is expected to return [nat], got [int]",
+            ),
+            (
+                "{ PUSH operation Unit }",
+                &[],
+                "$[0]",
+                "type operation can't be pushed",
+            ),
+            (
+                "{ DUP }",
+                &["ticket nat"],
+                "$[0]",
+                "type (ticket nat) can't be duplicated",
+            ),
+            (
+                "{ PACK }",
+                &["big_map nat nat"],
+                "$[0]",
+                "PACK is not defined for (big_map nat nat)",
+            ),
+            (
+                "{ FAILWITH ; DROP }",
+                &["nat"],
+                "$[1]",
+                "unreachable instruction after a failing one",
+            ),
+            (
+                "{ LOOP { DROP } }",
+                &["bool", "nat"],
+                "$[0].0",
+                "body of LOOP is expected to return [bool : nat], got []",
+            ),
+            (
+                "{ ITER { ADD ; INT } }",
+                &["list nat", "nat"],
+                "$[0].0",
+                "body of ITER is expected to return [nat], got [int]",
+            ),
+            (
+                "{ LAMBDA unit unit { SELF } }",
+                &[],
+                "$[0].2[0]",
+                "SELF is not allowed in lambdas and views",
+            ),
+            (
+                "{ CAR @x @y }",
+                &["pair nat nat"],
+                "$[0]",
+                "unexpected annotations @x @y on CAR",
+            ),
+            (
+                "{ EMPTY_SET (list nat) }",
+                &[],
+                "$[0]",
+                "type (list nat) is not comparable",
+            ),
+            ("{ FOO }", &[], "$[0]", "unknown instruction FOO"),
+            ("{ 1 }", &[], "$[0]", "expected an instruction, got 1"),
+            (
+                "{ CADR }",
+                &["pair nat nat nat"],
+                "$[0][1]",
+                "CDR is not defined for nat",
+            ),
+            (
+                "{ DIP { FAILWITH } }",
+                &["nat", "string"],
+                "$[0]",
+                "body of DIP always fails, failing is only allowed in tail position",
+            ),
+            (
+                "{ DIP 2 { FAILWITH } }",
+                &["nat", "nat", "string"],
+                "$[0]",
+                "body of DIP always fails, failing is only allowed in tail position",
+            ),
+            (
+                "{ ITER { FAILWITH } }",
+                &["list nat"],
+                "$[0]",
+                "body of ITER always fails, failing is only allowed in tail position",
+            ),
+            (
+                "{ MAP { FAILWITH } }",
+                &["list nat"],
+                "$[0]",
+                "body of MAP always fails, failing is only allowed in tail position",
+            ),
+            (
+                "{ LOOP { PUSH string \"a\" ; FAILWITH } }",
+                &["bool"],
+                "$[0]",
+                "body of LOOP always fails, failing is only allowed in tail position",
+            ),
+            (
+                "{ LOOP_LEFT { FAILWITH } }",
+                &["or nat unit"],
+                "$[0]",
+                "body of LOOP_LEFT always fails, failing is only allowed in tail position",
+            ),
+            (
+                "{ SWAP }",
+                &["nat"],
+                "$[0]",
+                "SWAP expects at least 2 element(s) on the stack, got [nat]",
+            ),
+            (
+                "{ CONS }",
+                &["int", "list nat"],
+                "$[0]",
+                "CONS is not defined for int : (list nat)",
+            ),
+            (
+                "{ COMPARE }",
+                &["nat", "int"],
+                "$[0]",
+                "COMPARE is not defined for nat : int",
+            ),
+            (
+                "{ COMPARE }",
+                &["list nat", "list nat"],
+                "$[0]",
+                "COMPARE is not defined for (list nat) : (list nat)",
+            ),
+            ("{ ABS }", &["nat"], "$[0]", "ABS is not defined for nat"),
+            ("{ NAT }", &["int"], "$[0]", "NAT is not defined for int"),
+            (
+                "{ BYTES }",
+                &["string"],
+                "$[0]",
+                "BYTES is not defined for string",
+            ),
+            (
+                "{ LSL }",
+                &["nat", "bytes"],
+                "$[0]",
+                "LSL is not defined for nat : bytes",
+            ),
+            (
+                "{ ADDRESS }",
+                &["address"],
+                "$[0]",
+                "ADDRESS is not defined for address",
+            ),
+            (
+                "{ EMIT nat }",
+                &["int"],
+                "$[0]",
+                "EMIT is not defined for int",
+            ),
+            (
+                "{ EMIT }",
+                &["contract unit"],
+                "$[0]",
+                "EMIT is not defined for (contract unit)",
+            ),
+            (
+                "{ LAMBDA_REC nat nat { DROP } }",
+                &[],
+                "$[0].2",
+                "lambda is expected to return [nat], got [(lambda nat nat)]",
+            ),
+            (
+                "{ CONTRACT operation }",
+                &["address"],
+                "$[0]",
+                "type operation can't be passed to contracts",
+            ),
+            (
+                "{ UNPACK (big_map nat nat) }",
+                &["bytes"],
+                "$[0]",
+                "type (big_map nat nat) can't be unpacked",
+            ),
+            (
+                "{ TRANSFER_TOKENS }",
+                &["int", "mutez", "contract nat"],
+                "$[0]",
+                "TRANSFER_TOKENS is not defined for int : mutez : (contract nat)",
+            ),
+            ("{ CAST int }", &["nat"], "$[0]", "can't cast nat to int"),
+            (
+                "{ EMPTY_BIG_MAP nat (big_map nat nat) }",
+                &[],
+                "$[0]",
+                "type (big_map nat nat) can't be used as a big_map value",
+            ),
+            (
+                "{ UNPAIR 3 }",
+                &["pair nat nat"],
+                "$[0]",
+                "UNPAIR is not defined for (pair nat nat)",
+            ),
+            (
+                "{ GET 3 }",
+                &["pair nat nat"],
+                "$[0]",
+                "GET is not defined for (pair nat nat)",
+            ),
+            (
+                "{ NEVER }",
+                &["unit"],
+                "$[0]",
+                "NEVER is not defined for unit",
+            ),
+            (
+                "{ TICKET }",
+                &["nat", "int"],
+                "$[0]",
+                "TICKET is not defined for nat : int",
+            ),
+        ];
+        for (code, stack, path, description) in values {
+            assert_eq!(
+                error_of(typecheck_stack(code, stack)),
+                Some((path.to_string(), description.to_string())),
+                "{} : {:?}",
+                code,
+                stack
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_typecheck_script() -> Result<()> {
+        let script = Micheline::parse_script(
+            r#"
+            parameter (or (pair %transfer address nat) (or (unit %default) (address %deploy)));
+            storage (pair (big_map address nat) (option address));
+            code {
+                UNPAIR ;
+                IF_LEFT
+                  { UNPAIR ;
+                    DIP { SWAP ; UNPAIR } ;
+                    DUP 2 ; DUP 2 ; GET ;
+                    IF_NONE { PUSH nat 0 } {} ;
+                    DIG 4 ; ADD ; SOME ; SWAP ; UPDATE ;
+                    PAIR ; NIL operation ; PAIR }
+                  { IF_LEFT
+                      { DROP ; SELF %transfer ; DROP ; NIL operation ; PAIR }
+                      { CONTRACT unit ;
+                        IF_NONE { PUSH string "no contract" ; FAILWITH } {} ;
+                        PUSH mutez 0 ; UNIT ; TRANSFER_TOKENS ;
+                        PUSH nat 1 ; PUSH mutez 0 ; NONE key_hash ;
+                        CREATE_CONTRACT
+                          { parameter unit ; storage nat ; code { CDR ; NIL operation ; PAIR } } ;
+                        SWAP ; SOME ; DIG 3 ; CAR ; PAIR ; DUG 2 ;
+                        NIL operation ; SWAP ; CONS ; SWAP ; CONS ; PAIR } } } ;
+            view "balance" address nat { UNPAIR ; DIP { CAR } ; GET ; IF_NONE { PUSH nat 0 } {} } ;
+            "#,
+        )?;
+        typecheck_script(&script)?;
+
+        let scripts = [
+            "parameter unit ; storage unit ; code { DROP ; SELF ; ADDRESS ; DROP ; UNIT ; NIL operation ; PAIR }",
+            "parameter (pair nat nat) ; storage nat ; code { CAAR ; NIL operation ; PAIR }",
+            "parameter nat ; storage nat ; code { UNPAIR ; DUP 2 ; DUP 2 ; ASSERT_CMPLT ; DROP ; NIL operation ; PAIR }",
+            "parameter nat ; storage unit ; code { CAR ; EMIT %received ; NIL operation ; SWAP ; CONS ; UNIT ; SWAP ; PAIR }",
+        ];
+        for script in scripts {
+            assert!(
+                typecheck_script(&Micheline::parse_script(script)?).is_ok(),
+                "{}",
+                script
+            );
+        }
+
+        let values = [
+            (
+                "parameter unit ; storage nat",
+                "$",
+                "missing code section",
+            ),
+            (
+                "parameter unit ; storage nat ; storage nat ; code {}",
+                "$.storage",
+                "duplicate storage section",
+            ),
+            (
+                "parameter unit ; storage nat ; code { CDR }",
+                "$.code",
+                "code is expected to return [(pair (list operation) nat)], got [nat]",
+            ),
+            (
+                "parameter unit ; storage operation ; code { FAILWITH }",
+                "$.storage",
+                "type operation can't be used as a storage",
+            ),
+            (
+                "parameter (or (nat %a) (int %a)) ; storage unit ; code { FAILWITH }",
+                "$.parameter",
+                "duplicate entrypoint %a",
+            ),
+            (
+                "parameter (or (nat %a) (int %b)) ; storage unit ; code { SELF %c ; FAILWITH }",
+                "$.code[0]",
+                "unknown entrypoint %c",
+            ),
+            (
+                "parameter unit ; storage unit ; code { CDR ; NIL operation ; PAIR } ; view \"v\" unit (contract unit) { DROP ; SELF }",
+                "$.view.v[1]",
+                "SELF is not allowed in lambdas and views",
+            ),
+            (
+                "parameter unit ; storage unit ; code { CDR ; NIL operation ; PAIR } ; view \"v\" unit operation { DROP ; NONE key_hash ; SET_DELEGATE }",
+                "$.view.v",
+                "type operation can't be used in views",
+            ),
+            (
+                "parameter unit ; storage unit ; code { CDR ; NIL operation ; PAIR } ; view \"v\" unit unit { NONE key_hash ; SET_DELEGATE }",
+                "$.view.v[1]",
+                "SET_DELEGATE is not allowed in views",
+            ),
+            (
+                "parameter unit ; storage unit ; code { CDR ; NIL operation ; PAIR } ; view \"v\" unit unit { CDR } ; view \"v\" unit unit { CDR }",
+                "$.view",
+                "duplicate view v",
+            ),
+            (
+                "parameter unit ; storage unit ; code { DROP ; PUSH nat 1 ; PUSH mutez 0 ; NONE key_hash ; CREATE_CONTRACT { parameter unit ; storage unit ; code { CDR ; NIL operation ; PAIR } } ; FAILWITH }",
+                "$.code[4]",
+                "CREATE_CONTRACT is not defined for (option key_hash) : mutez : nat",
+            ),
+            (
+                "parameter unit ; storage unit ; code { DROP ; UNIT ; PUSH mutez 0 ; NONE key_hash ; CREATE_CONTRACT { parameter unit ; storage unit ; code { CAR } } ; FAILWITH }",
+                "$.code[4].0.code",
+                "code is expected to return [(pair (list operation) unit)], got [unit]",
+            ),
+            (
+                "parameter string ; storage unit ; code { UNPAIR ; DIP { FAILWITH } }",
+                "$.code[1]",
+                "body of DIP always fails, failing is only allowed in tail position",
+            ),
+            (
+                "parameter unit ; storage unit ; code { CDR ; NIL operation ; PAIR } ; view \"v\" unit unit { CDR ; DUP ; EMIT ; DROP }",
+                "$.view.v[2]",
+                "EMIT is not allowed in views",
+            ),
+            (
+                "parameter (pair nat nat) ; storage nat ; code { CDDR ; NIL operation ; PAIR }",
+                "$.code[0][1]",
+                "CDR is not defined for nat",
+            ),
+        ];
+        for (script, path, description) in values {
+            assert_eq!(
+                error_of(typecheck_script(&Micheline::parse_script(script)?)),
+                Some((path.to_string(), description.to_string())),
+                "{}",
+                script
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_typecheck_instruction() -> Result<()> {
+        use crate::michelson::{data::instructions, types};
+
+        let code: instructions::Instruction =
+            instructions::sequence(vec![instructions::unpair(None), instructions::add()])
+                .try_into()?;
+        assert_eq!(
+            code.typecheck(&[types::pair(vec![types::nat(), types::int()])])?,
+            typed(&["int"])
+        );
+        Ok(())
+    }
+}
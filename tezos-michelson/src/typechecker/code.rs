@@ -0,0 +1,1448 @@
+use alloc::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+use super::{
+    data, error,
+    types::{self, Ty},
+    Path,
+};
+use crate::{
+    micheline::{literals::Literal, primitive_application::PrimitiveApplication, Micheline},
+    Error, Result,
+};
+
+/// The types of the stack elements, the top of the stack being the last one.
+pub(crate) type Stack = Vec<Ty>;
+
+/// The largest depth accepted by the instructions taking one, e.g. `DIG` or `DROP`.
+const MAX_DEPTH: usize = 1023;
+const MAX_NAME_LENGTH: usize = 31;
+
+/// The parameter of the contract whose code is being typechecked.
+pub(crate) struct Parameter<'a> {
+    r#type: &'a Micheline,
+    root: Option<&'a str>,
+}
+
+impl<'a> Parameter<'a> {
+    /// Returns the type of the entrypoint, `None` if the parameter doesn't define it.
+    fn entrypoint(&self, name: &str, path: &Path) -> Result<Option<Ty>> {
+        if self.root == Some(name) {
+            return Ty::parse(self.r#type, path).map(Some);
+        }
+        match find_entrypoint(self.r#type, name) {
+            Some(r#type) => Ty::parse(r#type, path).map(Some),
+            None if name == "default" => Ty::parse(self.r#type, path).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+fn find_entrypoint<'a>(r#type: &'a Micheline, name: &str) -> Option<&'a Micheline> {
+    let primitive_application = match r#type {
+        Micheline::PrimitiveApplication(value) => value,
+        _ => return None,
+    };
+    if field_annotations(primitive_application).any(|annot| annot == name) {
+        return Some(r#type);
+    }
+    match (primitive_application.prim(), primitive_application.args()) {
+        ("or", Some(args)) => args.iter().find_map(|arg| find_entrypoint(arg, name)),
+        _ => None,
+    }
+}
+
+fn collect_entrypoints<'a>(r#type: &'a Micheline, entrypoints: &mut Vec<&'a str>) {
+    if let Micheline::PrimitiveApplication(primitive_application) = r#type {
+        entrypoints.extend(field_annotations(primitive_application));
+        if let ("or", Some(args)) = (primitive_application.prim(), primitive_application.args()) {
+            for arg in args {
+                collect_entrypoints(arg, entrypoints);
+            }
+        }
+    }
+}
+
+fn field_annotations(primitive_application: &PrimitiveApplication) -> impl Iterator<Item = &str> {
+    primitive_application
+        .annots()
+        .iter()
+        .flatten()
+        .filter_map(|annot| annot.strip_prefix('%'))
+}
+
+/// What the code being typechecked is allowed to do.
+#[derive(Clone, Copy)]
+pub(crate) struct Context<'a> {
+    /// The parameter of the contract, `None` if `SELF` is not allowed, e.g. in lambdas.
+    parameter: Option<&'a Parameter<'a>>,
+    /// Whether the code is the body of a view, which can't emit operations.
+    view: bool,
+}
+
+impl Context<'_> {
+    pub fn lambda() -> Self {
+        Self {
+            parameter: None,
+            view: false,
+        }
+    }
+}
+
+/// Typechecks a sequence of instructions, or a single instruction, returning the resulting stack,
+/// or `None` if the code always fails.
+pub(crate) fn check_code(
+    code: &Micheline,
+    stack: Stack,
+    context: Context,
+    path: &Path,
+) -> Result<Option<Stack>> {
+    match code {
+        Micheline::Sequence(sequence) => check_sequence(sequence.values(), stack, context, path),
+        Micheline::PrimitiveApplication(primitive_application) => Checker {
+            primitive_application,
+            path,
+            context,
+            stack,
+        }
+        .check(),
+        Micheline::Literal(_) => Err(error(
+            path,
+            format!("expected an instruction, got {}", code.to_compact_string()),
+        )),
+    }
+}
+
+fn check_sequence(
+    values: &[Micheline],
+    stack: Stack,
+    context: Context,
+    path: &Path,
+) -> Result<Option<Stack>> {
+    let mut stack = Some(stack);
+    for (index, value) in values.iter().enumerate() {
+        let path = path.index(index);
+        let current =
+            stack.ok_or_else(|| error(&path, "unreachable instruction after a failing one"))?;
+        stack = check_code(value, current, context, &path)?;
+    }
+    Ok(stack)
+}
+
+/// Typechecks a block of code, i.e. a sequence of instructions, used as a branch or as a body.
+fn check_block(
+    code: &Micheline,
+    stack: Stack,
+    context: Context,
+    path: &Path,
+) -> Result<Option<Stack>> {
+    match code {
+        Micheline::Sequence(sequence) => check_sequence(sequence.values(), stack, context, path),
+        _ => Err(error(
+            path,
+            format!(
+                "expected a sequence of instructions, got {}",
+                code.to_compact_string()
+            ),
+        )),
+    }
+}
+
+/// Typechecks the body of a lambda of type `lambda parameter result`.
+pub(crate) fn check_lambda(
+    code: &Micheline,
+    parameter: &Ty,
+    result: &Ty,
+    path: &Path,
+) -> Result<()> {
+    check_lambda_body(code, vec![parameter.clone()], result, path)
+}
+
+/// Typechecks the body of a recursive lambda of type `lambda parameter result`, which finds
+/// itself on the stack below its argument.
+pub(crate) fn check_lambda_rec(
+    code: &Micheline,
+    parameter: &Ty,
+    result: &Ty,
+    path: &Path,
+) -> Result<()> {
+    let lambda = Ty::Lambda(Box::new(parameter.clone()), Box::new(result.clone()));
+    check_lambda_body(code, vec![lambda, parameter.clone()], result, path)
+}
+
+fn check_lambda_body(code: &Micheline, stack: Stack, result: &Ty, path: &Path) -> Result<()> {
+    let stack = check_block(code, stack, Context::lambda(), path)?;
+    expect_stack(stack, &[result], "lambda", path)
+}
+
+/// Checks that the stack resulting from a body is `expected`, given from the top, if the body doesn't fail.
+fn expect_stack(stack: Option<Stack>, expected: &[&Ty], what: &str, path: &Path) -> Result<()> {
+    let stack = match stack {
+        Some(stack) => stack,
+        None => return Ok(()),
+    };
+    if stack.len() != expected.len()
+        || stack
+            .iter()
+            .rev()
+            .zip(expected)
+            .any(|(lhs, rhs)| lhs != *rhs)
+    {
+        let expected = expected
+            .iter()
+            .rev()
+            .map(|ty| (*ty).clone())
+            .collect::<Vec<_>>();
+        return Err(error(
+            path,
+            format!(
+                "{} is expected to return {}, got {}",
+                what,
+                display_stack(&expected),
+                display_stack(&stack)
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Typechecks a script, i.e. a sequence of the `parameter`, `storage`, `code` and `view` sections,
+/// returning its storage type.
+pub(crate) fn check_script(script: &Micheline, path: &Path) -> Result<Ty> {
+    let sections = match script {
+        Micheline::Sequence(sequence) => sequence.values(),
+        _ => return Err(error(path, "expected a sequence of script sections")),
+    };
+    let mut parameter = None;
+    let mut storage = None;
+    let mut code = None;
+    let mut views = Vec::new();
+    for (index, section) in sections.iter().enumerate() {
+        let section = match section {
+            Micheline::PrimitiveApplication(section) => section,
+            _ => {
+                return Err(error(
+                    &path.index(index),
+                    format!(
+                        "expected a script section, got {}",
+                        section.to_compact_string()
+                    ),
+                ))
+            }
+        };
+        let args = section.args().as_deref().unwrap_or_default();
+        let (slot, args_count) = match section.prim() {
+            "parameter" => (&mut parameter, 1),
+            "storage" => (&mut storage, 1),
+            "code" => (&mut code, 1),
+            "view" => {
+                views.push(section);
+                continue;
+            }
+            prim => {
+                return Err(error(
+                    &path.index(index),
+                    format!("unknown script section {}", prim),
+                ))
+            }
+        };
+        let path = path.field(section.prim());
+        if slot.is_some() {
+            return Err(error(
+                &path,
+                format!("duplicate {} section", section.prim()),
+            ));
+        }
+        if args.len() != args_count {
+            return Err(error(
+                &path,
+                format!(
+                    "section {} expects {} argument(s), got {}",
+                    section.prim(),
+                    args_count,
+                    args.len()
+                ),
+            ));
+        }
+        *slot = Some(section);
+    }
+    let missing = |name: &str| error(path, format!("missing {} section", name));
+    let (parameter, storage, code) = (
+        parameter.ok_or_else(|| missing("parameter"))?,
+        storage.ok_or_else(|| missing("storage"))?,
+        code.ok_or_else(|| missing("code"))?,
+    );
+
+    let parameter_path = path.field("parameter");
+    let parameter = Parameter {
+        r#type: &parameter.args().as_deref().unwrap_or_default()[0],
+        root: field_annotations(parameter).next(),
+    };
+    let parameter_ty = Ty::parse(parameter.r#type, &parameter_path)?;
+    if !parameter_ty.is_passable() {
+        return Err(error(
+            &parameter_path,
+            format!("type {} can't be used as a parameter", parameter_ty),
+        ));
+    }
+    let mut entrypoints = parameter.root.into_iter().collect::<Vec<_>>();
+    collect_entrypoints(parameter.r#type, &mut entrypoints);
+    for (index, entrypoint) in entrypoints.iter().enumerate() {
+        if !is_valid_name(entrypoint) {
+            return Err(error(
+                &parameter_path,
+                format!("invalid entrypoint %{}", entrypoint),
+            ));
+        }
+        if entrypoints[..index].contains(entrypoint) {
+            return Err(error(
+                &parameter_path,
+                format!("duplicate entrypoint %{}", entrypoint),
+            ));
+        }
+    }
+
+    let storage_path = path.field("storage");
+    let storage_ty = Ty::parse(
+        &storage.args().as_deref().unwrap_or_default()[0],
+        &storage_path,
+    )?;
+    if !storage_ty.is_storable() {
+        return Err(error(
+            &storage_path,
+            format!("type {} can't be used as a storage", storage_ty),
+        ));
+    }
+
+    let context = Context {
+        parameter: Some(&parameter),
+        view: false,
+    };
+    let code_path = path.field("code");
+    let stack = check_block(
+        &code.args().as_deref().unwrap_or_default()[0],
+        vec![Ty::Pair(
+            Box::new(parameter_ty),
+            Box::new(storage_ty.clone()),
+        )],
+        context,
+        &code_path,
+    )?;
+    let result = Ty::Pair(
+        Box::new(Ty::List(Box::new(Ty::Operation))),
+        Box::new(storage_ty.clone()),
+    );
+    expect_stack(stack, &[&result], "code", &code_path)?;
+
+    let mut names = Vec::new();
+    for view in views {
+        let name = check_view(view, &storage_ty, path)?;
+        if names.contains(&name) {
+            return Err(error(
+                &path.field("view"),
+                format!("duplicate view {}", name),
+            ));
+        }
+        names.push(name);
+    }
+
+    Ok(storage_ty)
+}
+
+/// Typechecks a `view "name" parameter result { code }` section, returning its name.
+fn check_view<'a>(view: &'a PrimitiveApplication, storage: &Ty, path: &Path) -> Result<&'a str> {
+    let path = path.field("view");
+    let args = match view.args().as_deref() {
+        Some(args @ [_, _, _, _]) => args,
+        _ => {
+            return Err(error(
+                &path,
+                format!(
+                    "section view expects 4 arguments, got {}",
+                    view.args_count()
+                ),
+            ))
+        }
+    };
+    let name = match &args[0] {
+        Micheline::Literal(Literal::String(name)) if is_valid_name(name.to_str()) => name.to_str(),
+        name => {
+            return Err(error(
+                &path,
+                format!("invalid view name {}", name.to_compact_string()),
+            ))
+        }
+    };
+    let path = path.field(name);
+    let parameter = Ty::parse(&args[1], &path)?;
+    let result = Ty::parse(&args[2], &path)?;
+    for ty in [&parameter, &result] {
+        if !ty.is_packable() {
+            return Err(error(&path, format!("type {} can't be used in views", ty)));
+        }
+    }
+    let context = Context {
+        parameter: None,
+        view: true,
+    };
+    let stack = check_block(
+        &args[3],
+        vec![Ty::Pair(Box::new(parameter), Box::new(storage.clone()))],
+        context,
+        &path,
+    )?;
+    expect_stack(stack, &[&result], "view", &path)?;
+    Ok(name)
+}
+
+/// Returns true if the value is a valid entrypoint or view name.
+pub(crate) fn is_valid_name(name: &str) -> bool {
+    name.len() <= MAX_NAME_LENGTH && name.bytes().all(is_valid_name_character)
+}
+
+fn is_valid_name_character(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, b'_' | b'.' | b'%' | b'@')
+}
+
+pub(crate) fn display_stack(stack: &[Ty]) -> String {
+    let types = stack
+        .iter()
+        .rev()
+        .map(|ty| ty.to_string())
+        .collect::<Vec<_>>();
+    format!("[{}]", types.join(" : "))
+}
+
+/// Typechecks a single primitive application.
+struct Checker<'a> {
+    primitive_application: &'a PrimitiveApplication,
+    path: &'a Path,
+    context: Context<'a>,
+    stack: Stack,
+}
+
+impl Checker<'_> {
+    fn prim(&self) -> &str {
+        self.primitive_application.prim()
+    }
+
+    fn args(&self) -> &[Micheline] {
+        self.primitive_application
+            .args()
+            .as_deref()
+            .unwrap_or_default()
+    }
+
+    fn error<S: ToString>(&self, description: S) -> Error {
+        error(self.path, description)
+    }
+
+    fn arity(&self, expected: usize) -> Result<()> {
+        let args = self.args().len();
+        if args != expected {
+            return Err(self.error(format!(
+                "{} expects {} argument(s), got {}",
+                self.prim(),
+                expected,
+                args
+            )));
+        }
+        Ok(())
+    }
+
+    /// Reads the optional depth argument of instructions like `DROP` or `DUP`.
+    fn depth(&self, default: usize) -> Result<usize> {
+        match self.args() {
+            [] => Ok(default),
+            [arg] => self.depth_arg(arg, 0),
+            args => Err(self.error(format!(
+                "{} expects at most 1 argument, got {}",
+                self.prim(),
+                args.len()
+            ))),
+        }
+    }
+
+    fn depth_arg(&self, arg: &Micheline, index: usize) -> Result<usize> {
+        match arg {
+            Micheline::Literal(Literal::Int(value)) => value
+                .to_integer::<usize>()
+                .ok()
+                .filter(|depth| *depth <= MAX_DEPTH)
+                .ok_or_else(|| error(&self.path.field(index), format!("invalid depth {}", value))),
+            _ => Err(error(
+                &self.path.field(index),
+                format!("expected a depth, got {}", arg.to_compact_string()),
+            )),
+        }
+    }
+
+    fn type_arg(&self, index: usize) -> Result<Ty> {
+        Ty::parse(&self.args()[index], &self.path.field(index))
+    }
+
+    /// Checks that the stack has at least `count` elements.
+    fn require(&self, count: usize) -> Result<()> {
+        if self.stack.len() < count {
+            return Err(self.error(format!(
+                "{} expects at least {} element(s) on the stack, got {}",
+                self.prim(),
+                count,
+                display_stack(&self.stack)
+            )));
+        }
+        Ok(())
+    }
+
+    /// Pops the top `count` elements, returning them from the top.
+    fn pop(&mut self, count: usize) -> Result<Vec<Ty>> {
+        self.require(count)?;
+        let mut popped = self.stack.split_off(self.stack.len() - count);
+        popped.reverse();
+        Ok(popped)
+    }
+
+    fn pop1(&mut self) -> Result<Ty> {
+        Ok(self.pop(1)?.remove(0))
+    }
+
+    fn pop2(&mut self) -> Result<(Ty, Ty)> {
+        let mut popped = self.pop(2)?;
+        let second = popped.pop().unwrap();
+        Ok((popped.remove(0), second))
+    }
+
+    fn pop3(&mut self) -> Result<(Ty, Ty, Ty)> {
+        let mut popped = self.pop(3)?;
+        let third = popped.pop().unwrap();
+        let second = popped.pop().unwrap();
+        Ok((popped.remove(0), second, third))
+    }
+
+    fn unsupported(&self, types: &[&Ty]) -> Error {
+        let types = types.iter().map(|ty| ty.to_string()).collect::<Vec<_>>();
+        self.error(format!(
+            "{} is not defined for {}",
+            self.prim(),
+            types.join(" : ")
+        ))
+    }
+
+    fn block(&self, index: usize, stack: Stack) -> Result<Option<Stack>> {
+        check_block(
+            &self.args()[index],
+            stack,
+            self.context,
+            &self.path.field(index),
+        )
+    }
+
+    /// Typechecks a body which must give a stack back to the instruction, i.e. the body of `DIP`,
+    /// `MAP` or of a loop: failing is only allowed in tail position.
+    fn body(&self, index: usize, stack: Stack) -> Result<Stack> {
+        self.block(index, stack)?.ok_or_else(|| {
+            self.error(format!(
+                "body of {} always fails, failing is only allowed in tail position",
+                self.prim()
+            ))
+        })
+    }
+
+    /// Merges the stacks resulting from two branches.
+    fn merge(&self, lhs: Option<Stack>, rhs: Option<Stack>) -> Result<Option<Stack>> {
+        match (lhs, rhs) {
+            (Some(lhs), Some(rhs)) if lhs != rhs => Err(self.error(format!(
+                "branches of {} have different stack types: {} and {}",
+                self.prim(),
+                display_stack(&lhs),
+                display_stack(&rhs)
+            ))),
+            (Some(stack), _) | (None, Some(stack)) => Ok(Some(stack)),
+            (None, None) => Ok(None),
+        }
+    }
+
+    /// Checks that the body of a loop returns the stack it started with, topped with `top` if provided.
+    fn loop_body(&self, index: usize, input: Stack, top: Option<&Ty>) -> Result<()> {
+        let mut expected = self.stack.clone();
+        expected.extend(top.cloned());
+        let stack = self.body(index, input)?;
+        let expected = expected.iter().rev().collect::<Vec<_>>();
+        expect_stack(
+            Some(stack),
+            &expected,
+            &format!("body of {}", self.prim()),
+            &self.path.field(index),
+        )
+    }
+
+    fn check_annotations(&self) -> Result<()> {
+        let annots = match self.primitive_application.annots() {
+            Some(annots) => annots,
+            None => return Ok(()),
+        };
+        let (mut variables, mut types, mut fields) = (0, 0, 0);
+        for annot in annots {
+            let counter = match annot.chars().next() {
+                Some('@') => &mut variables,
+                Some(':') => &mut types,
+                Some('%') => &mut fields,
+                _ => return Err(self.error(format!("invalid annotation {}", annot))),
+            };
+            *counter += 1;
+            if !annot[1..].bytes().all(is_valid_name_character) {
+                return Err(self.error(format!("invalid annotation {}", annot)));
+            }
+        }
+        let max_variables = match self.prim() {
+            "UNPAIR" | "CREATE_CONTRACT" => 2,
+            _ => 1,
+        };
+        let max_fields = match self.prim() {
+            "PAIR" | "UNPAIR" | "LEFT" | "RIGHT" => 2,
+            "CAR" | "CDR" | "SOME" | "NONE" | "SELF" | "CONTRACT" | "CAST" | "UNIT" | "NIL"
+            | "EMPTY_SET" | "EMPTY_MAP" | "EMPTY_BIG_MAP" | "LAMBDA" | "LAMBDA_REC" | "PUSH"
+            | "EMIT" => 1,
+            _ => 0,
+        };
+        if variables > max_variables || types > 1 || fields > max_fields {
+            return Err(self.error(format!(
+                "unexpected annotations {} on {}",
+                annots.join(" "),
+                self.prim()
+            )));
+        }
+        Ok(())
+    }
+
+    fn forbid_in_view(&self) -> Result<()> {
+        if self.context.view {
+            return Err(self.error(format!("{} is not allowed in views", self.prim())));
+        }
+        Ok(())
+    }
+
+    fn check(mut self) -> Result<Option<Stack>> {
+        self.check_annotations()?;
+        let prim = self.primitive_application.prim();
+        if let Some(ty) = constant(prim) {
+            self.arity(0)?;
+            self.stack.push(ty);
+            return Ok(Some(self.stack));
+        }
+        if let Some(operation) = arithmetic(prim) {
+            self.arity(0)?;
+            let (lhs, rhs) = self.pop2()?;
+            let result = operation(&lhs, &rhs).ok_or_else(|| self.unsupported(&[&lhs, &rhs]))?;
+            self.stack.push(result);
+            return Ok(Some(self.stack));
+        }
+        if let Some(operation) = unary(prim) {
+            self.arity(0)?;
+            let ty = self.pop1()?;
+            let result = operation(&ty).ok_or_else(|| self.unsupported(&[&ty]))?;
+            self.stack.push(result);
+            return Ok(Some(self.stack));
+        }
+
+        match prim {
+            "DROP" => {
+                let depth = self.depth(1)?;
+                self.pop(depth)?;
+            }
+            "DUP" => {
+                let depth = self.depth(1)?;
+                if depth == 0 {
+                    return Err(self.error("DUP 0 is not allowed"));
+                }
+                self.require(depth)?;
+                let ty = self.stack[self.stack.len() - depth].clone();
+                if !ty.is_dupable() {
+                    return Err(self.error(format!("type {} can't be duplicated", ty)));
+                }
+                self.stack.push(ty);
+            }
+            "SWAP" => {
+                self.arity(0)?;
+                let (top, second) = self.pop2()?;
+                self.stack.push(top);
+                self.stack.push(second);
+            }
+            "DIG" | "DUG" => {
+                self.arity(1)?;
+                let depth = self.depth_arg(&self.args()[0], 0)?;
+                self.require(depth + 1)?;
+                let index = self.stack.len() - 1 - depth;
+                if prim == "DIG" {
+                    let ty = self.stack.remove(index);
+                    self.stack.push(ty);
+                } else {
+                    let ty = self.stack.pop().unwrap();
+                    self.stack.insert(index, ty);
+                }
+            }
+            "DIP" => {
+                let (depth, body) = match self.args() {
+                    [_] => (1, 0),
+                    [depth, _] => (self.depth_arg(depth, 0)?, 1),
+                    _ => return Err(self.error("DIP expects 1 or 2 arguments")),
+                };
+                self.require(depth)?;
+                let protected = self.stack.split_off(self.stack.len() - depth);
+                let stack = core::mem::take(&mut self.stack);
+                self.stack = self.body(body, stack)?;
+                self.stack.extend(protected);
+            }
+            "PUSH" => {
+                self.arity(2)?;
+                let ty = self.type_arg(0)?;
+                if !ty.is_pushable() {
+                    return Err(self.error(format!("type {} can't be pushed", ty)));
+                }
+                data::check(&self.args()[1], &ty, &self.path.field(1))?;
+                self.stack.push(ty);
+            }
+            "SOME" => {
+                self.arity(0)?;
+                let ty = self.pop1()?;
+                self.stack.push(Ty::Option(Box::new(ty)));
+            }
+            "NONE" => {
+                self.arity(1)?;
+                let ty = self.type_arg(0)?;
+                self.stack.push(Ty::Option(Box::new(ty)));
+            }
+            "UNIT" => {
+                self.arity(0)?;
+                self.stack.push(Ty::Unit);
+            }
+            "NEVER" => {
+                self.arity(0)?;
+                match self.pop1()? {
+                    Ty::Never => return Ok(None),
+                    ty => return Err(self.unsupported(&[&ty])),
+                }
+            }
+            "IF_NONE" => {
+                self.arity(2)?;
+                let inner = match self.pop1()? {
+                    Ty::Option(inner) => *inner,
+                    ty => return Err(self.unsupported(&[&ty])),
+                };
+                let none = self.block(0, self.stack.clone())?;
+                let mut stack = self.stack.clone();
+                stack.push(inner);
+                let some = self.block(1, stack)?;
+                return self.merge(none, some);
+            }
+            "PAIR" => {
+                let depth = self.depth(2)?;
+                if depth < 2 {
+                    return Err(self.error(format!("PAIR {} is not allowed", depth)));
+                }
+                let types = self.pop(depth)?;
+                self.stack.push(Ty::comb(types));
+            }
+            "UNPAIR" => {
+                let depth = self.depth(2)?;
+                if depth < 2 {
+                    return Err(self.error(format!("UNPAIR {} is not allowed", depth)));
+                }
+                let ty = self.pop1()?;
+                let types = ty.uncomb(depth).ok_or_else(|| self.unsupported(&[&ty]))?;
+                self.stack.extend(types.into_iter().rev());
+            }
+            "CAR" | "CDR" => {
+                self.arity(0)?;
+                match self.pop1()? {
+                    Ty::Pair(left, right) => {
+                        self.stack.push(if prim == "CAR" { *left } else { *right })
+                    }
+                    ty => return Err(self.unsupported(&[&ty])),
+                }
+            }
+            "LEFT" | "RIGHT" => {
+                self.arity(1)?;
+                let other = Box::new(self.type_arg(0)?);
+                let ty = Box::new(self.pop1()?);
+                self.stack.push(if prim == "LEFT" {
+                    Ty::Or(ty, other)
+                } else {
+                    Ty::Or(other, ty)
+                });
+            }
+            "IF_LEFT" => {
+                self.arity(2)?;
+                let (left, right) = match self.pop1()? {
+                    Ty::Or(left, right) => (*left, *right),
+                    ty => return Err(self.unsupported(&[&ty])),
+                };
+                let mut stack = self.stack.clone();
+                stack.push(left);
+                let left = self.block(0, stack)?;
+                let mut stack = self.stack.clone();
+                stack.push(right);
+                let right = self.block(1, stack)?;
+                return self.merge(left, right);
+            }
+            "NIL" => {
+                self.arity(1)?;
+                let ty = self.type_arg(0)?;
+                self.stack.push(Ty::List(Box::new(ty)));
+            }
+            "CONS" => {
+                self.arity(0)?;
+                let (ty, list) = self.pop2()?;
+                match &list {
+                    Ty::List(inner) if **inner == ty => self.stack.push(list),
+                    _ => return Err(self.unsupported(&[&ty, &list])),
+                }
+            }
+            "IF_CONS" => {
+                self.arity(2)?;
+                let list = self.pop1()?;
+                let inner = match &list {
+                    Ty::List(inner) => (**inner).clone(),
+                    ty => return Err(self.unsupported(&[ty])),
+                };
+                let mut stack = self.stack.clone();
+                stack.push(list);
+                stack.push(inner);
+                let cons = self.block(0, stack)?;
+                let nil = self.block(1, self.stack.clone())?;
+                return self.merge(cons, nil);
+            }
+            "SIZE" => {
+                self.arity(0)?;
+                match self.pop1()? {
+                    Ty::String | Ty::Bytes | Ty::List(_) | Ty::Set(_) | Ty::Map(_, _) => {
+                        self.stack.push(Ty::Nat)
+                    }
+                    ty => return Err(self.unsupported(&[&ty])),
+                }
+            }
+            "EMPTY_SET" => {
+                self.arity(1)?;
+                let ty = self.type_arg(0)?;
+                if !ty.is_comparable() {
+                    return Err(self.error(format!("type {} is not comparable", ty)));
+                }
+                self.stack.push(Ty::Set(Box::new(ty)));
+            }
+            "EMPTY_MAP" | "EMPTY_BIG_MAP" => {
+                self.arity(2)?;
+                let (key, value) = (self.type_arg(0)?, self.type_arg(1)?);
+                if !key.is_comparable() {
+                    return Err(self.error(format!("type {} is not comparable", key)));
+                }
+                if prim == "EMPTY_MAP" {
+                    self.stack.push(Ty::Map(Box::new(key), Box::new(value)));
+                } else {
+                    if !value.is_big_map_value() {
+                        return Err(
+                            self.error(format!("type {} can't be used as a big_map value", value))
+                        );
+                    }
+                    self.stack.push(Ty::BigMap(Box::new(key), Box::new(value)));
+                }
+            }
+            "MAP" => {
+                self.arity(1)?;
+                let container = self.pop1()?;
+                let element = match &container {
+                    Ty::List(ty) | Ty::Option(ty) => (**ty).clone(),
+                    Ty::Map(key, value) => Ty::Pair(key.clone(), value.clone()),
+                    ty => return Err(self.unsupported(&[ty])),
+                };
+                let mut stack = self.stack.clone();
+                stack.push(element);
+                let mut stack = self.body(0, stack)?;
+                let result = Box::new(
+                    stack
+                        .pop()
+                        .ok_or_else(|| self.error("body of MAP returns an empty stack"))?,
+                );
+                expect_stack(
+                    Some(stack),
+                    &self.stack.iter().rev().collect::<Vec<_>>(),
+                    "body of MAP",
+                    &self.path.field(0),
+                )?;
+                self.stack.push(match container {
+                    Ty::List(_) => Ty::List(result),
+                    Ty::Option(_) => Ty::Option(result),
+                    Ty::Map(key, _) => Ty::Map(key, result),
+                    _ => unreachable!(),
+                });
+            }
+            "ITER" => {
+                self.arity(1)?;
+                let element = match self.pop1()? {
+                    Ty::List(ty) | Ty::Set(ty) => *ty,
+                    Ty::Map(key, value) => Ty::Pair(key, value),
+                    ty => return Err(self.unsupported(&[&ty])),
+                };
+                let mut stack = self.stack.clone();
+                stack.push(element);
+                self.loop_body(0, stack, None)?;
+            }
+            "MEM" => {
+                self.arity(0)?;
+                let (key, container) = self.pop2()?;
+                match &container {
+                    Ty::Set(ty) | Ty::Map(ty, _) | Ty::BigMap(ty, _) if **ty == key => {
+                        self.stack.push(Ty::Bool)
+                    }
+                    _ => return Err(self.unsupported(&[&key, &container])),
+                }
+            }
+            "GET" if self.args().is_empty() => {
+                let (key, container) = self.pop2()?;
+                match &container {
+                    Ty::Map(ty, value) | Ty::BigMap(ty, value) if **ty == key => {
+                        self.stack.push(Ty::Option(value.clone()))
+                    }
+                    _ => return Err(self.unsupported(&[&key, &container])),
+                }
+            }
+            "GET" => {
+                self.arity(1)?;
+                let index = self.depth_arg(&self.args()[0], 0)?;
+                let ty = self.pop1()?;
+                let field = comb_get(&ty, index).ok_or_else(|| self.unsupported(&[&ty]))?;
+                self.stack.push(field);
+            }
+            "UPDATE" if self.args().is_empty() => {
+                let (key, value, container) = self.pop3()?;
+                let valid = match (&value, &container) {
+                    (Ty::Bool, Ty::Set(ty)) => **ty == key,
+                    (Ty::Option(value), Ty::Map(ty, expected) | Ty::BigMap(ty, expected)) => {
+                        **ty == key && value == expected
+                    }
+                    _ => false,
+                };
+                if !valid {
+                    return Err(self.unsupported(&[&key, &value, &container]));
+                }
+                self.stack.push(container);
+            }
+            "UPDATE" => {
+                self.arity(1)?;
+                let index = self.depth_arg(&self.args()[0], 0)?;
+                let (value, ty) = self.pop2()?;
+                let updated = comb_update(&ty, index, value.clone())
+                    .ok_or_else(|| self.unsupported(&[&value, &ty]))?;
+                self.stack.push(updated);
+            }
+            "GET_AND_UPDATE" => {
+                self.arity(0)?;
+                let (key, value, container) = self.pop3()?;
+                match (&value, &container) {
+                    (Ty::Option(value), Ty::Map(ty, expected) | Ty::BigMap(ty, expected))
+                        if **ty == key && value == expected => {}
+                    _ => return Err(self.unsupported(&[&key, &value, &container])),
+                }
+                self.stack.push(container);
+                self.stack.push(value);
+            }
+            "IF" => {
+                self.arity(2)?;
+                match self.pop1()? {
+                    Ty::Bool => {}
+                    ty => return Err(self.unsupported(&[&ty])),
+                }
+                let then = self.block(0, self.stack.clone())?;
+                let r#else = self.block(1, self.stack.clone())?;
+                return self.merge(then, r#else);
+            }
+            "LOOP" => {
+                self.arity(1)?;
+                match self.pop1()? {
+                    Ty::Bool => {}
+                    ty => return Err(self.unsupported(&[&ty])),
+                }
+                self.loop_body(0, self.stack.clone(), Some(&Ty::Bool))?;
+            }
+            "LOOP_LEFT" => {
+                self.arity(1)?;
+                let ty = self.pop1()?;
+                let (left, right) = match &ty {
+                    Ty::Or(left, right) => ((**left).clone(), (**right).clone()),
+                    _ => return Err(self.unsupported(&[&ty])),
+                };
+                let mut stack = self.stack.clone();
+                stack.push(left);
+                self.loop_body(0, stack, Some(&ty))?;
+                self.stack.push(right);
+            }
+            "LAMBDA" => {
+                self.arity(3)?;
+                let (parameter, result) = (self.type_arg(0)?, self.type_arg(1)?);
+                check_lambda(&self.args()[2], &parameter, &result, &self.path.field(2))?;
+                self.stack
+                    .push(Ty::Lambda(Box::new(parameter), Box::new(result)));
+            }
+            "LAMBDA_REC" => {
+                self.arity(3)?;
+                let (parameter, result) = (self.type_arg(0)?, self.type_arg(1)?);
+                check_lambda_rec(&self.args()[2], &parameter, &result, &self.path.field(2))?;
+                self.stack
+                    .push(Ty::Lambda(Box::new(parameter), Box::new(result)));
+            }
+            "EXEC" => {
+                self.arity(0)?;
+                let (argument, lambda) = self.pop2()?;
+                match &lambda {
+                    Ty::Lambda(parameter, result) if **parameter == argument => {
+                        self.stack.push((**result).clone())
+                    }
+                    _ => return Err(self.unsupported(&[&argument, &lambda])),
+                }
+            }
+            "APPLY" => {
+                self.arity(0)?;
+                let (argument, lambda) = self.pop2()?;
+                match &lambda {
+                    Ty::Lambda(parameter, result) if argument.is_packable() => match &**parameter {
+                        Ty::Pair(captured, rest) if **captured == argument => {
+                            self.stack.push(Ty::Lambda(rest.clone(), result.clone()))
+                        }
+                        _ => return Err(self.unsupported(&[&argument, &lambda])),
+                    },
+                    _ => return Err(self.unsupported(&[&argument, &lambda])),
+                }
+            }
+            "FAILWITH" => {
+                self.arity(0)?;
+                let ty = self.pop1()?;
+                if !ty.is_packable() {
+                    return Err(self.unsupported(&[&ty]));
+                }
+                return Ok(None);
+            }
+            "CAST" => {
+                self.arity(1)?;
+                let ty = self.type_arg(0)?;
+                let top = self.pop1()?;
+                if top != ty {
+                    return Err(self.error(format!("can't cast {} to {}", top, ty)));
+                }
+                self.stack.push(ty);
+            }
+            "RENAME" => {
+                self.arity(0)?;
+                self.require(1)?;
+            }
+            "CONCAT" => {
+                self.arity(0)?;
+                let top = self.pop1()?;
+                match &top {
+                    Ty::String | Ty::Bytes => {
+                        let second = self.pop1()?;
+                        if second != top {
+                            return Err(self.unsupported(&[&top, &second]));
+                        }
+                        self.stack.push(top);
+                    }
+                    Ty::List(ty) if matches!(**ty, Ty::String | Ty::Bytes) => {
+                        self.stack.push((**ty).clone())
+                    }
+                    _ => return Err(self.unsupported(&[&top])),
+                }
+            }
+            "SLICE" => {
+                self.arity(0)?;
+                let (offset, length, ty) = self.pop3()?;
+                match (&offset, &length, &ty) {
+                    (Ty::Nat, Ty::Nat, Ty::String | Ty::Bytes) => {
+                        self.stack.push(Ty::Option(Box::new(ty)))
+                    }
+                    _ => return Err(self.unsupported(&[&offset, &length, &ty])),
+                }
+            }
+            "PACK" => {
+                self.arity(0)?;
+                let ty = self.pop1()?;
+                if !ty.is_packable() {
+                    return Err(self.unsupported(&[&ty]));
+                }
+                self.stack.push(Ty::Bytes);
+            }
+            "UNPACK" => {
+                self.arity(1)?;
+                let ty = self.type_arg(0)?;
+                if !ty.is_packable() {
+                    return Err(self.error(format!("type {} can't be unpacked", ty)));
+                }
+                match self.pop1()? {
+                    Ty::Bytes => self.stack.push(Ty::Option(Box::new(ty))),
+                    top => return Err(self.unsupported(&[&top])),
+                }
+            }
+            "SUB_MUTEZ" => {
+                self.arity(0)?;
+                match self.pop2()? {
+                    (Ty::Mutez, Ty::Mutez) => self.stack.push(Ty::Option(Box::new(Ty::Mutez))),
+                    (lhs, rhs) => return Err(self.unsupported(&[&lhs, &rhs])),
+                }
+            }
+            "COMPARE" => {
+                self.arity(0)?;
+                let (lhs, rhs) = self.pop2()?;
+                if lhs != rhs || !lhs.is_comparable() {
+                    return Err(self.unsupported(&[&lhs, &rhs]));
+                }
+                self.stack.push(Ty::Int);
+            }
+            "SELF" => {
+                self.arity(0)?;
+                let parameter = self
+                    .context
+                    .parameter
+                    .ok_or_else(|| self.error("SELF is not allowed in lambdas and views"))?;
+                let entrypoint = field_annotations(self.primitive_application)
+                    .next()
+                    .unwrap_or("default");
+                let ty = parameter
+                    .entrypoint(entrypoint, self.path)?
+                    .ok_or_else(|| self.error(format!("unknown entrypoint %{}", entrypoint)))?;
+                self.stack.push(Ty::Contract(Box::new(ty)));
+            }
+            "CONTRACT" => {
+                self.arity(1)?;
+                let ty = self.type_arg(0)?;
+                if !ty.is_passable() {
+                    return Err(self.error(format!("type {} can't be passed to contracts", ty)));
+                }
+                match self.pop1()? {
+                    Ty::Address => self
+                        .stack
+                        .push(Ty::Option(Box::new(Ty::Contract(Box::new(ty))))),
+                    top => return Err(self.unsupported(&[&top])),
+                }
+            }
+            "TRANSFER_TOKENS" => {
+                self.arity(0)?;
+                self.forbid_in_view()?;
+                let (argument, amount, contract) = self.pop3()?;
+                match (&amount, &contract) {
+                    (Ty::Mutez, Ty::Contract(parameter)) if **parameter == argument => {
+                        self.stack.push(Ty::Operation)
+                    }
+                    _ => return Err(self.unsupported(&[&argument, &amount, &contract])),
+                }
+            }
+            "SET_DELEGATE" => {
+                self.arity(0)?;
+                self.forbid_in_view()?;
+                match self.pop1()? {
+                    Ty::Option(ty) if *ty == Ty::KeyHash => self.stack.push(Ty::Operation),
+                    ty => return Err(self.unsupported(&[&ty])),
+                }
+            }
+            "CREATE_CONTRACT" => {
+                self.arity(1)?;
+                self.forbid_in_view()?;
+                let storage = check_script(&self.args()[0], &self.path.field(0))?;
+                let (delegate, amount, initial_storage) = self.pop3()?;
+                match (&delegate, &amount) {
+                    (Ty::Option(ty), Ty::Mutez)
+                        if **ty == Ty::KeyHash && initial_storage == storage =>
+                    {
+                        self.stack.push(Ty::Address);
+                        self.stack.push(Ty::Operation);
+                    }
+                    _ => return Err(self.unsupported(&[&delegate, &amount, &initial_storage])),
+                }
+            }
+            "EMIT" => {
+                self.forbid_in_view()?;
+                let expected = match self.args() {
+                    [] => None,
+                    [_] => Some(self.type_arg(0)?),
+                    args => {
+                        return Err(self.error(format!(
+                            "EMIT expects at most 1 argument, got {}",
+                            args.len()
+                        )))
+                    }
+                };
+                let ty = self.pop1()?;
+                if expected.is_some_and(|expected| expected != ty) || !ty.is_pushable() {
+                    return Err(self.unsupported(&[&ty]));
+                }
+                self.stack.push(Ty::Operation);
+            }
+            "IMPLICIT_ACCOUNT" | "VOTING_POWER" => {
+                self.arity(0)?;
+                match self.pop1()? {
+                    Ty::KeyHash if prim == "VOTING_POWER" => self.stack.push(Ty::Nat),
+                    Ty::KeyHash => self.stack.push(Ty::Contract(Box::new(Ty::Unit))),
+                    ty => return Err(self.unsupported(&[&ty])),
+                }
+            }
+            "CHECK_SIGNATURE" => {
+                self.arity(0)?;
+                match self.pop3()? {
+                    (Ty::Key, Ty::Signature, Ty::Bytes) => self.stack.push(Ty::Bool),
+                    (key, signature, bytes) => {
+                        return Err(self.unsupported(&[&key, &signature, &bytes]))
+                    }
+                }
+            }
+            "PAIRING_CHECK" => {
+                self.arity(0)?;
+                let ty = self.pop1()?;
+                let expected = Ty::List(Box::new(Ty::Pair(
+                    Box::new(Ty::Bls12_381G1),
+                    Box::new(Ty::Bls12_381G2),
+                )));
+                if ty != expected {
+                    return Err(self.unsupported(&[&ty]));
+                }
+                self.stack.push(Ty::Bool);
+            }
+            "SAPLING_EMPTY_STATE" => {
+                self.arity(1)?;
+                match &self.args()[0] {
+                    Micheline::Literal(Literal::Int(memo_size)) => {
+                        let memo_size = types::memo_size(memo_size, &self.path.field(0))?;
+                        self.stack.push(Ty::SaplingState(memo_size))
+                    }
+                    arg => {
+                        return Err(error(
+                            &self.path.field(0),
+                            format!("expected a memo size, got {}", arg.to_compact_string()),
+                        ))
+                    }
+                }
+            }
+            "SAPLING_VERIFY_UPDATE" => {
+                self.arity(0)?;
+                match self.pop2()? {
+                    (Ty::SaplingTransaction(lhs), Ty::SaplingState(rhs)) if lhs == rhs => {
+                        self.stack.push(Ty::Option(Box::new(Ty::comb(vec![
+                            Ty::Bytes,
+                            Ty::Int,
+                            Ty::SaplingState(rhs),
+                        ]))))
+                    }
+                    (lhs, rhs) => return Err(self.unsupported(&[&lhs, &rhs])),
+                }
+            }
+            "TICKET" | "TICKET_DEPRECATED" => {
+                self.arity(0)?;
+                let (ty, amount) = self.pop2()?;
+                if amount != Ty::Nat || !ty.is_comparable() {
+                    return Err(self.unsupported(&[&ty, &amount]));
+                }
+                let ticket = Ty::Ticket(Box::new(ty));
+                if self.prim() == "TICKET" {
+                    self.stack.push(Ty::Option(Box::new(ticket)));
+                } else {
+                    self.stack.push(ticket);
+                }
+            }
+            "READ_TICKET" => {
+                self.arity(0)?;
+                let ticket = self.pop1()?;
+                let ty = match &ticket {
+                    Ty::Ticket(ty) => (**ty).clone(),
+                    _ => return Err(self.unsupported(&[&ticket])),
+                };
+                self.stack.push(ticket);
+                self.stack.push(Ty::comb(vec![Ty::Address, ty, Ty::Nat]));
+            }
+            "SPLIT_TICKET" => {
+                self.arity(0)?;
+                let (ticket, amounts) = self.pop2()?;
+                match (&ticket, &amounts) {
+                    (Ty::Ticket(_), Ty::Pair(lhs, rhs)) if **lhs == Ty::Nat && **rhs == Ty::Nat => {
+                        self.stack
+                            .push(Ty::Option(Box::new(Ty::comb(vec![ticket.clone(), ticket]))))
+                    }
+                    _ => return Err(self.unsupported(&[&ticket, &amounts])),
+                }
+            }
+            "JOIN_TICKETS" => {
+                self.arity(0)?;
+                match self.pop1()? {
+                    Ty::Pair(lhs, rhs) if lhs == rhs && matches!(*lhs, Ty::Ticket(_)) => {
+                        self.stack.push(Ty::Option(lhs))
+                    }
+                    ty => return Err(self.unsupported(&[&ty])),
+                }
+            }
+            "OPEN_CHEST" => {
+                self.arity(0)?;
+                match self.pop3()? {
+                    (Ty::ChestKey, Ty::Chest, Ty::Nat) => self
+                        .stack
+                        .push(Ty::Or(Box::new(Ty::Bytes), Box::new(Ty::Bool))),
+                    (key, chest, time) => return Err(self.unsupported(&[&key, &chest, &time])),
+                }
+            }
+            "VIEW" => {
+                self.arity(2)?;
+                match &self.args()[0] {
+                    Micheline::Literal(Literal::String(name)) if is_valid_name(name.to_str()) => {}
+                    name => {
+                        return Err(error(
+                            &self.path.field(0),
+                            format!("invalid view name {}", name.to_compact_string()),
+                        ))
+                    }
+                }
+                let ty = self.type_arg(1)?;
+                if !ty.is_packable() {
+                    return Err(self.error(format!("type {} can't be used in views", ty)));
+                }
+                let (argument, address) = self.pop2()?;
+                if address != Ty::Address || !argument.is_packable() {
+                    return Err(self.unsupported(&[&argument, &address]));
+                }
+                self.stack.push(Ty::Option(Box::new(ty)));
+            }
+            _ => return Err(self.error(format!("unknown instruction {}", prim))),
+        }
+        Ok(Some(self.stack))
+    }
+}
+
+/// Returns the type pushed by the instructions taking nothing from the stack.
+fn constant(prim: &str) -> Option<Ty> {
+    Some(match prim {
+        "NOW" => Ty::Timestamp,
+        "LEVEL" | "TOTAL_VOTING_POWER" | "MIN_BLOCK_TIME" => Ty::Nat,
+        "AMOUNT" | "BALANCE" => Ty::Mutez,
+        "SOURCE" | "SENDER" | "SELF_ADDRESS" => Ty::Address,
+        "CHAIN_ID" => Ty::ChainId,
+        _ => return None,
+    })
+}
+
+type Unary = fn(&Ty) -> Option<Ty>;
+type Binary = fn(&Ty, &Ty) -> Option<Ty>;
+
+/// Returns the typing rule of the instructions taking the top element of the stack.
+fn unary(prim: &str) -> Option<Unary> {
+    Some(match prim {
+        "ABS" => |ty| matches!(ty, Ty::Int).then_some(Ty::Nat),
+        "ISNAT" => |ty| matches!(ty, Ty::Int).then(|| Ty::Option(Box::new(Ty::Nat))),
+        "INT" => |ty| matches!(ty, Ty::Nat | Ty::Bls12_381Fr | Ty::Bytes).then_some(Ty::Int),
+        "NAT" => |ty| matches!(ty, Ty::Bytes).then_some(Ty::Nat),
+        "BYTES" => |ty| matches!(ty, Ty::Nat | Ty::Int).then_some(Ty::Bytes),
+        "NEG" => |ty| match ty {
+            Ty::Nat | Ty::Int => Some(Ty::Int),
+            Ty::Bls12_381G1 | Ty::Bls12_381G2 | Ty::Bls12_381Fr => Some(ty.clone()),
+            _ => None,
+        },
+        "NOT" => |ty| match ty {
+            Ty::Bool | Ty::Bytes => Some(ty.clone()),
+            Ty::Nat | Ty::Int => Some(Ty::Int),
+            _ => None,
+        },
+        "EQ" | "NEQ" | "LT" | "GT" | "LE" | "GE" => |ty| matches!(ty, Ty::Int).then_some(Ty::Bool),
+        "BLAKE2B" | "KECCAK" | "SHA3" | "SHA256" | "SHA512" => {
+            |ty| matches!(ty, Ty::Bytes).then_some(Ty::Bytes)
+        }
+        "HASH_KEY" => |ty| matches!(ty, Ty::Key).then_some(Ty::KeyHash),
+        "ADDRESS" => |ty| matches!(ty, Ty::Contract(_)).then_some(Ty::Address),
+        _ => return None,
+    })
+}
+
+/// Returns the typing rule of the instructions taking the two top elements of the stack.
+fn arithmetic(prim: &str) -> Option<Binary> {
+    Some(match prim {
+        "ADD" => |lhs, rhs| {
+            Some(match (lhs, rhs) {
+                (Ty::Nat, Ty::Nat) => Ty::Nat,
+                (Ty::Nat | Ty::Int, Ty::Nat | Ty::Int) => Ty::Int,
+                (Ty::Timestamp, Ty::Int) | (Ty::Int, Ty::Timestamp) => Ty::Timestamp,
+                (Ty::Mutez, Ty::Mutez) => Ty::Mutez,
+                (Ty::Bls12_381G1, Ty::Bls12_381G1)
+                | (Ty::Bls12_381G2, Ty::Bls12_381G2)
+                | (Ty::Bls12_381Fr, Ty::Bls12_381Fr) => lhs.clone(),
+                _ => return None,
+            })
+        },
+        "SUB" => |lhs, rhs| {
+            Some(match (lhs, rhs) {
+                (Ty::Nat | Ty::Int, Ty::Nat | Ty::Int) => Ty::Int,
+                (Ty::Timestamp, Ty::Int) => Ty::Timestamp,
+                (Ty::Timestamp, Ty::Timestamp) => Ty::Int,
+                _ => return None,
+            })
+        },
+        "MUL" => |lhs, rhs| {
+            Some(match (lhs, rhs) {
+                (Ty::Nat, Ty::Nat) => Ty::Nat,
+                (Ty::Nat | Ty::Int, Ty::Nat | Ty::Int) => Ty::Int,
+                (Ty::Mutez, Ty::Nat) | (Ty::Nat, Ty::Mutez) => Ty::Mutez,
+                (Ty::Bls12_381G1 | Ty::Bls12_381G2 | Ty::Bls12_381Fr, Ty::Bls12_381Fr) => {
+                    lhs.clone()
+                }
+                (Ty::Nat | Ty::Int, Ty::Bls12_381Fr) | (Ty::Bls12_381Fr, Ty::Nat | Ty::Int) => {
+                    Ty::Bls12_381Fr
+                }
+                _ => return None,
+            })
+        },
+        "EDIV" => |lhs, rhs| {
+            let (quotient, remainder) = match (lhs, rhs) {
+                (Ty::Nat, Ty::Nat) => (Ty::Nat, Ty::Nat),
+                (Ty::Nat | Ty::Int, Ty::Nat | Ty::Int) => (Ty::Int, Ty::Nat),
+                (Ty::Mutez, Ty::Nat) => (Ty::Mutez, Ty::Mutez),
+                (Ty::Mutez, Ty::Mutez) => (Ty::Nat, Ty::Mutez),
+                _ => return None,
+            };
+            Some(Ty::Option(Box::new(Ty::Pair(
+                Box::new(quotient),
+                Box::new(remainder),
+            ))))
+        },
+        "LSL" | "LSR" => |lhs, rhs| match (lhs, rhs) {
+            (Ty::Nat | Ty::Bytes, Ty::Nat) => Some(lhs.clone()),
+            _ => None,
+        },
+        "OR" | "XOR" => |lhs, rhs| match (lhs, rhs) {
+            (Ty::Bool, Ty::Bool) | (Ty::Nat, Ty::Nat) | (Ty::Bytes, Ty::Bytes) => Some(lhs.clone()),
+            _ => None,
+        },
+        "AND" => |lhs, rhs| match (lhs, rhs) {
+            (Ty::Bool, Ty::Bool) | (Ty::Nat, Ty::Nat) | (Ty::Bytes, Ty::Bytes) => Some(lhs.clone()),
+            (Ty::Int, Ty::Nat) => Some(Ty::Nat),
+            _ => None,
+        },
+        _ => return None,
+    })
+}
+
+/// Returns the type of the `index`-th node of a right comb of pairs, as accessed by `GET index`.
+fn comb_get(ty: &Ty, index: usize) -> Option<Ty> {
+    match (index, ty) {
+        (0, _) => Some(ty.clone()),
+        (1, Ty::Pair(left, _)) => Some((**left).clone()),
+        (_, Ty::Pair(_, right)) => comb_get(right, index - 2),
+        _ => None,
+    }
+}
+
+/// Returns the type of a right comb of pairs after `UPDATE index` replaced its `index`-th node with `value`.
+fn comb_update(ty: &Ty, index: usize, value: Ty) -> Option<Ty> {
+    match (index, ty) {
+        (0, _) => Some(value),
+        (1, Ty::Pair(_, right)) => Some(Ty::Pair(Box::new(value), right.clone())),
+        (_, Ty::Pair(left, right)) => Some(Ty::Pair(
+            left.clone(),
+            Box::new(comb_update(right, index - 2, value)?),
+        )),
+        _ => None,
+    }
+}
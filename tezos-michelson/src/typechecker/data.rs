@@ -0,0 +1,454 @@
+use alloc::{
+    borrow::Cow,
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use num_traits::ToPrimitive;
+use tezos_core::types::encoded::{
+    Address, ChainId, Encoded, ImplicitAddress, PublicKey, Signature, SmartRollupAddress,
+};
+
+use super::{code, error, types::Ty, Path};
+use crate::{
+    internal::timestamp::parse_rfc3339,
+    micheline::{
+        literals::{Int, Literal},
+        primitive_application, Micheline,
+    },
+    Result,
+};
+
+/// Length of an address in its binary representation, without the entrypoint.
+const ADDRESS_BYTES_LENGTH: usize = 22;
+/// Tag of the smart rollup addresses in the binary representation of an address.
+const SMART_ROLLUP_ADDRESS_TAG: u8 = 3;
+const BLS12_381_G1_BYTES_LENGTH: usize = 96;
+const BLS12_381_G2_BYTES_LENGTH: usize = 192;
+const BLS12_381_FR_BYTES_LENGTH: usize = 32;
+const DEFAULT_ENTRYPOINT: &[u8] = b"default";
+
+/// A value of a comparable type, ordered the way the protocol orders it.
+///
+/// Values of the same type always map to the same variants, so the derived ordering
+/// is the Michelson one: `False < True`, `None < Some _`, `Left _ < Right _`, pairs compare
+/// lexicographically, and strings, bytes and the encoded types compare by their bytes.
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub(crate) enum ComparableValue {
+    Unit,
+    Bool(bool),
+    Number(Int),
+    Bytes(Vec<u8>),
+    Option(Option<Box<ComparableValue>>),
+    Left(Box<ComparableValue>),
+    Right(Box<ComparableValue>),
+    Pair(Box<ComparableValue>, Box<ComparableValue>),
+}
+
+/// Checks the value against the type, returning its comparable form if the type is comparable.
+pub(crate) fn check(value: &Micheline, ty: &Ty, path: &Path) -> Result<Option<ComparableValue>> {
+    let mismatch = || {
+        error(
+            path,
+            format!("expected {}, got {}", ty, value.to_compact_string()),
+        )
+    };
+    let invalid = |kind: &str| {
+        error(
+            path,
+            format!("invalid {} {}", kind, value.to_compact_string()),
+        )
+    };
+
+    let comparable = match ty {
+        Ty::Unit => {
+            expect_prim(value, "Unit", 0).ok_or_else(mismatch)?;
+            ComparableValue::Unit
+        }
+        Ty::Never => return Err(error(path, "type never has no values")),
+        Ty::Operation => {
+            return Err(error(path, "type operation has no literal values"));
+        }
+        Ty::Bool => {
+            if expect_prim(value, "True", 0).is_some() {
+                ComparableValue::Bool(true)
+            } else if expect_prim(value, "False", 0).is_some() {
+                ComparableValue::Bool(false)
+            } else {
+                return Err(mismatch());
+            }
+        }
+        Ty::Int => ComparableValue::Number(int(value).ok_or_else(mismatch)?.clone()),
+        Ty::Nat => {
+            let value = int(value).ok_or_else(mismatch)?;
+            if *value < Int::from_integer(0) {
+                return Err(invalid("nat"));
+            }
+            ComparableValue::Number(value.clone())
+        }
+        Ty::Mutez => {
+            let value = int(value).ok_or_else(mismatch)?;
+            if !matches!(value.to_i64(), Some(0..)) {
+                return Err(invalid("mutez"));
+            }
+            ComparableValue::Number(value.clone())
+        }
+        Ty::String => {
+            let value = string(value).ok_or_else(mismatch)?;
+            if let Some(c) = value.chars().find(|c| !matches!(c, ' '..='~' | '\n')) {
+                return Err(error(path, format!("invalid character {:?} in string", c)));
+            }
+            ComparableValue::Bytes(value.as_bytes().to_vec())
+        }
+        Ty::Bytes => ComparableValue::Bytes(bytes(value).ok_or_else(mismatch)?),
+        Ty::Timestamp => match value {
+            Micheline::Literal(Literal::Int(value)) => ComparableValue::Number(value.clone()),
+            Micheline::Literal(Literal::String(string)) => {
+                let millis = parse_rfc3339(string.to_str()).ok_or_else(|| invalid("timestamp"))?;
+                ComparableValue::Number(millis.div_euclid(1000).into())
+            }
+            _ => return Err(mismatch()),
+        },
+        Ty::Address | Ty::Contract(_) => {
+            if string(value).is_none() && bytes(value).is_none() {
+                return Err(mismatch());
+            }
+            ComparableValue::Bytes(address(value, path).ok_or_else(|| invalid("address"))??)
+        }
+        Ty::KeyHash => ComparableValue::Bytes(
+            encoded::<ImplicitAddress>(value, Some(21))
+                .ok_or_else(mismatch)?
+                .ok_or_else(|| invalid("key_hash"))?,
+        ),
+        Ty::Key => ComparableValue::Bytes(
+            encoded::<PublicKey>(value, None)
+                .ok_or_else(mismatch)?
+                .ok_or_else(|| invalid("key"))?,
+        ),
+        Ty::Signature => ComparableValue::Bytes(
+            encoded::<Signature>(value, None)
+                .ok_or_else(mismatch)?
+                .ok_or_else(|| invalid("signature"))?,
+        ),
+        Ty::ChainId => ComparableValue::Bytes(
+            encoded::<ChainId>(value, Some(4))
+                .ok_or_else(mismatch)?
+                .ok_or_else(|| invalid("chain_id"))?,
+        ),
+        Ty::Option(inner) => {
+            if expect_prim(value, "None", 0).is_some() {
+                ComparableValue::Option(None)
+            } else {
+                let args = expect_prim(value, "Some", 1).ok_or_else(mismatch)?;
+                let inner = check(&args[0], inner, &path.field("some"))?;
+                return Ok(inner.map(|inner| ComparableValue::Option(Some(Box::new(inner)))));
+            }
+        }
+        Ty::Or(left, right) => {
+            if let Some(args) = expect_prim(value, "Left", 1) {
+                let left = check(&args[0], left, &path.field("left"))?;
+                return Ok(left.map(|left| ComparableValue::Left(Box::new(left))));
+            }
+            let args = expect_prim(value, "Right", 1).ok_or_else(mismatch)?;
+            let right = check(&args[0], right, &path.field("right"))?;
+            return Ok(right.map(|right| ComparableValue::Right(Box::new(right))));
+        }
+        Ty::Pair(_, _) => return check_comb(value, ty, path, 0),
+        Ty::List(inner) => {
+            for (index, element) in sequence(value).ok_or_else(mismatch)?.iter().enumerate() {
+                check(element, inner, &path.index(index))?;
+            }
+            return Ok(None);
+        }
+        Ty::Set(inner) => {
+            let mut previous = None;
+            for (index, element) in sequence(value).ok_or_else(mismatch)?.iter().enumerate() {
+                let path = path.index(index);
+                let element = check(element, inner, &path)?;
+                check_increasing(&mut previous, element, &path, "set elements")?;
+            }
+            return Ok(None);
+        }
+        Ty::Map(key, value_ty) => {
+            check_map(value, key, value_ty, path).ok_or_else(mismatch)??;
+            return Ok(None);
+        }
+        Ty::BigMap(key, value_ty) => {
+            if int(value).is_none() {
+                check_map(value, key, value_ty, path).ok_or_else(mismatch)??;
+            }
+            return Ok(None);
+        }
+        Ty::Lambda(parameter, result) => {
+            if let Some(args) = expect_prim(value, "Lambda_rec", 1) {
+                code::check_lambda_rec(&args[0], parameter, result, &path.field(0))?;
+            } else {
+                sequence(value).ok_or_else(mismatch)?;
+                code::check_lambda(value, parameter, result, path)?;
+            }
+            return Ok(None);
+        }
+        Ty::Ticket(_) => {
+            return Err(error(path, "type ticket has no literal values"));
+        }
+        Ty::Bls12_381G1 | Ty::Bls12_381G2 => {
+            let expected_length = if *ty == Ty::Bls12_381G1 {
+                BLS12_381_G1_BYTES_LENGTH
+            } else {
+                BLS12_381_G2_BYTES_LENGTH
+            };
+            if bytes(value).ok_or_else(mismatch)?.len() != expected_length {
+                return Err(invalid(&ty.to_string()));
+            }
+            return Ok(None);
+        }
+        Ty::Bls12_381Fr => {
+            if int(value).is_none()
+                && bytes(value).ok_or_else(mismatch)?.len() > BLS12_381_FR_BYTES_LENGTH
+            {
+                return Err(invalid("bls12_381_fr"));
+            }
+            return Ok(None);
+        }
+        Ty::SaplingState(_) => {
+            if int(value).is_none() && !sequence(value).is_some_and(|values| values.is_empty()) {
+                return Err(mismatch());
+            }
+            return Ok(None);
+        }
+        Ty::SaplingTransaction(_) | Ty::Chest | Ty::ChestKey => {
+            bytes(value).ok_or_else(mismatch)?;
+            return Ok(None);
+        }
+    };
+    Ok(Some(comparable))
+}
+
+/// Checks a right comb of pairs, numbering its elements from `index` on, so that
+/// `Pair 1 2 3`, `Pair 1 (Pair 2 3)` and `{ 1 ; 2 ; 3 }` get the same paths.
+fn check_comb(
+    value: &Micheline,
+    ty: &Ty,
+    path: &Path,
+    index: usize,
+) -> Result<Option<ComparableValue>> {
+    let (left_ty, right_ty) = match ty {
+        Ty::Pair(left, right) => (left, right),
+        _ => return check(value, ty, &path.field(index)),
+    };
+    let (left, right) = split_pair(value).ok_or_else(|| {
+        let path = if index > 0 {
+            path.field(index)
+        } else {
+            path.clone()
+        };
+        error(
+            &path,
+            format!("expected {}, got {}", ty, value.to_compact_string()),
+        )
+    })?;
+    let left = check(left, left_ty, &path.field(index))?;
+    let right = check_comb(&right, right_ty, path, index + 1)?;
+    Ok(left
+        .zip(right)
+        .map(|(left, right)| ComparableValue::Pair(Box::new(left), Box::new(right))))
+}
+
+/// Splits a pair value written as `Pair a b ...` or `{ a ; b ; ... }` into its first element
+/// and the comb of the remaining ones.
+fn split_pair(value: &Micheline) -> Option<(&Micheline, Cow<'_, Micheline>)> {
+    let values = match value {
+        Micheline::PrimitiveApplication(primitive_application)
+            if primitive_application.prim() == "Pair" =>
+        {
+            primitive_application.args().as_deref()?
+        }
+        Micheline::Sequence(sequence) => sequence.values(),
+        _ => return None,
+    };
+    match values {
+        [] | [_] => None,
+        [left, right] => Some((left, Cow::Borrowed(right))),
+        [left, rest @ ..] => Some((
+            left,
+            Cow::Owned(
+                primitive_application("Pair")
+                    .with_args(rest.to_vec())
+                    .into(),
+            ),
+        )),
+    }
+}
+
+fn check_map(value: &Micheline, key_ty: &Ty, value_ty: &Ty, path: &Path) -> Option<Result<()>> {
+    let elements = sequence(value)?;
+    let mut previous = None;
+    for (index, element) in elements.iter().enumerate() {
+        let path = path.index(index);
+        let args = match expect_prim(element, "Elt", 2) {
+            Some(args) => args,
+            None => {
+                return Some(Err(error(
+                    &path,
+                    format!("expected Elt, got {}", element.to_compact_string()),
+                )))
+            }
+        };
+        let result = check(&args[0], key_ty, &path.field("key")).and_then(|key| {
+            check_increasing(&mut previous, key, &path, "map keys")?;
+            check(&args[1], value_ty, &path.field("value")).map(|_| ())
+        });
+        if result.is_err() {
+            return Some(result);
+        }
+    }
+    Some(Ok(()))
+}
+
+fn check_increasing(
+    previous: &mut Option<ComparableValue>,
+    current: Option<ComparableValue>,
+    path: &Path,
+    kind: &str,
+) -> Result<()> {
+    if let (Some(previous), Some(current)) = (previous.as_ref(), current.as_ref()) {
+        if previous >= current {
+            let description = if previous == current {
+                format!("duplicate {}", kind)
+            } else {
+                format!("{} are not in strictly increasing order", kind)
+            };
+            return Err(error(path, description));
+        }
+    }
+    *previous = current;
+    Ok(())
+}
+
+/// Returns the bytes of an address, followed by its entrypoint, if any.
+///
+/// Returns `None` if the value is not a valid address.
+fn address(value: &Micheline, path: &Path) -> Option<Result<Vec<u8>>> {
+    let (mut bytes, entrypoint) = match value {
+        Micheline::Literal(Literal::String(value)) => {
+            let (address, entrypoint) = match value.to_str().split_once('%') {
+                Some((_, "")) => return Some(Err(error(path, "empty entrypoint"))),
+                Some((address, entrypoint)) => (address, entrypoint.as_bytes()),
+                None => (value.to_str(), [].as_slice()),
+            };
+            (address_bytes(address)?, entrypoint.to_vec())
+        }
+        Micheline::Literal(Literal::Bytes(value)) => {
+            let bytes: Vec<u8> = value.into();
+            if bytes.len() < ADDRESS_BYTES_LENGTH {
+                return None;
+            }
+            if bytes[0] == SMART_ROLLUP_ADDRESS_TAG {
+                SmartRollupAddress::from_bytes(&bytes[1..ADDRESS_BYTES_LENGTH - 1]).ok()?;
+                if bytes[ADDRESS_BYTES_LENGTH - 1] != 0 {
+                    return None;
+                }
+            } else {
+                Address::from_bytes(&bytes[..ADDRESS_BYTES_LENGTH]).ok()?;
+            }
+            (
+                bytes[..ADDRESS_BYTES_LENGTH].to_vec(),
+                bytes[ADDRESS_BYTES_LENGTH..].to_vec(),
+            )
+        }
+        _ => return None,
+    };
+    if entrypoint == DEFAULT_ENTRYPOINT {
+        return Some(Err(error(
+            path,
+            "entrypoint %default must be omitted in an address",
+        )));
+    }
+    if !entrypoint.is_empty() && !core::str::from_utf8(&entrypoint).is_ok_and(code::is_valid_name) {
+        return Some(Err(error(
+            path,
+            format!(
+                "invalid entrypoint {}",
+                String::from_utf8_lossy(&entrypoint)
+            ),
+        )));
+    }
+    bytes.extend(entrypoint);
+    Some(Ok(bytes))
+}
+
+/// Returns the binary representation of an implicit, originated or smart rollup address.
+fn address_bytes(address: &str) -> Option<Vec<u8>> {
+    if let Ok(address) = Address::new(address.into()) {
+        return address.to_bytes().ok();
+    }
+    let address = SmartRollupAddress::new(address.into()).ok()?;
+    let mut bytes = vec![SMART_ROLLUP_ADDRESS_TAG];
+    bytes.extend(address.to_bytes().ok()?);
+    bytes.push(0);
+    Some(bytes)
+}
+
+/// Returns the bytes of an encoded value written either in base58 or in bytes.
+///
+/// Returns `None` if the value is neither a string nor bytes, and `Some(None)` if it's not a valid
+/// value of the type.
+fn encoded<E: Encoded>(value: &Micheline, bytes_length: Option<usize>) -> Option<Option<Vec<u8>>> {
+    match value {
+        Micheline::Literal(Literal::String(value)) => Some(
+            E::new(value.to_str().into())
+                .and_then(|value| value.to_bytes())
+                .ok(),
+        ),
+        Micheline::Literal(Literal::Bytes(value)) => {
+            let bytes: Vec<u8> = value.into();
+            if bytes_length.is_some_and(|length| length != bytes.len()) {
+                return Some(None);
+            }
+            Some(E::from_bytes(&bytes).ok().map(|_| bytes))
+        }
+        _ => None,
+    }
+}
+
+fn expect_prim<'a>(value: &'a Micheline, prim: &str, args_count: usize) -> Option<&'a [Micheline]> {
+    match value {
+        Micheline::PrimitiveApplication(primitive_application)
+            if primitive_application.prim() == prim
+                && primitive_application.args_count() == args_count =>
+        {
+            Some(primitive_application.args().as_deref().unwrap_or_default())
+        }
+        _ => None,
+    }
+}
+
+fn int(value: &Micheline) -> Option<&Int> {
+    match value {
+        Micheline::Literal(Literal::Int(value)) => Some(value),
+        _ => None,
+    }
+}
+
+fn string(value: &Micheline) -> Option<&str> {
+    match value {
+        Micheline::Literal(Literal::String(value)) => Some(value.to_str()),
+        _ => None,
+    }
+}
+
+fn bytes(value: &Micheline) -> Option<Vec<u8>> {
+    match value {
+        Micheline::Literal(Literal::Bytes(value)) => Some(value.into()),
+        _ => None,
+    }
+}
+
+fn sequence(value: &Micheline) -> Option<&[Micheline]> {
+    match value {
+        Micheline::Sequence(sequence) => Some(sequence.values()),
+        _ => None,
+    }
+}
@@ -0,0 +1,349 @@
+use alloc::{boxed::Box, format, string::ToString, vec, vec::Vec};
+use core::fmt;
+
+use super::{error, Path};
+use crate::{
+    micheline::{
+        literals::{Int, Literal},
+        primitive_application, Micheline,
+    },
+    Result,
+};
+
+/// A Michelson type stripped of its annotations, with the pair combs folded into binary pairs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Ty {
+    Unit,
+    Never,
+    Bool,
+    Int,
+    Nat,
+    String,
+    ChainId,
+    Bytes,
+    Mutez,
+    KeyHash,
+    Key,
+    Signature,
+    Timestamp,
+    Address,
+    Operation,
+    Option(Box<Ty>),
+    Or(Box<Ty>, Box<Ty>),
+    Pair(Box<Ty>, Box<Ty>),
+    List(Box<Ty>),
+    Set(Box<Ty>),
+    Map(Box<Ty>, Box<Ty>),
+    BigMap(Box<Ty>, Box<Ty>),
+    Contract(Box<Ty>),
+    Ticket(Box<Ty>),
+    Lambda(Box<Ty>, Box<Ty>),
+    Bls12_381G1,
+    Bls12_381G2,
+    Bls12_381Fr,
+    SaplingTransaction(Int),
+    SaplingState(Int),
+    Chest,
+    ChestKey,
+}
+
+impl Ty {
+    /// Reads a type from its Micheline representation, checking that the types which require
+    /// comparable arguments get comparable ones.
+    pub fn parse(value: &Micheline, path: &Path) -> Result<Self> {
+        let primitive_application = match value {
+            Micheline::PrimitiveApplication(value) => value,
+            _ => return Err(error(path, "expected a type")),
+        };
+        let args: &[Micheline] = primitive_application
+            .args()
+            .as_ref()
+            .map(|args| args.as_slice())
+            .unwrap_or_default();
+        let prim = primitive_application.prim();
+        let arity = |expected: usize| {
+            if args.len() == expected {
+                Ok(())
+            } else {
+                Err(error(
+                    path,
+                    format!(
+                        "type {} expects {} argument(s), got {}",
+                        prim,
+                        expected,
+                        args.len()
+                    ),
+                ))
+            }
+        };
+        let arg =
+            |index: usize| -> Result<Box<Ty>> { Ok(Box::new(Self::parse(&args[index], path)?)) };
+        let comparable_arg = |index: usize| -> Result<Box<Ty>> {
+            let ty = arg(index)?;
+            if !ty.is_comparable() {
+                return Err(error(
+                    path,
+                    format!("type {} expects a comparable type, got {}", prim, ty),
+                ));
+            }
+            Ok(ty)
+        };
+        let simple = |ty: Ty| arity(0).map(|_| ty);
+
+        match prim {
+            "unit" => simple(Self::Unit),
+            "never" => simple(Self::Never),
+            "bool" => simple(Self::Bool),
+            "int" => simple(Self::Int),
+            "nat" => simple(Self::Nat),
+            "string" => simple(Self::String),
+            "chain_id" => simple(Self::ChainId),
+            "bytes" => simple(Self::Bytes),
+            "mutez" => simple(Self::Mutez),
+            "key_hash" => simple(Self::KeyHash),
+            "key" => simple(Self::Key),
+            "signature" => simple(Self::Signature),
+            "timestamp" => simple(Self::Timestamp),
+            "address" => simple(Self::Address),
+            "operation" => simple(Self::Operation),
+            "bls12_381_g1" => simple(Self::Bls12_381G1),
+            "bls12_381_g2" => simple(Self::Bls12_381G2),
+            "bls12_381_fr" => simple(Self::Bls12_381Fr),
+            "chest" => simple(Self::Chest),
+            "chest_key" => simple(Self::ChestKey),
+            "option" => arity(1).and_then(|_| Ok(Self::Option(arg(0)?))),
+            "list" => arity(1).and_then(|_| Ok(Self::List(arg(0)?))),
+            "set" => arity(1).and_then(|_| Ok(Self::Set(comparable_arg(0)?))),
+            "contract" => {
+                arity(1)?;
+                let ty = arg(0)?;
+                if !ty.is_passable() {
+                    return Err(error(
+                        path,
+                        format!("type {} can't be passed to contracts", ty),
+                    ));
+                }
+                Ok(Self::Contract(ty))
+            }
+            "ticket" => arity(1).and_then(|_| Ok(Self::Ticket(comparable_arg(0)?))),
+            "or" => arity(2).and_then(|_| Ok(Self::Or(arg(0)?, arg(1)?))),
+            "lambda" => arity(2).and_then(|_| Ok(Self::Lambda(arg(0)?, arg(1)?))),
+            "map" => arity(2).and_then(|_| Ok(Self::Map(comparable_arg(0)?, arg(1)?))),
+            "big_map" => {
+                arity(2)?;
+                let (key, value) = (comparable_arg(0)?, arg(1)?);
+                if !value.is_big_map_value() {
+                    return Err(error(
+                        path,
+                        format!("type {} can't be used as a big_map value", value),
+                    ));
+                }
+                Ok(Self::BigMap(key, value))
+            }
+            "pair" => {
+                if args.len() < 2 {
+                    return Err(error(
+                        path,
+                        format!("type pair expects at least 2 arguments, got {}", args.len()),
+                    ));
+                }
+                let types = args
+                    .iter()
+                    .map(|arg| Self::parse(arg, path))
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(Self::comb(types))
+            }
+            "sapling_transaction" | "sapling_state" => {
+                arity(1)?;
+                let memo_size = match &args[0] {
+                    Micheline::Literal(Literal::Int(value)) => memo_size(value, path)?,
+                    _ => return Err(error(path, format!("type {} expects a memo size", prim))),
+                };
+                if prim == "sapling_state" {
+                    Ok(Self::SaplingState(memo_size))
+                } else {
+                    Ok(Self::SaplingTransaction(memo_size))
+                }
+            }
+            _ => Err(error(path, format!("unknown type {}", prim))),
+        }
+    }
+
+    /// Folds the types into a right comb of pairs.
+    pub fn comb(mut types: Vec<Ty>) -> Self {
+        let right = if types.len() == 2 {
+            types.pop().unwrap()
+        } else {
+            Self::comb(types.split_off(1))
+        };
+        Self::Pair(Box::new(types.remove(0)), Box::new(right))
+    }
+
+    /// Splits a right comb of pairs into its first `n` elements, the last one being the rest of the comb.
+    pub fn uncomb(&self, n: usize) -> Option<Vec<Ty>> {
+        match self {
+            _ if n == 1 => Some(vec![self.clone()]),
+            Self::Pair(left, right) => {
+                let mut types = vec![(**left).clone()];
+                types.extend(right.uncomb(n - 1)?);
+                Some(types)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns true if the type or one of the types it's made of, not counting lambdas
+    /// and contracts, matches the predicate.
+    fn has(&self, predicate: fn(&Ty) -> bool) -> bool {
+        if predicate(self) {
+            return true;
+        }
+        match self {
+            Self::Option(ty) | Self::List(ty) | Self::Set(ty) => ty.has(predicate),
+            Self::Or(lhs, rhs)
+            | Self::Pair(lhs, rhs)
+            | Self::Map(lhs, rhs)
+            | Self::BigMap(lhs, rhs) => lhs.has(predicate) || rhs.has(predicate),
+            _ => false,
+        }
+    }
+
+    pub fn is_pushable(&self) -> bool {
+        !self.has(|ty| {
+            matches!(
+                ty,
+                Self::Operation
+                    | Self::BigMap(_, _)
+                    | Self::SaplingState(_)
+                    | Self::Ticket(_)
+                    | Self::Contract(_)
+            )
+        })
+    }
+
+    pub fn is_packable(&self) -> bool {
+        !self.has(|ty| {
+            matches!(
+                ty,
+                Self::Operation | Self::BigMap(_, _) | Self::SaplingState(_) | Self::Ticket(_)
+            )
+        })
+    }
+
+    pub fn is_storable(&self) -> bool {
+        !self.has(|ty| matches!(ty, Self::Operation | Self::Contract(_)))
+    }
+
+    pub fn is_passable(&self) -> bool {
+        !self.has(|ty| matches!(ty, Self::Operation))
+    }
+
+    pub fn is_dupable(&self) -> bool {
+        !self.has(|ty| matches!(ty, Self::Ticket(_)))
+    }
+
+    pub fn is_big_map_value(&self) -> bool {
+        !self.has(|ty| {
+            matches!(
+                ty,
+                Self::Operation | Self::BigMap(_, _) | Self::SaplingState(_)
+            )
+        })
+    }
+
+    pub fn is_comparable(&self) -> bool {
+        match self {
+            Self::Unit
+            | Self::Never
+            | Self::Bool
+            | Self::Int
+            | Self::Nat
+            | Self::String
+            | Self::ChainId
+            | Self::Bytes
+            | Self::Mutez
+            | Self::KeyHash
+            | Self::Key
+            | Self::Signature
+            | Self::Timestamp
+            | Self::Address => true,
+            Self::Option(ty) => ty.is_comparable(),
+            Self::Or(lhs, rhs) | Self::Pair(lhs, rhs) => lhs.is_comparable() && rhs.is_comparable(),
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Ty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Unit => "unit",
+            Self::Never => "never",
+            Self::Bool => "bool",
+            Self::Int => "int",
+            Self::Nat => "nat",
+            Self::String => "string",
+            Self::ChainId => "chain_id",
+            Self::Bytes => "bytes",
+            Self::Mutez => "mutez",
+            Self::KeyHash => "key_hash",
+            Self::Key => "key",
+            Self::Signature => "signature",
+            Self::Timestamp => "timestamp",
+            Self::Address => "address",
+            Self::Operation => "operation",
+            Self::Bls12_381G1 => "bls12_381_g1",
+            Self::Bls12_381G2 => "bls12_381_g2",
+            Self::Bls12_381Fr => "bls12_381_fr",
+            Self::Chest => "chest",
+            Self::ChestKey => "chest_key",
+            Self::Option(ty) => return write!(f, "(option {})", ty),
+            Self::List(ty) => return write!(f, "(list {})", ty),
+            Self::Set(ty) => return write!(f, "(set {})", ty),
+            Self::Contract(ty) => return write!(f, "(contract {})", ty),
+            Self::Ticket(ty) => return write!(f, "(ticket {})", ty),
+            Self::Or(lhs, rhs) => return write!(f, "(or {} {})", lhs, rhs),
+            Self::Pair(lhs, rhs) => return write!(f, "(pair {} {})", lhs, rhs),
+            Self::Lambda(lhs, rhs) => return write!(f, "(lambda {} {})", lhs, rhs),
+            Self::Map(lhs, rhs) => return write!(f, "(map {} {})", lhs, rhs),
+            Self::BigMap(lhs, rhs) => return write!(f, "(big_map {} {})", lhs, rhs),
+            Self::SaplingTransaction(memo_size) => {
+                return write!(f, "(sapling_transaction {})", memo_size)
+            }
+            Self::SaplingState(memo_size) => return write!(f, "(sapling_state {})", memo_size),
+        };
+        f.write_str(name)
+    }
+}
+
+impl From<&Ty> for Micheline {
+    fn from(value: &Ty) -> Self {
+        let (prim, args): (_, Vec<Micheline>) = match value {
+            Ty::Option(ty) => ("option", vec![(&**ty).into()]),
+            Ty::List(ty) => ("list", vec![(&**ty).into()]),
+            Ty::Set(ty) => ("set", vec![(&**ty).into()]),
+            Ty::Contract(ty) => ("contract", vec![(&**ty).into()]),
+            Ty::Ticket(ty) => ("ticket", vec![(&**ty).into()]),
+            Ty::Or(lhs, rhs) => ("or", vec![(&**lhs).into(), (&**rhs).into()]),
+            Ty::Pair(lhs, rhs) => ("pair", vec![(&**lhs).into(), (&**rhs).into()]),
+            Ty::Lambda(lhs, rhs) => ("lambda", vec![(&**lhs).into(), (&**rhs).into()]),
+            Ty::Map(lhs, rhs) => ("map", vec![(&**lhs).into(), (&**rhs).into()]),
+            Ty::BigMap(lhs, rhs) => ("big_map", vec![(&**lhs).into(), (&**rhs).into()]),
+            Ty::SaplingTransaction(memo_size) => ("sapling_transaction", vec![memo_size.into()]),
+            Ty::SaplingState(memo_size) => ("sapling_state", vec![memo_size.into()]),
+            _ => return primitive_application(value.to_string()).into(),
+        };
+        primitive_application(prim).with_args(args).into()
+    }
+}
+
+/// Checks that the memo size of a Sapling type fits in 16 bits.
+pub(super) fn memo_size(value: &Int, path: &Path) -> Result<Int> {
+    match value.to_integer::<u16>() {
+        Ok(_) => Ok(value.clone()),
+        Err(_) => Err(error(
+            path,
+            format!("invalid memo size {}, expected 0 to 65535", value),
+        )),
+    }
+}
@@ -72,7 +72,7 @@ mod test {
         };
 
         let payload = OperationContentBytesCoder::encode(&header)?;
-        let hash = Crypto::new(None, None, None)
+        let hash = Crypto::new(None, None, None, None)
             .blake2b(payload.as_slice(), 32)?;
     
         let actual = BlockHash::from_bytes(&hash)?;
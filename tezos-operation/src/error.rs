@@ -9,6 +9,7 @@ pub enum Error {
     InvalidOperationContentTag,
     InvalidBytes,
     InvalidStringConversion { source: FromUtf8Error },
+    UnknownPublicKeyHash,
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
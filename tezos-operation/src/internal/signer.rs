@@ -1,11 +1,13 @@
 use tezos_core::{
     internal::crypto::Crypto,
     types::encoded::{
-        Ed25519PublicKey, Ed25519SecretKey, Ed25519Signature, Encoded, P256PublicKey,
-        P256SecretKey, P256Signature, PublicKey, Secp256K1PublicKey, Secp256K1SecretKey,
-        Secp256K1Signature, SecretKey, Signature,
+        Bls12381PublicKey, Bls12381SecretKey, Bls12381Signature, Ed25519PublicKey,
+        Ed25519SecretKey, Ed25519Signature, Encoded, P256PublicKey, P256SecretKey, P256Signature,
+        PublicKey, Secp256K1PublicKey, Secp256K1SecretKey, Secp256K1Signature, SecretKey,
+        Signature,
     },
 };
+use zeroize::Zeroizing;
 
 use crate::{
     operations::{Operation, SignedOperation, UnsignedOperation},
@@ -55,11 +57,46 @@ impl OperationSigner {
         signer(&self.hash(operation)?, key)
     }
 
+    /// Signs the already watermarked message with the given secret key.
+    pub fn sign_message(&self, message: &[u8], secret: &SecretKey) -> Result<Signature> {
+        let key = Zeroizing::new(secret.to_bytes()?);
+
+        Ok(match secret {
+            SecretKey::Ed25519(_) => {
+                let signature = self
+                    .crypto
+                    .sign_ed25519(&self.hash_message(message)?, &key)?;
+                Signature::Ed25519((&signature).try_into()?)
+            }
+            SecretKey::Secp256K1(_) => {
+                let signature = self
+                    .crypto
+                    .sign_secp256_k1(&self.hash_message(message)?, &key)?;
+                Signature::Secp256K1((&signature).try_into()?)
+            }
+            SecretKey::P256(_) => {
+                let signature = self.crypto.sign_p256(&self.hash_message(message)?, &key)?;
+                Signature::P256((&signature).try_into()?)
+            }
+            SecretKey::Bls12381(_) => {
+                let signature = self.crypto.sign_bls12_381(message, &key)?;
+                Signature::Bls12381((&signature).try_into()?)
+            }
+        })
+    }
+
+    /// Returns the watermarked message that is signed to authorize the operation.
+    pub fn message<O: Operation>(operation: &O) -> Result<Vec<u8>> {
+        Ok([&[Self::WATERMARK], operation.to_forged_bytes()?.as_slice()].concat())
+    }
+
     fn hash<O: Operation>(&self, operation: &O) -> Result<Vec<u8>> {
-        Ok(self.crypto.blake2b(
-            &[&[Self::WATERMARK], operation.to_forged_bytes()?.as_slice()].concat(),
-            Self::MESSAGE_HASH_SIZE,
-        )?)
+        self.hash_message(&Self::message(operation)?)
+    }
+
+    /// BLS signatures are computed over the watermarked message directly, without hashing it first.
+    fn hash_message(&self, message: &[u8]) -> Result<Vec<u8>> {
+        Ok(self.crypto.blake2b(message, Self::MESSAGE_HASH_SIZE)?)
     }
 
     fn verify_raw<F>(&self, operation: &SignedOperation, key: &[u8], verifier: F) -> Result<bool>
@@ -82,6 +119,7 @@ impl Signer<SecretKey> for OperationSigner {
             SecretKey::Ed25519(key) => self.sign(message, key).map(|signature| signature.into()),
             SecretKey::Secp256K1(key) => self.sign(message, key).map(|signature| signature.into()),
             SecretKey::P256(key) => self.sign(message, key).map(|signature| signature.into()),
+            SecretKey::Bls12381(key) => self.sign(message, key).map(|signature| signature.into()),
         }
     }
 }
@@ -94,6 +132,7 @@ impl Verifier<PublicKey> for OperationSigner {
             PublicKey::Ed25519(key) => self.verify(message, key),
             PublicKey::Secp256K1(key) => self.verify(message, key),
             PublicKey::P256(key) => self.verify(message, key),
+            PublicKey::Bls12381(key) => self.verify(message, key),
         }
     }
 }
@@ -104,7 +143,7 @@ impl Signer<Ed25519SecretKey> for OperationSigner {
     type Error = Error;
 
     fn sign(&self, message: &Self::Message, secret: &Ed25519SecretKey) -> Result<Self::Output> {
-        let key = secret.to_bytes()?;
+        let key = Zeroizing::new(secret.to_bytes()?);
         let signature = self.sign_raw(message, &key, |message, secret| {
             Ok(self.crypto.sign_ed25519(message, secret)?)
         })?;
@@ -130,7 +169,7 @@ impl Signer<Secp256K1SecretKey> for OperationSigner {
     type Error = Error;
 
     fn sign(&self, message: &Self::Message, secret: &Secp256K1SecretKey) -> Result<Self::Output> {
-        let key = secret.to_bytes()?;
+        let key = Zeroizing::new(secret.to_bytes()?);
         let signature = self.sign_raw(message, &key, |message, secret| {
             Ok(self.crypto.sign_secp256_k1(message, secret)?)
         })?;
@@ -156,7 +195,7 @@ impl Signer<P256SecretKey> for OperationSigner {
     type Error = Error;
 
     fn sign(&self, message: &Self::Message, secret: &P256SecretKey) -> Result<Self::Output> {
-        let key = secret.to_bytes()?;
+        let key = Zeroizing::new(secret.to_bytes()?);
         let signature = self.sign_raw(message, &key, |message, secret| {
             Ok(self.crypto.sign_p256(message, secret)?)
         })?;
@@ -176,9 +215,40 @@ impl Verifier<P256PublicKey> for OperationSigner {
     }
 }
 
+impl Signer<Bls12381SecretKey> for OperationSigner {
+    type Message = UnsignedOperation;
+    type Output = Bls12381Signature;
+    type Error = Error;
+
+    fn sign(&self, message: &Self::Message, secret: &Bls12381SecretKey) -> Result<Self::Output> {
+        let key = Zeroizing::new(secret.to_bytes()?);
+        let signature = self.crypto.sign_bls12_381(&Self::message(message)?, &key)?;
+
+        Ok((&signature).try_into()?)
+    }
+}
+
+impl Verifier<Bls12381PublicKey> for OperationSigner {
+    type Message = SignedOperation;
+
+    fn verify(&self, message: &Self::Message, key: &Bls12381PublicKey) -> Result<bool> {
+        let signature = message.signature.to_bytes()?;
+        Self::message(message).map_or(Ok(false), |message| {
+            Ok(self
+                .crypto
+                .verify_bls12_381(&message, &signature, &key.to_bytes()?)?)
+        })
+    }
+}
+
 #[cfg(test)]
 mod test {
-    #[cfg(any(feature = "ed25519", feature = "secp256_k1", feature = "p256"))]
+    #[cfg(any(
+        feature = "ed25519",
+        feature = "secp256_k1",
+        feature = "p256",
+        feature = "bls"
+    ))]
     use crate::operations::SeedNonceRevelation;
 
     use super::*;
@@ -241,6 +311,18 @@ mod test {
         )
     }
 
+    #[cfg(feature = "bls")]
+    fn bls12_381_pair() -> (SecretKey, PublicKey) {
+        (
+            "BLsk1WMaoyRDXHuLDViHoExYpeCE52AH9y3n2YZUrF1yYPqgkMxLQB"
+                .try_into()
+                .unwrap(),
+            "BLpk1zKCZWUm6fXHxeLZY8jyv8m4cNKvYtM1yVC6uE9fViEGwU5CMRF8kL1DUKtdaqYmX71FJPCe"
+                .try_into()
+                .unwrap(),
+        )
+    }
+
     fn operations_with_signatures(
     ) -> Vec<((SecretKey, PublicKey), Vec<(UnsignedOperation, Signature)>)> {
         vec![
@@ -312,7 +394,23 @@ mod test {
                         "p2sigPCKxgbzaH1zxX8Hb4cFvTpUUK2rbESVSPbvgcVSUmzfP8Q3kLYwNkATj2bJrDdPEAn8xqSWq6pia3Sidb2LQzC2DkSTVZ".try_into().unwrap(), // "p2sigrjm1STjRF4ygPiPzd4L34MzCErExERsH79jWwJTdYqdaYbYA29UfE1y8f78268B2xNdT3gzR5tXR7G21DCYyYkGnFe3Dm".try_into().unwrap(),
                     ),
                 ]
-            )
+            ),
+            #[cfg(feature = "bls")]
+            (
+                bls12_381_pair(),
+                vec![
+                    (
+                        UnsignedOperation::new("BLjg4HU2BwnCgJfRutxJX5rHACzLDxRJes1MXqbXXdxvHWdK3Te".try_into().unwrap(), vec![]),
+                        "BLsigBTypkrXJze2bz2Pj3UvX97H7TzTkk3PCdBYYGv3ChH3ENcvvopTbn6N3ZaggdtAUxLoKemisvVrxVG1mqTLx3PpQcx14R6aUv1cVbotAzhdmkYvkVnLJpFcDZ59GSHtSgLPGXMp47".try_into().unwrap(),
+                    ),
+                    (
+                        UnsignedOperation::new("BLjg4HU2BwnCgJfRutxJX5rHACzLDxRJes1MXqbXXdxvHWdK3Te".try_into().unwrap(), vec![
+                            SeedNonceRevelation::new(1, "6cdaf9367e551995a670a5c642a9396290f8c9d17e6bc3c1555bfaa910d92214".try_into().unwrap()).into()
+                        ]),
+                        "BLsigABHk3RjcPVUMoG6fSN4KAbHbmmeWyCiBYijZMiLssrDXnJbicPz87wkv5PtrzyTeJqYg6VmwACsoBQ1P9Huh5DnT2QSRiwZg9HDFkk72eDCxaFDUCZ7dYZTXueGMT1Y3zr4fxeRCr".try_into().unwrap(),
+                    ),
+                ]
+            ),
         ]
     }
 }
@@ -139,10 +139,26 @@
 //! # #[cfg(feature = "ed25519")]
 //! let is_signature_valid = signed.verify(&public_key.into()).expect("verification completed without errors");
 //! ```
+//!
+//! When the secret key is not available in the process, e.g. because it is kept in an HSM or by a remote signer, implement
+//! the asynchronous [Signer](crate::signer::Signer) trait and use [UnsignedOperation::into_signed_operation_using](crate::operations::UnsignedOperation::into_signed_operation_using).
+//! The [InMemorySigner](crate::signer::InMemorySigner) implements the trait for secret keys held in memory:
+//!
+//! ```rust
+//! use tezos_operation::{operations::UnsignedOperation, signer::InMemorySigner, Result};
+//!
+//! async fn example(unsigned: UnsignedOperation) -> Result<()> {
+//!     let mut signer = InMemorySigner::new();
+//!     let public_key_hash = signer.add_key("edskRv7VyXGVZb8EsrR7D9XKUbbAQNQGtALP6QeB16ZCD7SmmJpzyeneJVg3Mq56YLbxRA1kSdAXiswwPiaVfR3NHGMCXCziuZ".try_into()?)?;
+//!     let signed = unsigned.into_signed_operation_using(&signer, &public_key_hash).await?;
+//!     Ok(())
+//! }
+//! ```
 
 pub mod block_header;
 mod error;
 pub mod internal;
 pub mod operations;
+pub mod signer;
 
 pub use error::{Error, Result};
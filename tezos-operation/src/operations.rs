@@ -22,7 +22,10 @@ use tezos_core::{
         coder::{Decoder, Encoder},
     },
     types::{
-        encoded::{BlockHash, Encoded, PublicKey, SecretKey, Signature, OperationHash},
+        encoded::{
+            BlockHash, ContractAddress, Encoded, ImplicitAddress, OperationHash, PublicKey,
+            SecretKey, Signature,
+        },
         mutez::Mutez,
     },
     Tezos,
@@ -126,6 +129,9 @@ impl UnsignedOperation {
     }
 
     /// Creates a [SignedOperation] by signing the [UnsignedOperation] with the given secret key.
+    ///
+    /// Use [UnsignedOperation::into_signed_operation_using] to sign with any [Signer](crate::signer::Signer),
+    /// e.g. a remote signer or a hardware wallet.
     pub fn into_signed_operation(self, key: &SecretKey) -> Result<SignedOperation> {
         let tezos: Tezos = Default::default();
 
@@ -146,6 +152,30 @@ impl UnsignedOperation {
 
         self.sign_with(key, &tezos)
     }
+
+    /// Creates a [SignedOperation] by signing the [UnsignedOperation] with the key of the given account
+    /// held by the [Signer](crate::signer::Signer).
+    pub async fn into_signed_operation_using<S: crate::signer::Signer + ?Sized>(
+        self,
+        signer: &S,
+        public_key_hash: &ImplicitAddress,
+    ) -> Result<SignedOperation> {
+        let signature = self.sign_using(signer, public_key_hash).await?;
+
+        Ok(SignedOperation::new(self.branch, self.contents, signature))
+    }
+
+    /// Creates a [Signature] by signing the [UnsignedOperation] with the key of the given account
+    /// held by the [Signer](crate::signer::Signer).
+    pub async fn sign_using<S: crate::signer::Signer + ?Sized>(
+        &self,
+        signer: &S,
+        public_key_hash: &ImplicitAddress,
+    ) -> Result<Signature> {
+        let message = OperationSigner::message(self)?;
+
+        signer.sign(public_key_hash, &message).await
+    }
 }
 
 impl Operation for UnsignedOperation {
@@ -231,7 +261,7 @@ impl SignedOperation {
 
     /// Static method that calculates and encodes operation hash given its forged bytes (including signature)
     pub fn operation_hash(payload: &[u8]) -> Result<OperationHash> {
-        let crypto = Crypto::new(None, None, None);
+        let crypto = Crypto::new(None, None, None, None);
         let hash = crypto.blake2b(payload, 32)?;
         OperationHash::from_bytes(&hash).map_err(|e| e.into())
     }
@@ -241,6 +271,24 @@ impl SignedOperation {
         let payload = [self.to_forged_bytes()?, self.signature.to_bytes()?].concat();
         Self::operation_hash(payload.as_slice())
     }
+
+    /// Calculates the addresses of the contracts originated by the [Origination] contents of the operation,
+    /// in the order of the contents.
+    ///
+    /// The addresses are only correct if no content before an [Origination] originates contracts internally,
+    /// e.g. a [Transaction] to a contract performing a `CREATE_CONTRACT`, as those consume origination indices too.
+    pub fn originated_contracts(&self) -> Result<Vec<ContractAddress>> {
+        let hash = self.hash()?;
+        let originations = self
+            .contents
+            .iter()
+            .filter(|content| matches!(content, OperationContent::Origination(_)))
+            .count() as u32;
+
+        Ok((0..originations)
+            .map(|index| ContractAddress::from_origination(&hash, index))
+            .collect::<tezos_core::Result<Vec<_>>>()?)
+    }
 }
 
 impl Operation for SignedOperation {
@@ -808,4 +856,46 @@ mod test {
         assert_eq!(expected, actual);
         Ok(())
     }
+
+    #[test]
+    fn test_originated_contracts() -> Result<()> {
+        let origination: OperationContent = Origination::new(
+            "tz1V3dHSCJnWPRdzDmZGCZaTMuiTmbtPakmU".try_into().unwrap(),
+            417u32.into(),
+            2336132u32.into(),
+            1527u32.into(),
+            357u32.into(),
+            0u8.into(),
+            None,
+            Script::new(vec![].into(), vec![].into()),
+        )
+        .into();
+        let transaction: OperationContent = Transaction::new(
+            "tz1V3dHSCJnWPRdzDmZGCZaTMuiTmbtPakmU".try_into().unwrap(),
+            417u32.into(),
+            2336133u32.into(),
+            1527u32.into(),
+            357u32.into(),
+            498719u32.into(),
+            "tz1d5Dr3gjsxQo5XNbjAj558mLy3nGGQgMFA".try_into().unwrap(),
+            None,
+        )
+        .into();
+        let opg = SignedOperation::new(
+            "BMNvSHmWUkdonkG2oFwwQKxHUdrYQhUXqxLaSRX9wjMGfLddURC".try_into().unwrap(),
+            vec![origination.clone(), transaction, origination],
+            "sigw1WNdYweqz1c7zKcvZFHQ18swSv4HBWje5quRmixxitPk7z8jtY63qXgKLPVfTM6XGxExPatBWJP44Bknyu3hDHDKJZgY".try_into().unwrap()
+        );
+        let hash = opg.hash()?;
+
+        let expected = vec![
+            ContractAddress::from_origination(&hash, 0)?,
+            ContractAddress::from_origination(&hash, 1)?,
+        ];
+        assert_eq!(expected, opg.originated_contracts()?);
+
+        let opg = SignedOperation::new(opg.branch, vec![], opg.signature);
+        assert!(opg.originated_contracts()?.is_empty());
+        Ok(())
+    }
 }
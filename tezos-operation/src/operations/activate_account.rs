@@ -1,6 +1,10 @@
-use tezos_core::types::{encoded::Ed25519PublicKeyHash, hex_string::HexString};
+use tezos_core::types::{
+    encoded::{Ed25519BlindedPublicKeyHash, Ed25519PublicKeyHash},
+    hex_string::HexString,
+};
 
 use super::{OperationContentTag, TraitOperationContent};
+use crate::Result;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ActivateAccount {
@@ -12,6 +16,17 @@ impl ActivateAccount {
     pub fn new(pkh: Ed25519PublicKeyHash, secret: HexString) -> Self {
         Self { pkh, secret }
     }
+
+    /// Computes the blinded public key hash the activation is checked against.
+    pub fn blinded_public_key_hash(&self) -> Result<Ed25519BlindedPublicKeyHash> {
+        Ok(self.pkh.blinded(&self.secret.to_bytes())?)
+    }
+
+    /// Returns true if the activation matches the given commitment, i.e. the operation would activate
+    /// the fundraiser account committed to with that blinded public key hash.
+    pub fn matches_commitment(&self, commitment: &Ed25519BlindedPublicKeyHash) -> Result<bool> {
+        Ok(&self.blinded_public_key_hash()? == commitment)
+    }
 }
 
 impl TraitOperationContent for ActivateAccount {
@@ -19,3 +34,25 @@ impl TraitOperationContent for ActivateAccount {
         OperationContentTag::ActivateAccount
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_matches_commitment() -> Result<()> {
+        let activation = ActivateAccount::new(
+            "tz1PokEhtiBGCmekQrcN87pCDmqy99TjaLuN".try_into()?,
+            "7b27ba02550e6834b50173c8c506de42d901c606".try_into()?,
+        );
+
+        assert!(
+            activation.matches_commitment(&"btz1VdTKLHhJJXu7Hyx5DrPZ5zT4rLJeKSniS".try_into()?)?
+        );
+        assert!(
+            !activation.matches_commitment(&"btz1LKdDpaKfVBUiFvxhj7hZ6ZYurnMBiEv5z".try_into()?)?
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,249 @@
+//! Signing abstraction allowing the secret keys to be kept outside of the process, e.g. in an HSM, a KMS,
+//! a remote signer or a hardware wallet.
+
+use std::{collections::HashMap, fmt, sync::Arc};
+
+use async_trait::async_trait;
+use tezos_core::{
+    types::encoded::{Encoded, ImplicitAddress, PublicKey, SecretKey, Signature},
+    BlsCryptoProvider, CryptoConfig, CryptoProvider, DefaultCryptoConfig, Tezos,
+};
+
+use crate::{internal::signer::OperationSigner, Error, Result};
+
+/// Trait defining the interface of a signer holding the keys of one or more accounts.
+///
+/// Accounts are identified by their public key hash. The messages to sign are already watermarked
+/// (e.g. `0x03` followed by the forged operation bytes), which matches what remote signers expect.
+#[async_trait]
+pub trait Signer: Sync {
+    /// Returns the public key of the account identified by the given public key hash.
+    async fn public_key(&self, public_key_hash: &ImplicitAddress) -> Result<PublicKey>;
+
+    /// Signs the watermarked message with the key of the account identified by the given public key hash.
+    async fn sign(&self, public_key_hash: &ImplicitAddress, message: &[u8]) -> Result<Signature>;
+}
+
+/// A [Signer] keeping the secret keys in memory.
+///
+/// # Example
+///
+/// ```rust
+/// use tezos_operation::signer::InMemorySigner;
+/// use tezos_core::types::encoded::SecretKey;
+///
+/// let secret_key: SecretKey = "edskRv7VyXGVZb8EsrR7D9XKUbbAQNQGtALP6QeB16ZCD7SmmJpzyeneJVg3Mq56YLbxRA1kSdAXiswwPiaVfR3NHGMCXCziuZ".try_into().unwrap();
+/// let mut signer = InMemorySigner::new();
+/// # #[cfg(feature = "ed25519")]
+/// let public_key_hash = signer.add_key(secret_key).expect("valid key"); // = tz1RFxSHjxxyaaXyAvJG3uRQeJUCCm4jk2LX
+/// ```
+#[derive(Default, Clone)]
+pub struct InMemorySigner {
+    keys: HashMap<String, InMemoryKey>,
+}
+
+/// A key of an [InMemorySigner], along with the crypto config it was added with.
+#[derive(Clone)]
+struct InMemoryKey {
+    secret_key: SecretKey,
+    public_key: PublicKey,
+    crypto_config: Arc<dyn CryptoConfig + Send + Sync>,
+}
+
+impl InMemorySigner {
+    /// Creates a new empty [InMemorySigner].
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Adds the secret key to the signer. Its public key is derived and its signatures are computed with
+    /// the crypto providers of the given config. Returns the public key hash identifying the key.
+    pub fn add_key_with<C>(&mut self, key: SecretKey, crypto_config: C) -> Result<ImplicitAddress>
+    where
+        C: CryptoConfig + Send + Sync + 'static,
+    {
+        let crypto_config: Arc<dyn CryptoConfig + Send + Sync> = Arc::new(crypto_config);
+        let public_key = key.public_key_with(&tezos(&crypto_config))?;
+        let public_key_hash = public_key.public_key_hash()?;
+        self.keys.insert(
+            public_key_hash.value().into(),
+            InMemoryKey {
+                secret_key: key,
+                public_key,
+                crypto_config,
+            },
+        );
+
+        Ok(public_key_hash)
+    }
+
+    /// Adds the secret key to the signer, using the default crypto providers. Returns the public key hash
+    /// identifying the key.
+    pub fn add_key(&mut self, key: SecretKey) -> Result<ImplicitAddress> {
+        self.add_key_with(key, DefaultCryptoConfig)
+    }
+
+    fn get(&self, public_key_hash: &ImplicitAddress) -> Result<&InMemoryKey> {
+        self.keys
+            .get(public_key_hash.value())
+            .ok_or(Error::UnknownPublicKeyHash)
+    }
+}
+
+impl fmt::Debug for InMemorySigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InMemorySigner")
+            .field("keys", &self.keys.keys())
+            .finish()
+    }
+}
+
+#[async_trait]
+impl Signer for InMemorySigner {
+    async fn public_key(&self, public_key_hash: &ImplicitAddress) -> Result<PublicKey> {
+        Ok(self.get(public_key_hash)?.public_key.clone())
+    }
+
+    async fn sign(&self, public_key_hash: &ImplicitAddress, message: &[u8]) -> Result<Signature> {
+        let key = self.get(public_key_hash)?;
+
+        OperationSigner::new(tezos(&key.crypto_config).get_crypto())
+            .sign_message(message, &key.secret_key)
+    }
+}
+
+/// Creates a [Tezos] instance using the shared crypto config.
+fn tezos(crypto_config: &Arc<dyn CryptoConfig + Send + Sync>) -> Tezos {
+    Tezos::new(Box::new(SharedCryptoConfig(crypto_config.clone())))
+}
+
+struct SharedCryptoConfig(Arc<dyn CryptoConfig + Send + Sync>);
+
+impl CryptoConfig for SharedCryptoConfig {
+    fn get_ed25519_crypto_provider(&self) -> Option<Box<dyn CryptoProvider>> {
+        self.0.get_ed25519_crypto_provider()
+    }
+
+    fn get_secp256_k1_crypto_provider(&self) -> Option<Box<dyn CryptoProvider>> {
+        self.0.get_secp256_k1_crypto_provider()
+    }
+
+    fn get_p256_crypto_provider(&self) -> Option<Box<dyn CryptoProvider>> {
+        self.0.get_p256_crypto_provider()
+    }
+
+    fn get_bls12_381_crypto_provider(&self) -> Option<Box<dyn BlsCryptoProvider>> {
+        self.0.get_bls12_381_crypto_provider()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_unknown_public_key_hash() {
+        let signer = InMemorySigner::new();
+        let public_key_hash: ImplicitAddress =
+            "tz1RFxSHjxxyaaXyAvJG3uRQeJUCCm4jk2LX".try_into().unwrap();
+
+        assert!(matches!(
+            signer.public_key(&public_key_hash).await,
+            Err(Error::UnknownPublicKeyHash)
+        ));
+        assert!(matches!(
+            signer.sign(&public_key_hash, &[3]).await,
+            Err(Error::UnknownPublicKeyHash)
+        ));
+    }
+
+    #[cfg(feature = "ed25519")]
+    #[tokio::test]
+    async fn test_sign() -> Result<()> {
+        let mut signer = InMemorySigner::new();
+        let public_key_hash = signer.add_key("edskRv7VyXGVZb8EsrR7D9XKUbbAQNQGtALP6QeB16ZCD7SmmJpzyeneJVg3Mq56YLbxRA1kSdAXiswwPiaVfR3NHGMCXCziuZ".try_into()?)?;
+
+        assert_eq!(
+            public_key_hash.value(),
+            "tz1RFxSHjxxyaaXyAvJG3uRQeJUCCm4jk2LX"
+        );
+        assert_eq!(
+            signer.public_key(&public_key_hash).await?.value(),
+            "edpkttZKC51wemRqL2QxwpMnEKxWnbd35pq47Y6xsCHp5M1f7LN8NP"
+        );
+
+        let operation = crate::operations::UnsignedOperation::new(
+            "BLjg4HU2BwnCgJfRutxJX5rHACzLDxRJes1MXqbXXdxvHWdK3Te".try_into()?,
+            vec![],
+        );
+        let signed = operation
+            .into_signed_operation_using(&signer, &public_key_hash)
+            .await?;
+
+        assert_eq!(
+            signed.signature.value(),
+            "edsigtfLuR4pGGfJwYgWZbWi9JGzjLA8ThhThxqFGC8V6u4WTdS4fM7VFQKoN9jPDLKiAW75PtG1bykpnRa6ozr8m12iKGYCxNd"
+        );
+
+        Ok(())
+    }
+
+    #[cfg(feature = "ed25519")]
+    #[tokio::test]
+    async fn test_sign_with_crypto_config() -> Result<()> {
+        use tezos_core::crypto::default::DefaultEd25519CryptoProvider;
+
+        struct FixedSignatureCryptoProvider;
+
+        impl CryptoProvider for FixedSignatureCryptoProvider {
+            fn sign(&self, _message: &[u8], _secret: &[u8]) -> tezos_core::Result<Vec<u8>> {
+                Ok(vec![7u8; 64])
+            }
+
+            fn verify(
+                &self,
+                _message: &[u8],
+                _signature: &[u8],
+                _public_key: &[u8],
+            ) -> tezos_core::Result<bool> {
+                Ok(true)
+            }
+
+            fn public_key(&self, secret: &[u8]) -> tezos_core::Result<Vec<u8>> {
+                DefaultEd25519CryptoProvider.public_key(secret)
+            }
+        }
+
+        struct FixedSignatureCryptoConfig;
+
+        impl CryptoConfig for FixedSignatureCryptoConfig {
+            fn get_ed25519_crypto_provider(&self) -> Option<Box<dyn CryptoProvider>> {
+                Some(Box::new(FixedSignatureCryptoProvider))
+            }
+
+            fn get_secp256_k1_crypto_provider(&self) -> Option<Box<dyn CryptoProvider>> {
+                None
+            }
+
+            fn get_p256_crypto_provider(&self) -> Option<Box<dyn CryptoProvider>> {
+                None
+            }
+        }
+
+        let mut signer = InMemorySigner::new();
+        let public_key_hash = signer.add_key_with(
+            "edskRv7VyXGVZb8EsrR7D9XKUbbAQNQGtALP6QeB16ZCD7SmmJpzyeneJVg3Mq56YLbxRA1kSdAXiswwPiaVfR3NHGMCXCziuZ".try_into()?,
+            FixedSignatureCryptoConfig,
+        )?;
+        assert_eq!(
+            public_key_hash.value(),
+            "tz1RFxSHjxxyaaXyAvJG3uRQeJUCCm4jk2LX"
+        );
+
+        let signature = signer.sign(&public_key_hash, &[3]).await?;
+        assert_eq!(signature.to_bytes()?, vec![7u8; 64]);
+        assert!(format!("{:?}", signer).contains("tz1RFxSHjxxyaaXyAvJG3uRQeJUCCm4jk2LX"));
+
+        Ok(())
+    }
+}
@@ -1,5 +1,7 @@
-use tezos_core::types::encoded::{Address, BlockHash, ChainId, Encoded, ScriptExprHash};
-use tezos_operation::operations::UnsignedOperation;
+use tezos_core::types::encoded::{
+    Address, BlockHash, ChainId, Encoded, ImplicitAddress, OperationHash, ScriptExprHash,
+};
+use tezos_operation::{operations::UnsignedOperation, signer::Signer};
 
 #[cfg(feature = "http")]
 use crate::http::default::HttpClient;
@@ -281,6 +283,24 @@ impl<HttpClient: Http> TezosRpc<HttpClient> {
         shell_rpc::injection::operation::post(&self.context, signed_operation_contents)
     }
 
+    /// Signs the `operation` with the key of the given account held by the `signer` and injects it in node.
+    ///
+    /// Returns the ID of the operation.
+    pub async fn sign_and_inject_operation<S: Signer + ?Sized>(
+        &self,
+        operation: UnsignedOperation,
+        signer: &S,
+        public_key_hash: &ImplicitAddress,
+    ) -> Result<OperationHash> {
+        let signed_operation = operation
+            .into_signed_operation_using(signer, public_key_hash)
+            .await?;
+
+        self.inject_operation(signed_operation.to_injectable_string()?.as_str())
+            .send()
+            .await
+    }
+
     /// Inject a block in the node and broadcast it.
     ///
     /// The `operations` might be pre-validated using a contextual RPCs
@@ -14,7 +14,7 @@ pub struct SaplingState {
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Diff {
     pub action: DiffAction,
-    pub updates: Vec<Update>,
+    pub updates: Update,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub source: Option<String>,
     pub memo_size: Option<u16>,
@@ -23,7 +23,7 @@ pub struct Diff {
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Update {
     pub commitments_and_ciphertexts: Vec<[CommitmentAndCiphertext; 2]>,
-    pub nullifiers: String,
+    pub nullifiers: Vec<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -42,3 +42,159 @@ pub struct Ciphertext {
     pub payload_out: String,
     pub nonce_out: String,
 }
+
+#[cfg(feature = "sapling")]
+impl Diff {
+    /// Decrypts the ciphertexts of the updates sent to the viewing key, and returns the notes
+    /// along with their commitment.
+    pub fn incoming_notes(
+        &self,
+        viewing_key: &tezos_core::sapling::SaplingViewingKey,
+    ) -> crate::Result<Vec<(String, tezos_core::sapling::SaplingNote)>> {
+        let mut notes = Vec::new();
+        for [commitment, ciphertext] in &self.updates.commitments_and_ciphertexts {
+            if let (
+                CommitmentAndCiphertext::Commitment(commitment),
+                CommitmentAndCiphertext::Ciphertext(ciphertext),
+            ) = (commitment, ciphertext)
+            {
+                if let Some(note) = viewing_key.decrypt(&ciphertext.try_into()?)? {
+                    notes.push((commitment.clone(), note));
+                }
+            }
+        }
+        Ok(notes)
+    }
+}
+
+#[cfg(feature = "sapling")]
+impl TryFrom<&Ciphertext> for tezos_core::sapling::SaplingCiphertext {
+    type Error = crate::Error;
+
+    fn try_from(value: &Ciphertext) -> crate::Result<Self> {
+        fn bytes(value: &str) -> crate::Result<Vec<u8>> {
+            Ok(tezos_core::types::hex_string::HexString::new(value.into())?.to_bytes())
+        }
+        fn array<const N: usize>(value: &str) -> crate::Result<[u8; N]> {
+            bytes(value)?
+                .try_into()
+                .map_err(|_| crate::Error::InvalidConversion)
+        }
+
+        Ok(Self {
+            cv: array(&value.cv)?,
+            epk: array(&value.epk)?,
+            payload_enc: bytes(&value.payload_enc)?,
+            nonce_enc: array(&value.nonce_enc)?,
+            payload_out: bytes(&value.payload_out)?,
+            nonce_out: array(&value.nonce_out)?,
+        })
+    }
+}
+
+#[cfg(all(test, feature = "sapling"))]
+mod tests {
+    use super::*;
+    use crate::models::operation::operation_result::lazy_storage_diff::LazyStorageDiff;
+    use tezos_core::{
+        sapling::SaplingCiphertext,
+        types::{encoded::SaplingSpendingKey, hex_string::HexString},
+    };
+
+    // A shielding of 1000000 mutez with the memo `memo` and of 5 mutez, to the first addresses
+    // of the keys of the seeds `[1; 32]` and `[2; 32]`.
+    fn sapling_state() -> SaplingState {
+        let value = serde_json::json!({
+            "kind": "sapling_state",
+            "id": "14",
+            "diff": {
+                "action": "update",
+                "updates": {
+                    "commitments_and_ciphertexts": [
+                        [
+                            "1f311391190abde5319baa90e333213b58d1304f2ef4a97ef3e6aec3928a5e3f",
+                            {
+                            "cv": "cfbb1a5d08474ed6981d3895ded7482d8f147fcae4ebb49cde96b8be17bdf7c0",
+                            "epk": "3db398ad2fc088e95013f2d5c5725cf4b0fdbd31c0940ef0280338f3ce8e30d4",
+                            "payload_enc": "03667c089c41d47d8d69dae034a5898069d55b616feb8cd3f69ecac7740542da81827b5ea9faaf89c6e5eb3d99f6fabb34a038640109089843e54f6eda7c8154ea51f849c47c12cfdd9a5cc181b0cd",
+                            "nonce_enc": "65e55d52c52ec1c3eeca58614780487baae13145cf77820f",
+                            "payload_out": "334e0222ff092a707fa70f68ca3f927c7d4a8472b56c8447ba9db69752cb3650fa956b78cd4d6d3f80cdbdaa8f5536b8e3b1b3c68c55a781402f291e725b6ffb4b5877607ec0994b6d932d0456f71dfe",
+                            "nonce_out": "2eb509a0dea2227104fa23787e5dbc120cb680c69b930c09"
+                            }
+                        ],
+                        [
+                            "c03ee0b5a7e2246f46fa8433dded747ce0beedf2b458a7b81813771c668b2444",
+                            {
+                            "cv": "587c429bd77ef476ff536cc41df5233851b3719c05ef2d2cdde8ac3c3a6ef68f",
+                            "epk": "c075ece13eff1055606cf329a94d6a1488909239a2c9dcb1874aca67188e2c93",
+                            "payload_enc": "10daf88937aa0aba2d78de153200277c14d5dfc884e053a4f2983000bc2e33dab2159e4204905292ac49993eea615a71f398dfe5f68dc6f5f73f26cc3f99d6eeba3bb9e9dea44a3b3787495351cac6",
+                            "nonce_enc": "d5003fd8059d8e016e8b3ecde74ae42a1c4b0b2df3897f16",
+                            "payload_out": "5be4c4426092b6c28fcc1e964dd108d4be219a50b9c1e90a04cc693da03a88bb74141156781ce27a0003fbe5d9d582c6ea6a49e606eaff6a518730ef5cb3d89f388f10442fec766c0a481fbca7b7bdf8",
+                            "nonce_out": "c362a87526cd0bb6ddea08648aed7d39682c90138a9e2e32"
+                            }
+                        ]
+                    ],
+                    "nullifiers": []
+                }
+            }
+        });
+        match serde_json::from_value::<LazyStorageDiff>(value).unwrap() {
+            LazyStorageDiff::SaplingState(sapling_state) => sapling_state,
+            diff => panic!("unexpected diff {:?}", diff),
+        }
+    }
+
+    #[test]
+    fn test_incoming_notes() -> crate::Result<()> {
+        let diff = sapling_state().diff;
+        let viewing_key = SaplingSpendingKey::from_seed(&[1u8; 32])?.viewing_key()?;
+        let notes = diff.incoming_notes(&viewing_key)?;
+        assert_eq!(notes.len(), 1);
+        let (commitment, note) = &notes[0];
+        assert_eq!(
+            commitment,
+            "1f311391190abde5319baa90e333213b58d1304f2ef4a97ef3e6aec3928a5e3f"
+        );
+        assert_eq!(note.address, viewing_key.address(0)?.0);
+        assert_eq!(note.amount, 1_000_000);
+        assert_eq!(note.memo, b"memo\0\0\0\0");
+
+        let viewing_key = SaplingSpendingKey::from_seed(&[2u8; 32])?.viewing_key()?;
+        let notes = diff.incoming_notes(&viewing_key)?;
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0].1.amount, 5);
+        assert_eq!(notes[0].1.memo, [0u8; 8]);
+
+        let viewing_key = SaplingSpendingKey::from_seed(&[3u8; 32])?.viewing_key()?;
+        assert!(diff.incoming_notes(&viewing_key)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_ciphertext_conversion() -> crate::Result<()> {
+        let diff = sapling_state().diff;
+        let ciphertext = match &diff.updates.commitments_and_ciphertexts[0][1] {
+            CommitmentAndCiphertext::Ciphertext(ciphertext) => ciphertext.clone(),
+            value => panic!("unexpected value {:?}", value),
+        };
+        let hex = |bytes: &[u8]| HexString::from_bytes(bytes).into_string(false);
+        let converted: SaplingCiphertext = (&ciphertext).try_into()?;
+        assert_eq!(hex(&converted.cv), ciphertext.cv);
+        assert_eq!(hex(&converted.epk), ciphertext.epk);
+        assert_eq!(hex(&converted.payload_enc), ciphertext.payload_enc);
+        assert_eq!(hex(&converted.nonce_enc), ciphertext.nonce_enc);
+        assert_eq!(hex(&converted.payload_out), ciphertext.payload_out);
+        assert_eq!(hex(&converted.nonce_out), ciphertext.nonce_out);
+
+        let mut invalid = ciphertext.clone();
+        invalid.nonce_enc.truncate(46);
+        assert!(matches!(
+            SaplingCiphertext::try_from(&invalid),
+            Err(crate::Error::InvalidConversion)
+        ));
+        let mut invalid = ciphertext;
+        invalid.epk = "zz".repeat(32);
+        assert!(SaplingCiphertext::try_from(&invalid).is_err());
+        Ok(())
+    }
+}
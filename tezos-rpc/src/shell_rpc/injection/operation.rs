@@ -85,7 +85,14 @@ pub fn post<'a, HttpClient: Http>(
 
 #[cfg(all(test, feature = "http"))]
 mod tests {
-    use tezos_core::types::encoded::{Encoded, OperationHash};
+    use async_trait::async_trait;
+    use tezos_core::types::encoded::{
+        Encoded, ImplicitAddress, OperationHash, PublicKey, Signature,
+    };
+    use tezos_operation::{
+        operations::{SignedOperation, UnsignedOperation},
+        signer::Signer,
+    };
     use {crate::client::TezosRpc, crate::error::Error, httpmock::prelude::*};
 
     #[tokio::test]
@@ -120,4 +127,69 @@ mod tests {
 
         Ok(())
     }
+
+    struct FixedSigner(Signature);
+
+    #[async_trait]
+    impl Signer for FixedSigner {
+        async fn public_key(
+            &self,
+            _public_key_hash: &ImplicitAddress,
+        ) -> tezos_operation::Result<PublicKey> {
+            Err(tezos_operation::Error::UnknownPublicKeyHash)
+        }
+
+        async fn sign(
+            &self,
+            _public_key_hash: &ImplicitAddress,
+            message: &[u8],
+        ) -> tezos_operation::Result<Signature> {
+            assert_eq!(message[0], 3);
+            Ok(self.0.clone())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sign_and_inject_operation() -> Result<(), Error> {
+        let server = MockServer::start();
+        let rpc_url = server.base_url();
+
+        let operation_hash: OperationHash = "ooG169iWhv7vQccPGcB2EWeAjFWvxcrmQVCi4eWCviUTHeQuH24"
+            .try_into()
+            .unwrap();
+        let operation = UnsignedOperation::new(
+            "BLjg4HU2BwnCgJfRutxJX5rHACzLDxRJes1MXqbXXdxvHWdK3Te".try_into()?,
+            vec![],
+        );
+        let signature: Signature = "edsigtfLuR4pGGfJwYgWZbWi9JGzjLA8ThhThxqFGC8V6u4WTdS4fM7VFQKoN9jPDLKiAW75PtG1bykpnRa6ozr8m12iKGYCxNd".try_into()?;
+        let signed_operation_contents = SignedOperation::new(
+            operation.branch.clone(),
+            operation.contents.clone(),
+            signature.clone(),
+        )
+        .to_injectable_string()?;
+
+        server.mock(|when, then| {
+            when.method(POST)
+                .path(super::path())
+                .query_param("chain", "main")
+                .json_body(signed_operation_contents.as_str());
+            then.status(200)
+                .header("content-type", "application/json")
+                .json_body(operation_hash.value());
+        });
+
+        let client = TezosRpc::new(rpc_url);
+        let op_hash = client
+            .sign_and_inject_operation(
+                operation,
+                &FixedSigner(signature),
+                &"tz1RFxSHjxxyaaXyAvJG3uRQeJUCCm4jk2LX".try_into()?,
+            )
+            .await?;
+
+        assert_eq!(operation_hash, op_hash);
+
+        Ok(())
+    }
 }